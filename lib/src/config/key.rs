@@ -36,6 +36,8 @@ pub struct Keys {
     pub global_help: BindingForEvent,
     pub global_player_seek_forward: BindingForEvent,
     pub global_player_seek_backward: BindingForEvent,
+    pub global_player_next_chapter: BindingForEvent,
+    pub global_player_previous_chapter: BindingForEvent,
     pub global_lyric_adjust_forward: BindingForEvent,
     pub global_lyric_adjust_backward: BindingForEvent,
     pub global_player_speed_up: BindingForEvent,
@@ -47,6 +49,9 @@ pub struct Keys {
     pub global_config_open: BindingForEvent,
     pub global_save_playlist: BindingForEvent,
     pub global_layout_podcast: BindingForEvent,
+    pub global_layout_browse: BindingForEvent,
+    pub global_lyric_toggle_scroll: BindingForEvent,
+    pub global_lyric_seek_line: BindingForEvent,
     pub global_xywh_move_left: BindingForEvent,
     pub global_xywh_move_right: BindingForEvent,
     pub global_xywh_move_up: BindingForEvent,
@@ -64,6 +69,7 @@ pub struct Keys {
     pub library_switch_root: BindingForEvent,
     pub library_add_root: BindingForEvent,
     pub library_remove_root: BindingForEvent,
+    pub library_organize: BindingForEvent,
     pub playlist_delete: BindingForEvent,
     pub playlist_delete_all: BindingForEvent,
     pub playlist_shuffle: BindingForEvent,
@@ -75,6 +81,7 @@ pub struct Keys {
     pub playlist_cmus_lqueue: BindingForEvent,
     pub playlist_cmus_tqueue: BindingForEvent,
     pub database_add_all: BindingForEvent,
+    pub database_stats: BindingForEvent,
     pub config_save: BindingForEvent,
     pub podcast_mark_played: BindingForEvent,
     pub podcast_mark_all_played: BindingForEvent,
@@ -82,7 +89,13 @@ pub struct Keys {
     pub podcast_episode_delete_file: BindingForEvent,
     pub podcast_delete_feed: BindingForEvent,
     pub podcast_delete_all_feeds: BindingForEvent,
+    pub podcast_feed_policy: BindingForEvent,
     pub podcast_search_add_feed: BindingForEvent,
+    pub podcast_search_transcripts: BindingForEvent,
+    pub podcast_queue_feed: BindingForEvent,
+    pub podcast_queue_show: BindingForEvent,
+    pub podcast_feed_filter: BindingForEvent,
+    pub podcast_download_failed: BindingForEvent,
     pub podcast_refresh_feed: BindingForEvent,
     pub podcast_refresh_all_feeds: BindingForEvent,
 }
@@ -108,6 +121,8 @@ impl Keys {
             .chain(once(self.global_help))
             .chain(once(self.global_player_seek_forward))
             .chain(once(self.global_player_seek_backward))
+            .chain(once(self.global_player_next_chapter))
+            .chain(once(self.global_player_previous_chapter))
             .chain(once(self.global_lyric_adjust_forward))
             .chain(once(self.global_lyric_adjust_backward))
             .chain(once(self.global_player_speed_up))
@@ -119,6 +134,9 @@ impl Keys {
             .chain(once(self.global_config_open))
             .chain(once(self.global_save_playlist))
             .chain(once(self.global_layout_podcast))
+            .chain(once(self.global_layout_browse))
+            .chain(once(self.global_lyric_toggle_scroll))
+            .chain(once(self.global_lyric_seek_line))
             .chain(once(self.global_xywh_move_left))
             .chain(once(self.global_xywh_move_right))
             .chain(once(self.global_xywh_move_up))
@@ -140,6 +158,7 @@ impl Keys {
             .chain(once(self.library_switch_root))
             .chain(once(self.library_add_root))
             .chain(once(self.library_remove_root))
+            .chain(once(self.library_organize))
     }
 
     fn iter_playlist(&self) -> impl Iterator<Item = BindingForEvent> {
@@ -161,6 +180,11 @@ impl Keys {
             .chain(once(self.podcast_refresh_all_feeds))
             .chain(once(self.podcast_delete_feed))
             .chain(once(self.podcast_delete_all_feeds))
+            .chain(once(self.podcast_feed_policy))
+            .chain(once(self.podcast_queue_feed))
+            .chain(once(self.podcast_queue_show))
+            .chain(once(self.podcast_feed_filter))
+            .chain(once(self.podcast_download_failed))
     }
 
     fn iter_episode(&self) -> impl Iterator<Item = BindingForEvent> {
@@ -168,6 +192,10 @@ impl Keys {
             .chain(once(self.podcast_mark_all_played))
             .chain(once(self.podcast_episode_download))
             .chain(once(self.podcast_episode_delete_file))
+            .chain(once(self.podcast_search_transcripts))
+            .chain(once(self.podcast_queue_show))
+            .chain(once(self.podcast_feed_filter))
+            .chain(once(self.podcast_download_failed))
     }
 
     pub fn has_unique_elements(&self) -> bool {
@@ -412,6 +440,14 @@ impl Default for Keys {
                 code: Key::Char('b'),
                 modifier: KeyModifiers::NONE,
             },
+            global_player_next_chapter: BindingForEvent {
+                code: Key::Char(']'),
+                modifier: KeyModifiers::NONE,
+            },
+            global_player_previous_chapter: BindingForEvent {
+                code: Key::Char('['),
+                modifier: KeyModifiers::NONE,
+            },
             global_player_speed_up: BindingForEvent {
                 code: Key::Char('f'),
                 modifier: KeyModifiers::CONTROL,
@@ -513,6 +549,10 @@ impl Default for Keys {
                 code: Key::Char('L'),
                 modifier: KeyModifiers::SHIFT,
             },
+            database_stats: BindingForEvent {
+                code: Key::Char('I'),
+                modifier: KeyModifiers::SHIFT,
+            },
             global_player_toggle_gapless: BindingForEvent {
                 code: Key::Char('g'),
                 modifier: KeyModifiers::CONTROL,
@@ -537,6 +577,10 @@ impl Default for Keys {
                 code: Key::Char('A'),
                 modifier: KeyModifiers::SHIFT,
             },
+            library_organize: BindingForEvent {
+                code: Key::Char('O'),
+                modifier: KeyModifiers::SHIFT,
+            },
             global_save_playlist: BindingForEvent {
                 code: Key::Char('s'),
                 modifier: KeyModifiers::CONTROL,
@@ -545,6 +589,18 @@ impl Default for Keys {
                 code: Key::Char('3'),
                 modifier: KeyModifiers::NONE,
             },
+            global_layout_browse: BindingForEvent {
+                code: Key::Char('4'),
+                modifier: KeyModifiers::NONE,
+            },
+            global_lyric_toggle_scroll: BindingForEvent {
+                code: Key::Char('V'),
+                modifier: KeyModifiers::SHIFT,
+            },
+            global_lyric_seek_line: BindingForEvent {
+                code: Key::Enter,
+                modifier: KeyModifiers::NONE,
+            },
             podcast_search_add_feed: BindingForEvent {
                 code: Key::Char('s'),
                 modifier: KeyModifiers::NONE,
@@ -581,6 +637,30 @@ impl Default for Keys {
                 code: Key::Char('D'),
                 modifier: KeyModifiers::SHIFT,
             },
+            podcast_feed_policy: BindingForEvent {
+                code: Key::Char('p'),
+                modifier: KeyModifiers::NONE,
+            },
+            podcast_search_transcripts: BindingForEvent {
+                code: Key::Char('/'),
+                modifier: KeyModifiers::NONE,
+            },
+            podcast_queue_feed: BindingForEvent {
+                code: Key::Char('u'),
+                modifier: KeyModifiers::NONE,
+            },
+            podcast_queue_show: BindingForEvent {
+                code: Key::Char('U'),
+                modifier: KeyModifiers::SHIFT,
+            },
+            podcast_feed_filter: BindingForEvent {
+                code: Key::Char('v'),
+                modifier: KeyModifiers::NONE,
+            },
+            podcast_download_failed: BindingForEvent {
+                code: Key::Char('X'),
+                modifier: KeyModifiers::SHIFT,
+            },
             global_xywh_move_left: BindingForEvent {
                 code: Key::Left,
                 modifier: CONTROL_SHIFT,
@@ -24,6 +24,7 @@
 mod key;
 mod theme;
 
+use crate::organize::DEFAULT_PATTERN;
 use crate::utils::get_app_config_path;
 use anyhow::{bail, Result};
 use figment::{
@@ -240,6 +241,65 @@ impl Xywh {
     }
 }
 
+#[derive(Clone, Deserialize, Serialize)]
+pub struct LyricProviderSettings {
+    pub name: String,
+    pub enabled: bool,
+    /// Search results arriving later than this are dropped
+    pub timeout_secs: u64,
+}
+
+/// Refresh interval of one podcast feed, overriding `podcast_refresh_mins`
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PodcastRefreshFeed {
+    pub url: String,
+    /// 0 never refreshes the feed in the background
+    pub mins: u64,
+}
+
+/// A podcast directory searched when adding a feed by name
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PodcastDirectorySettings {
+    /// `itunes`, `podcastindex` or `opml`
+    pub name: String,
+    pub enabled: bool,
+    /// Results arriving later than this are dropped
+    pub timeout_secs: u64,
+    /// Key and secret from api.podcastindex.org, needed by `podcastindex`
+    #[serde(default)]
+    pub api_key: String,
+    #[serde(default)]
+    pub api_secret: String,
+    /// OPML file searched by `opml`
+    #[serde(default)]
+    pub path: String,
+}
+
+/// Account on gpodder.net or a compatible server that subscriptions and episode progress
+/// are synced with
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PodcastSyncSettings {
+    /// Like `https://gpodder.net`, empty turns syncing off
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    /// Name this device has on the server
+    pub device: String,
+    /// Minutes between syncs by the server
+    pub mins: u64,
+    pub timeout_secs: u64,
+}
+
+/// Where builds with the `fingerprint` feature look up songs by how they sound
+#[derive(Clone, Deserialize, Serialize)]
+pub struct FingerprintSettings {
+    /// `AcoustID` lookup endpoint, can point to a local server for testing
+    pub url: String,
+    /// Application key from acoustid.org, no lookups are made while empty
+    pub api_key: String,
+    pub timeout_secs: u64,
+}
+
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SeekStep {
     Short,
@@ -295,7 +355,15 @@ pub struct Settings {
     pub player_gapless: bool,
     pub podcast_simultanious_download: usize,
     pub podcast_max_retries: usize,
+    /// KiB per second shared by all episode downloads, 0 is no limit
+    pub podcast_download_speed_limit: u64,
     pub podcast_dir: String,
+    /// Minutes between background refreshes of podcast feeds by the server, 0 turns them off
+    pub podcast_refresh_mins: u64,
+    pub podcast_refresh_feeds: Vec<PodcastRefreshFeed>,
+    /// Directories searched in parallel when adding a podcast by name
+    pub podcast_directories: Vec<PodcastDirectorySettings>,
+    pub podcast_sync: PodcastSyncSettings,
     pub player_seek_step: SeekStep,
     pub player_remember_last_played_position: LastPosition,
     pub enable_exit_confirmation: bool,
@@ -307,6 +375,30 @@ pub struct Settings {
     pub player_use_mpris: bool,
     pub player_use_discord: bool,
     pub album_photo_xywh: Xywh,
+    /// Tracks whose durations differ by at most this many seconds can be duplicates
+    pub library_duplicate_duration_tolerance: u64,
+    /// Also compare a hash of the decoded audio when looking for duplicates. Slow.
+    pub library_duplicate_compare_audio: bool,
+    /// Where `organize` moves tracks, relative to their music root. Placeholders are
+    /// `{artist}`, `{albumartist}`, `{album}`, `{title}`, `{genre}`, `{composer}`, `{year}`,
+    /// `{track}` and `{disc}`, and numbers can be padded like `{track:02}`.
+    pub library_organize_pattern: String,
+    /// Extra folder searched for `song.lrc` files, besides the folder of the song
+    pub lyric_sidecar_dir: String,
+    /// Lyric panel scrolls through the whole lyric instead of showing the lines around the
+    /// one being sung
+    pub lyric_scroll: bool,
+    /// Tag editor writes downloaded lyrics to a `.lrc` file and leaves the song untouched
+    pub lyric_save_to_sidecar: bool,
+    /// Where the tag editor looks for lyrics and covers, searched in this order
+    pub lyric_providers: Vec<LyricProviderSettings>,
+    /// Pattern the tag editor starts with when reading tags from file names, like
+    /// `{artist} - {title}`. Empty tries the usual layouts of downloaded files.
+    pub tag_editor_filename_pattern: String,
+    pub tag_editor_fingerprint: FingerprintSettings,
+    /// Covers larger than this many pixels are scaled down before the tag editor embeds
+    /// them. 0 keeps them as they are.
+    pub tag_editor_cover_max_size: u32,
     pub style_color_symbol: StyleColorSymbol,
     pub keys: Keys,
 }
@@ -339,11 +431,54 @@ impl Default for Settings {
             podcast_simultanious_download: 3,
             podcast_dir: PODCAST_DIR.to_string(),
             podcast_max_retries: 3,
+            podcast_download_speed_limit: 0,
+            podcast_refresh_mins: 360,
+            podcast_refresh_feeds: vec![],
+            podcast_directories: ["itunes", "podcastindex", "opml"]
+                .iter()
+                .map(|name| PodcastDirectorySettings {
+                    name: (*name).to_string(),
+                    enabled: true,
+                    timeout_secs: 10,
+                    api_key: String::new(),
+                    api_secret: String::new(),
+                    path: String::new(),
+                })
+                .collect(),
+            podcast_sync: PodcastSyncSettings {
+                url: String::new(),
+                username: String::new(),
+                password: String::new(),
+                device: "termusic".to_string(),
+                mins: 30,
+                timeout_secs: 30,
+            },
             player_seek_step: SeekStep::Auto,
             kill_daemon_when_quit: true,
             player_use_mpris: true,
             player_use_discord: true,
             player_port: 50101,
+            library_duplicate_duration_tolerance: 3,
+            library_duplicate_compare_audio: false,
+            library_organize_pattern: DEFAULT_PATTERN.to_string(),
+            lyric_sidecar_dir: String::new(),
+            lyric_scroll: false,
+            lyric_save_to_sidecar: false,
+            lyric_providers: ["netease", "migu", "kugou"]
+                .iter()
+                .map(|name| LyricProviderSettings {
+                    name: (*name).to_string(),
+                    enabled: true,
+                    timeout_secs: 10,
+                })
+                .collect(),
+            tag_editor_filename_pattern: String::new(),
+            tag_editor_fingerprint: FingerprintSettings {
+                url: "https://api.acoustid.org/v2/lookup".to_string(),
+                api_key: String::new(),
+                timeout_secs: 10,
+            },
+            tag_editor_cover_max_size: 1000,
         }
     }
 }
@@ -0,0 +1,164 @@
+/**
+ * MIT License
+ *
+ * termusic - Copyright (c) 2021 Larry Hao
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// album covers from the song folder, and preparing image files for embedding
+use anyhow::{bail, Result};
+use image::imageops::FilterType;
+use image::ImageOutputFormat;
+use lofty::{MimeType, Picture, PictureType};
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// File names of folder covers, preferred in this order
+const NAMES: [&str; 5] = ["cover", "folder", "front", "album", "albumart"];
+const EXTENSIONS: [&str; 3] = ["jpg", "jpeg", "png"];
+
+/// Whether `path` is a picture a folder cover can be, whatever the case of its extension
+pub(crate) fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(OsStr::to_str)
+        .map_or(false, |ext| {
+            EXTENSIONS.contains(&ext.to_lowercase().as_str())
+        })
+}
+
+/// Picture file for the songs in `dir`: `cover.jpg`, `Folder.png` and the like first, any
+/// other image otherwise.
+pub fn find_in_dir(dir: &Path) -> Option<PathBuf> {
+    let mut images: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_image(path))
+        .collect();
+    images.sort();
+    images.into_iter().min_by_key(|path| {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        NAMES.iter().position(|n| *n == stem).unwrap_or(NAMES.len())
+    })
+}
+
+/// Read an image file as front cover, scaled down to fit `max_size` pixels.
+pub fn load(path: &Path, max_size: u32) -> Result<Picture> {
+    let mut picture = Picture::from_reader(&mut File::open(path)?)?;
+    picture.set_pic_type(PictureType::CoverFront);
+    downscale(picture, max_size)
+}
+
+/// Covers wider or higher than `max_size` pixels are scaled down and stored as jpeg. With
+/// `max_size` 0 they are left as they are.
+pub fn downscale(picture: Picture, max_size: u32) -> Result<Picture> {
+    if max_size == 0 {
+        return Ok(picture);
+    }
+    let image = image::load_from_memory(picture.data())?;
+    if image.width() <= max_size && image.height() <= max_size {
+        return Ok(picture);
+    }
+    let mut data = Cursor::new(vec![]);
+    image
+        .resize(max_size, max_size, FilterType::Lanczos3)
+        .to_rgb8()
+        .write_to(&mut data, ImageOutputFormat::Jpeg(90))?;
+    Ok(Picture::new_unchecked(
+        picture.pic_type(),
+        MimeType::Jpeg,
+        picture.description().map(ToString::to_string),
+        data.into_inner(),
+    ))
+}
+
+/// Usual file extension for the picture
+pub fn extension(picture: &Picture) -> &'static str {
+    match picture.mime_type() {
+        MimeType::Png => "png",
+        MimeType::Tiff => "tiff",
+        MimeType::Bmp => "bmp",
+        MimeType::Gif => "gif",
+        _ => "jpg",
+    }
+}
+
+/// Write an embedded cover to an image file. Existing files are not overwritten.
+pub fn extract(picture: &Picture, path: &Path) -> Result<()> {
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+    std::fs::write(path, picture.data())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{downscale, find_in_dir, load};
+    use image::{ImageOutputFormat, RgbImage};
+    use lofty::{MimeType, PictureType};
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_find_in_dir() {
+        let dir = std::env::temp_dir().join("termusic_cover_test_find");
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["a.png", "song.mp3", "cover.txt"] {
+            fs::write(dir.join(name), b"").unwrap();
+        }
+        let first = find_in_dir(&dir);
+        fs::write(dir.join("Folder.JPG"), b"").unwrap();
+        let named = find_in_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first, Some(dir.join("a.png")));
+        assert_eq!(named, Some(dir.join("Folder.JPG")));
+    }
+
+    #[test]
+    fn test_downscale() {
+        let mut png = Cursor::new(vec![]);
+        RgbImage::new(40, 20)
+            .write_to(&mut png, ImageOutputFormat::Png)
+            .unwrap();
+        let path = std::env::temp_dir().join("termusic_cover_test_downscale.png");
+        fs::write(&path, png.into_inner()).unwrap();
+        let kept = load(&path, 0);
+        let small = load(&path, 10);
+        fs::remove_file(&path).unwrap();
+
+        let kept = kept.unwrap();
+        assert_eq!(kept.pic_type(), PictureType::CoverFront);
+        assert_eq!(kept.mime_type(), &MimeType::Png);
+        let small = small.unwrap();
+        assert_eq!(small.mime_type(), &MimeType::Jpeg);
+        let image = image::load_from_memory(small.data()).unwrap();
+        assert_eq!((image.width(), image.height()), (10, 5));
+        // small enough already
+        let same = downscale(kept, 40).unwrap();
+        assert_eq!(same.mime_type(), &MimeType::Png);
+    }
+}
@@ -0,0 +1,178 @@
+/**
+ * MIT License
+ *
+ * termusic - Copyright (c) 2021 Larry Hao
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// duplicate detection for library.db
+use crate::sqlite::TrackForDB;
+use crate::utils::{decode_audio, get_pin_yin};
+use anyhow::Result;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+lazy_static! {
+    /// Bracketed suffixes like "(Official Video)" or "[HD]" that yt-dlp titles carry
+    static ref RE_BRACKETS: Regex = Regex::new(r"[\(\[\{][^\)\]\}]*[\)\]\}]").expect("Regex error");
+
+    /// Featured artists are often only present in some copies of a song
+    static ref RE_FEAT: Regex = Regex::new(r"(?i)\b(feat|ft|featuring)\b.*$").expect("Regex error");
+}
+
+/// Lowercase, drop bracketed and "feat." parts, and keep only letters and digits, so that
+/// "Song (Official Video)" and "song" compare equal.
+pub fn normalize(input: &str) -> String {
+    let without_brackets = RE_BRACKETS.replace_all(input, " ");
+    let without_feat = RE_FEAT.replace(&without_brackets, " ");
+    get_pin_yin(&without_feat)
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Group tracks with the same normalized artist and title whose durations are within
+/// `tolerance` of each other. Only groups with more than one track are returned.
+pub fn group_tracks(records: Vec<TrackForDB>, tolerance: Duration) -> Vec<Vec<TrackForDB>> {
+    let mut by_name: HashMap<(String, String), Vec<TrackForDB>> = HashMap::new();
+    for record in records {
+        let key = (normalize(&record.artist), normalize(&record.title));
+        by_name.entry(key).or_default().push(record);
+    }
+
+    let mut groups = vec![];
+    for (_, mut candidates) in by_name {
+        if candidates.len() < 2 {
+            continue;
+        }
+        candidates.sort_by_key(|r| r.duration);
+        let mut group: Vec<TrackForDB> = vec![];
+        for record in candidates {
+            if let Some(last) = group.last() {
+                if record.duration - last.duration > tolerance {
+                    if group.len() > 1 {
+                        groups.push(std::mem::take(&mut group));
+                    }
+                    group.clear();
+                }
+            }
+            group.push(record);
+        }
+        if group.len() > 1 {
+            groups.push(group);
+        }
+    }
+
+    groups.sort_by_cached_key(|g| {
+        g.first()
+            .map(|r| get_pin_yin(&format!("{}{}", r.artist, r.title)))
+    });
+    groups
+}
+
+/// Split every group further by a hash of the decoded audio, dropping files that cannot
+/// be decoded. This catches re-tagged copies and tells apart different recordings that
+/// happen to share a name.
+pub fn refine_by_audio_hash(groups: Vec<Vec<TrackForDB>>) -> Vec<Vec<TrackForDB>> {
+    let mut result = vec![];
+    for group in groups {
+        let mut by_hash: HashMap<String, Vec<TrackForDB>> = HashMap::new();
+        for record in group {
+            match audio_hash(Path::new(&record.file)) {
+                Ok(hash) => by_hash.entry(hash).or_default().push(record),
+                Err(e) => warn!("cannot hash {}: {e}", record.file),
+            }
+        }
+        result.extend(by_hash.into_values().filter(|g| g.len() > 1));
+    }
+    result
+}
+
+/// MD5 of the decoded 16 bit samples of the default track, ignoring all tags.
+pub fn audio_hash(path: &Path) -> Result<String> {
+    let mut context = md5::Context::new();
+    decode_audio::<i16>(path, |samples, _| {
+        for sample in samples {
+            context.consume(sample.to_le_bytes());
+        }
+    })?;
+    Ok(format!("{:x}", context.compute()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{group_tracks, normalize};
+    use crate::sqlite::TrackForDB;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn record(artist: &str, title: &str, secs: u64, file: &str) -> TrackForDB {
+        TrackForDB {
+            id: 0,
+            artist: artist.to_string(),
+            title: title.to_string(),
+            album: String::new(),
+            genre: String::new(),
+            file: file.to_string(),
+            duration: Duration::from_secs(secs),
+            name: String::new(),
+            ext: String::new(),
+            directory: String::new(),
+            last_modified: String::new(),
+            last_position: Duration::from_secs(0),
+            album_artist: String::new(),
+            year: None,
+            track_number: None,
+            disc_number: None,
+            composer: String::new(),
+            comment: String::new(),
+            bitrate: None,
+            sample_rate: None,
+            file_size: 0,
+            has_picture: false,
+            has_lyrics: false,
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("Hello (Official Video)"), "hello");
+        assert_eq!(normalize("Hello [HD] feat. Someone"), "hello");
+        assert_eq!(normalize("  Don't   Stop! "), "don t stop");
+    }
+
+    #[test]
+    fn test_group_tracks() {
+        let records = vec![
+            record("Adele", "Hello", 295, "a.mp3"),
+            record("ADELE", "Hello (Official Video)", 297, "b.opus"),
+            record("Adele", "Hello", 340, "live.mp3"),
+            record("Adele", "Skyfall", 286, "c.mp3"),
+        ];
+        let groups = group_tracks(records, Duration::from_secs(3));
+        assert_eq!(groups.len(), 1);
+        let files: Vec<&str> = groups[0].iter().map(|r| r.file.as_str()).collect();
+        assert_eq!(files, vec!["a.mp3", "b.opus"]);
+    }
+}
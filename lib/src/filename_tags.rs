@@ -0,0 +1,254 @@
+/**
+ * MIT License
+ *
+ * termusic - Copyright (c) 2021 Larry Hao
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// reading tags out of file names, mostly for downloads that come without proper tags
+use crate::tag_batch::TagField;
+use anyhow::{bail, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::Path;
+
+lazy_static! {
+    /// Bracketed parts that say what the upload is rather than what the song is, like
+    /// "(Official Video)", "[HD]" or "(Lyric Video)"
+    static ref RE_NOISE: Regex = Regex::new(
+        r"(?i)\s*[\(\[\{][^\)\]\}]*\b(official|video|audio|lyrics?|visuali[sz]er|hd|hq|4k|mv|m/v)\b[^\)\]\}]*[\)\]\}]"
+    )
+    .expect("Regex error");
+
+    /// The video id youtube-dl appends, "[dQw4w9WgXcQ]"
+    static ref RE_VIDEO_ID: Regex = Regex::new(r"\s*\[[A-Za-z0-9_-]{11}\]").expect("Regex error");
+
+    static ref RE_SPACES: Regex = Regex::new(r"\s+").expect("Regex error");
+
+    /// Tried in order when there is no pattern of the user
+    static ref RE_BUILTIN: Vec<Regex> = [
+        // 01 - Artist - Title
+        r"^(?P<track>\d{1,3})\s*[.\-_]\s*(?P<artist>.+?)\s+[-–—]\s+(?P<title>.+)$",
+        // Artist - Title
+        r"^(?P<artist>.+?)\s+[-–—]\s+(?P<title>.+)$",
+        // 01. Title
+        r"^(?P<track>\d{1,3})\s*[.\-_]?\s+(?P<title>.+)$",
+        r"^(?P<title>.+)$",
+    ]
+    .iter()
+    .map(|re| Regex::new(re).expect("Regex error"))
+    .collect();
+}
+
+const FIELDS: [(&str, TagField); 9] = [
+    ("artist", TagField::Artist),
+    ("title", TagField::Title),
+    ("album", TagField::Album),
+    ("albumartist", TagField::AlbumArtist),
+    ("genre", TagField::Genre),
+    ("composer", TagField::Composer),
+    ("year", TagField::Year),
+    ("track", TagField::Track),
+    ("disc", TagField::Disc),
+];
+
+/// Drop "(Official Video)"-style parts and video ids, and tidy up the spaces.
+/// Underscores count as spaces in names that have none.
+pub fn strip_noise(name: &str) -> String {
+    let name = if name.contains(' ') {
+        name.to_string()
+    } else {
+        name.replace('_', " ")
+    };
+    let name = RE_VIDEO_ID.replace_all(&name, "");
+    let name = RE_NOISE.replace_all(&name, "");
+    RE_SPACES.replace_all(name.trim(), " ").to_string()
+}
+
+pub struct FilenameParser {
+    rules: Vec<Regex>,
+    /// Number of path components the rules match, 1 for the file name alone
+    depth: usize,
+}
+
+impl FilenameParser {
+    /// `pattern` uses the placeholders of `organize`, like `{artist} - {title}` or
+    /// `{artist}/{album}/{track} {title}` to read folders too. `{ignore}` matches anything.
+    /// An empty pattern tries the common layouts of downloaded files.
+    pub fn new(pattern: &str) -> Result<Self> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Ok(Self {
+                rules: RE_BUILTIN.clone(),
+                depth: 1,
+            });
+        }
+
+        let mut re = String::from("^");
+        let mut rest = pattern;
+        while let Some(start) = rest.find('{') {
+            re.push_str(&literal(&rest[..start]));
+            let Some(len) = rest[start..].find('}') else {
+                bail!("unmatched {{ in pattern");
+            };
+            let name = rest[start + 1..start + len].trim();
+            match FIELDS.iter().find(|(n, _)| *n == name) {
+                Some((_, field)) if is_number(*field) => {
+                    re.push_str(&format!(r"(?P<{name}>\d+)"));
+                }
+                Some(_) => re.push_str(&format!("(?P<{name}>.+?)")),
+                None if name == "ignore" => re.push_str(".*?"),
+                None => bail!("unknown placeholder {{{name}}}"),
+            }
+            rest = &rest[start + len + 1..];
+        }
+        re.push_str(&literal(rest));
+        re.push('$');
+
+        Ok(Self {
+            rules: vec![Regex::new(&re)?],
+            depth: pattern.split('/').count(),
+        })
+    }
+
+    /// The tags found in the name of `file`, after noise is stripped. Nothing if no rule fits.
+    pub fn parse(&self, file: &str) -> Vec<(TagField, String)> {
+        let path = Path::new(file);
+        let mut components: Vec<String> = path
+            .parent()
+            .map(|p| {
+                p.iter()
+                    .map(|c| c.to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let stem = path
+            .file_stem()
+            .map_or_else(String::new, |s| s.to_string_lossy().to_string());
+        components.push(stem);
+        let start = components.len().saturating_sub(self.depth);
+        let name = components[start..]
+            .iter()
+            .map(|c| strip_noise(c))
+            .collect::<Vec<_>>()
+            .join("/");
+
+        for rule in &self.rules {
+            let Some(captures) = rule.captures(&name) else {
+                continue;
+            };
+            // "01 - Title" is not by an artist called "01"
+            if captures
+                .name("artist")
+                .map_or(false, |a| a.as_str().chars().all(|c| c.is_ascii_digit()))
+            {
+                continue;
+            }
+            return FIELDS
+                .iter()
+                .filter_map(|(name, field)| {
+                    let value = captures.name(name)?.as_str().trim();
+                    if value.is_empty() {
+                        return None;
+                    }
+                    let value = if is_number(*field) {
+                        value.parse::<u32>().ok()?.to_string()
+                    } else {
+                        value.to_string()
+                    };
+                    Some((*field, value))
+                })
+                .collect();
+        }
+        vec![]
+    }
+}
+
+const fn is_number(field: TagField) -> bool {
+    matches!(field, TagField::Year | TagField::Track | TagField::Disc)
+}
+
+/// Text of the pattern matched as is, with runs of spaces matching any spacing
+fn literal(text: &str) -> String {
+    text.split(' ')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(r"\s+")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{strip_noise, FilenameParser};
+    use crate::tag_batch::TagField;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_strip_noise() {
+        assert_eq!(
+            strip_noise("Artist - Song (Official Video) [dQw4w9WgXcQ]"),
+            "Artist - Song"
+        );
+        assert_eq!(strip_noise("Song (Live) [HD]"), "Song (Live)");
+        assert_eq!(strip_noise("Artist_-_Song"), "Artist - Song");
+    }
+
+    #[test]
+    fn test_builtin() {
+        let parser = FilenameParser::new("").unwrap();
+        assert_eq!(
+            parser.parse("/music/Artist - Song (Official Video) [dQw4w9WgXcQ].mp3"),
+            vec![
+                (TagField::Artist, "Artist".to_string()),
+                (TagField::Title, "Song".to_string()),
+            ]
+        );
+        assert_eq!(
+            parser.parse("/music/07 - Song.flac"),
+            vec![
+                (TagField::Title, "Song".to_string()),
+                (TagField::Track, "7".to_string()),
+            ]
+        );
+        assert_eq!(
+            parser.parse("/music/50 Cent - In da Club.mp3"),
+            vec![
+                (TagField::Artist, "50 Cent".to_string()),
+                (TagField::Title, "In da Club".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pattern() {
+        let parser = FilenameParser::new("{artist}/{year} {album}/{track}. {title}").unwrap();
+        assert_eq!(
+            parser.parse("/music/Queen/1975 A Night at the Opera/11. Bohemian Rhapsody.mp3"),
+            vec![
+                (TagField::Artist, "Queen".to_string()),
+                (TagField::Title, "Bohemian Rhapsody".to_string()),
+                (TagField::Album, "A Night at the Opera".to_string()),
+                (TagField::Year, "1975".to_string()),
+                (TagField::Track, "11".to_string()),
+            ]
+        );
+        assert_eq!(parser.parse("/music/Queen/Bohemian Rhapsody.mp3"), vec![]);
+        assert!(FilenameParser::new("{artist} - {name}").is_err());
+        assert!(FilenameParser::new("{artist").is_err());
+    }
+}
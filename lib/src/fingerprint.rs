@@ -0,0 +1,528 @@
+/**
+ * MIT License
+ *
+ * termusic - Copyright (c) 2021 Larry Hao
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// audio fingerprints for identifying songs by how they sound
+use crate::songtag::SongTag;
+use crate::types::SearchLyricState;
+use crate::utils::decode_audio;
+use anyhow::{bail, Result};
+use base64::{engine::general_purpose, Engine as _};
+use std::f64::consts::PI;
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::thread;
+
+/// Everything below follows the default algorithm of Chromaprint, so the result can be
+/// looked up in the `AcoustID` database.
+const ALGORITHM: u8 = 1;
+const SAMPLE_RATE: u32 = 11025;
+const FRAME_SIZE: usize = 4096;
+const HOP: usize = FRAME_SIZE / 3;
+/// Only the start of the song is fingerprinted
+const MAX_SECS: usize = 120;
+const MIN_FREQ: f64 = 28.0;
+const MAX_FREQ: f64 = 3520.0;
+const BANDS: usize = 12;
+const CHROMA_FILTER: [f64; 5] = [0.25, 0.75, 1.0, 0.75, 0.25];
+const MAX_FILTER_WIDTH: usize = 16;
+
+type Row = [f64; BANDS];
+
+/// (filter kind, first band, bands, frames) and the thresholds of the quantizer
+const CLASSIFIERS: [(u8, usize, usize, usize, [f64; 3]); 16] = [
+    (0, 4, 3, 15, [1.982_15, 2.358_17, 2.635_23]),
+    (4, 4, 6, 15, [-1.038_09, -0.651_211, -0.282_167]),
+    (1, 0, 4, 16, [-0.298_702, 0.119_262, 0.558_497]),
+    (3, 8, 2, 12, [-0.105_439, 0.015_394_6, 0.135_898]),
+    (3, 4, 4, 8, [-0.142_891, 0.025_873_6, 0.200_632]),
+    (4, 0, 3, 5, [-0.826_319, -0.590_612, -0.368_214]),
+    (1, 2, 2, 9, [-0.557_409, -0.233_035, 0.053_452_5]),
+    (2, 7, 3, 4, [-0.064_682_6, 0.006_204_76, 0.078_484_7]),
+    (2, 6, 2, 16, [-0.192_387, -0.029_699, 0.215_855]),
+    (2, 1, 3, 2, [-0.039_781_8, -0.005_680_76, 0.029_202_6]),
+    (5, 10, 1, 15, [-0.538_23, -0.369_934, -0.190_235]),
+    (3, 6, 2, 10, [-0.124_877, 0.029_648_3, 0.139_239]),
+    (2, 1, 1, 14, [-0.101_475, 0.022_561_7, 0.231_971]),
+    (3, 5, 6, 4, [-0.079_991_5, -0.007_296_16, 0.063_262]),
+    (1, 9, 2, 12, [-0.272_556, 0.019_424, 0.302_559]),
+    (3, 4, 2, 14, [-0.164_292, -0.032_118_8, 0.084_633_9]),
+];
+
+pub struct Fingerprint {
+    /// Length of the whole song in seconds
+    pub duration: u32,
+    pub raw: Vec<u32>,
+}
+
+impl Fingerprint {
+    /// The compressed form, as printed by `fpcalc` and sent to `AcoustID`
+    pub fn encode(&self) -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode(compress(&self.raw))
+    }
+}
+
+/// Looks up the songs matching a fingerprint.
+pub trait Resolver: Send {
+    fn lookup(&self, fingerprint: &Fingerprint) -> Result<Vec<SongTag>>;
+}
+
+pub fn calculate(path: &Path) -> Result<Fingerprint> {
+    let (samples, rate, duration) = decode(path)?;
+    let raw = fingerprint(&resample(&samples, rate));
+    if raw.is_empty() {
+        bail!("{} is too short to identify", path.display());
+    }
+    Ok(Fingerprint { duration, raw })
+}
+
+pub fn identify(path: &Path, resolver: &dyn Resolver) -> Result<Vec<SongTag>> {
+    resolver.lookup(&calculate(path)?)
+}
+
+/// Identify the song in the background and send the candidates to the tag editor.
+pub fn search(file: &str, resolver: Box<dyn Resolver>, tx_tageditor: Sender<SearchLyricState>) {
+    let file = file.to_string();
+    thread::spawn(
+        move || match identify(Path::new(&file), resolver.as_ref()) {
+            Ok(candidates) => {
+                tx_tageditor
+                    .send(SearchLyricState::Identified(candidates))
+                    .ok();
+            }
+            Err(e) => warn!("cannot identify {file}: {e}"),
+        },
+    );
+}
+
+/// Mono samples of the start of the default track, scaled like 16 bit audio, together with
+/// the sample rate and the length of the whole track in seconds.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn decode(path: &Path) -> Result<(Vec<f64>, u32, u32)> {
+    let mut samples = Vec::new();
+    let mut frames: u64 = 0;
+    let mut rate = 0;
+    decode_audio::<f32>(path, |buf, spec| {
+        let channels = spec.channels.count();
+        rate = spec.rate;
+        frames += (buf.len() / channels) as u64;
+        if samples.len() < MAX_SECS * rate as usize {
+            samples.extend(buf.chunks(channels).map(|frame| {
+                frame.iter().map(|s| f64::from(*s)).sum::<f64>() * 32768.0 / channels as f64
+            }));
+        }
+    })?;
+    if rate == 0 {
+        bail!("no audio decoded");
+    }
+    samples.truncate(MAX_SECS * rate as usize);
+    Ok((samples, rate, (frames / u64::from(rate)) as u32))
+}
+
+/// Low pass below the new Nyquist frequency with a windowed sinc, then pick the samples.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn resample(input: &[f64], rate: u32) -> Vec<f64> {
+    if rate == SAMPLE_RATE {
+        return input.to_vec();
+    }
+    let ratio = f64::from(rate) / f64::from(SAMPLE_RATE);
+    let cutoff = 0.8 / ratio.max(1.0);
+    let half = (8.0 / cutoff).ceil();
+    let len = (input.len() as f64 / ratio) as usize;
+    (0..len)
+        .map(|n| {
+            let t = n as f64 * ratio;
+            let first = (t - half).ceil().max(0.0) as usize;
+            let last = ((t + half).floor() as usize).min(input.len() - 1);
+            (first..=last)
+                .map(|k| {
+                    let x = t - k as f64;
+                    let window = 0.5 + 0.5 * (PI * x / half).cos();
+                    input[k] * cutoff * sinc(cutoff * x) * window
+                })
+                .sum()
+        })
+        .collect()
+}
+
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+/// Raw fingerprint of mono samples at `SAMPLE_RATE`, one value for every frame once the
+/// filters have enough frames to look at.
+fn fingerprint(samples: &[f64]) -> Vec<u32> {
+    let rows = chroma(samples);
+    let filtered: Vec<Row> = rows
+        .windows(CHROMA_FILTER.len())
+        .map(|window| {
+            let mut row = [0.0; BANDS];
+            for (coefficient, frame) in CHROMA_FILTER.iter().zip(window) {
+                for (value, energy) in row.iter_mut().zip(frame) {
+                    *value += coefficient * energy;
+                }
+            }
+            normalize(&mut row);
+            row
+        })
+        .collect();
+    if filtered.len() < MAX_FILTER_WIDTH {
+        return vec![];
+    }
+    let image = Image::new(&filtered);
+    (0..=filtered.len() - MAX_FILTER_WIDTH)
+        .map(|x| {
+            CLASSIFIERS.iter().fold(0, |bits, classifier| {
+                (bits << 2) | classify(&image, x, classifier)
+            })
+        })
+        .collect()
+}
+
+/// Energy of every frame in the 12 pitch classes, starting at A.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn chroma(samples: &[f64]) -> Vec<Row> {
+    let to_index = |freq: f64| (FRAME_SIZE as f64 * freq / f64::from(SAMPLE_RATE)).round() as usize;
+    let min_index = to_index(MIN_FREQ).max(1);
+    let max_index = to_index(MAX_FREQ).min(FRAME_SIZE / 2);
+    let notes: Vec<usize> = (0..max_index)
+        .map(|i| {
+            let freq = i as f64 * f64::from(SAMPLE_RATE) / FRAME_SIZE as f64;
+            let octave = (freq / (440.0 / 16.0)).log2();
+            ((BANDS as f64 * (octave - octave.floor())) as usize).min(BANDS - 1)
+        })
+        .collect();
+    let window: Vec<f64> = (0..FRAME_SIZE)
+        .map(|i| 0.54 - 0.46 * (2.0 * PI * i as f64 / (FRAME_SIZE - 1) as f64).cos())
+        .collect();
+    let fft = Fft::new(FRAME_SIZE);
+
+    let mut rows = vec![];
+    let mut start = 0;
+    while start + FRAME_SIZE <= samples.len() {
+        let mut re: Vec<f64> = samples[start..start + FRAME_SIZE]
+            .iter()
+            .zip(&window)
+            .map(|(s, w)| s * w)
+            .collect();
+        let mut im = vec![0.0; FRAME_SIZE];
+        fft.run(&mut re, &mut im);
+        let mut row = [0.0; BANDS];
+        for i in min_index..max_index {
+            row[notes[i]] += re[i] * re[i] + im[i] * im[i];
+        }
+        rows.push(row);
+        start += HOP;
+    }
+    rows
+}
+
+fn normalize(row: &mut Row) {
+    let norm = row.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm < 0.01 {
+        *row = [0.0; BANDS];
+    } else {
+        row.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+/// Radix-2 FFT with precomputed twiddles
+struct Fft {
+    cos: Vec<f64>,
+    sin: Vec<f64>,
+}
+
+impl Fft {
+    #[allow(clippy::cast_precision_loss)]
+    fn new(size: usize) -> Self {
+        let angles: Vec<f64> = (0..size / 2)
+            .map(|k| 2.0 * PI * k as f64 / size as f64)
+            .collect();
+        Self {
+            cos: angles.iter().map(|a| a.cos()).collect(),
+            sin: angles.iter().map(|a| a.sin()).collect(),
+        }
+    }
+
+    #[allow(clippy::many_single_char_names)]
+    fn run(&self, re: &mut [f64], im: &mut [f64]) {
+        let n = re.len();
+        let mut j = 0;
+        for i in 1..n {
+            let mut bit = n >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j ^= bit;
+            if i < j {
+                re.swap(i, j);
+                im.swap(i, j);
+            }
+        }
+        let mut len = 2;
+        while len <= n {
+            let step = n / len;
+            for start in (0..n).step_by(len) {
+                for k in 0..len / 2 {
+                    let (c, s) = (self.cos[k * step], self.sin[k * step]);
+                    let (a, b) = (start + k, start + k + len / 2);
+                    let tr = re[b] * c + im[b] * s;
+                    let ti = im[b] * c - re[b] * s;
+                    re[b] = re[a] - tr;
+                    im[b] = im[a] - ti;
+                    re[a] += tr;
+                    im[a] += ti;
+                }
+            }
+            len <<= 1;
+        }
+    }
+}
+
+/// Summed area table of the filtered chroma, frames by bands
+struct Image {
+    rows: Vec<Row>,
+}
+
+impl Image {
+    fn new(features: &[Row]) -> Self {
+        let mut rows: Vec<Row> = Vec::with_capacity(features.len());
+        for feature in features {
+            let mut row = [0.0; BANDS];
+            let mut sum = 0.0;
+            for (band, value) in feature.iter().enumerate() {
+                sum += value;
+                row[band] = sum + rows.last().map_or(0.0, |last| last[band]);
+            }
+            rows.push(row);
+        }
+        Self { rows }
+    }
+
+    /// Sum of frames `x1..x2` in bands `y1..y2`
+    fn area(&self, x1: usize, y1: usize, x2: usize, y2: usize) -> f64 {
+        let at = |x: usize, y: usize| {
+            if x == 0 || y == 0 {
+                0.0
+            } else {
+                self.rows[x - 1][y - 1]
+            }
+        };
+        at(x2, y2) - at(x1, y2) - at(x2, y1) + at(x1, y1)
+    }
+}
+
+/// Two gray coded bits from comparing regions of the image at frame `x`
+#[allow(clippy::many_single_char_names)]
+fn classify(
+    image: &Image,
+    x: usize,
+    (kind, y, height, width, thresholds): &(u8, usize, usize, usize, [f64; 3]),
+) -> u32 {
+    let (y, h, w) = (*y, *height, *width);
+    let area = |dx1: usize, dy1: usize, dx2: usize, dy2: usize| {
+        image.area(x + dx1, y + dy1, x + dx2, y + dy2)
+    };
+    let (a, b) = match kind {
+        0 => (area(0, 0, w, h), 0.0),
+        1 => (area(0, h / 2, w, h), area(0, 0, w, h / 2)),
+        2 => (area(w / 2, 0, w, h), area(0, 0, w / 2, h)),
+        3 => (
+            area(0, h / 2, w / 2, h) + area(w / 2, 0, w, h / 2),
+            area(0, 0, w / 2, h / 2) + area(w / 2, h / 2, w, h),
+        ),
+        4 => (
+            area(0, h / 3, w, 2 * h / 3),
+            area(0, 0, w, h / 3) + area(0, 2 * h / 3, w, h),
+        ),
+        _ => (
+            area(w / 3, 0, 2 * w / 3, h),
+            area(0, 0, w / 3, h) + area(2 * w / 3, 0, w, h),
+        ),
+    };
+    let value = ((1.0 + a) / (1.0 + b)).ln();
+    match thresholds.iter().filter(|t| value >= **t).count() {
+        0 => 0,
+        1 => 1,
+        2 => 3,
+        _ => 2,
+    }
+}
+
+/// Header with the algorithm and length, then the positions of the bits that changed from
+/// the previous value, packed into 3 bits each with larger gaps continued in 5 bits.
+#[allow(clippy::cast_possible_truncation)]
+fn compress(raw: &[u32]) -> Vec<u8> {
+    let mut normal = vec![];
+    let mut exceptional = vec![];
+    let mut previous = 0;
+    for value in raw {
+        let mut x = value ^ previous;
+        previous = *value;
+        let (mut bit, mut last_bit) = (1, 0);
+        while x != 0 {
+            if x & 1 != 0 {
+                let gap = bit - last_bit;
+                if gap >= 7 {
+                    normal.push(7);
+                    exceptional.push(gap - 7);
+                } else {
+                    normal.push(gap);
+                }
+                last_bit = bit;
+            }
+            x >>= 1;
+            bit += 1;
+        }
+        normal.push(0);
+    }
+    let len = raw.len();
+    let mut output = vec![ALGORITHM, (len >> 16) as u8, (len >> 8) as u8, len as u8];
+    pack(&normal, 3, &mut output);
+    pack(&exceptional, 5, &mut output);
+    output
+}
+
+/// Little endian bit packing
+#[allow(clippy::cast_possible_truncation)]
+fn pack(values: &[u32], bits: u32, output: &mut Vec<u8>) {
+    let (mut acc, mut filled) = (0_u32, 0);
+    for value in values {
+        acc |= value << filled;
+        filled += bits;
+        while filled >= 8 {
+            output.push(acc as u8);
+            acc >>= 8;
+            filled -= 8;
+        }
+    }
+    if filled > 0 {
+        output.push(acc as u8);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chroma, compress, fingerprint, identify, Fingerprint, Resolver, SAMPLE_RATE};
+    use crate::songtag::SongTag;
+    use anyhow::Result;
+    use pretty_assertions::assert_eq;
+    use std::f64::consts::PI;
+    use std::path::Path;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn tone(freq: f64, secs: usize) -> Vec<f64> {
+        (0..secs * SAMPLE_RATE as usize)
+            .map(|i| 10000.0 * (2.0 * PI * freq * i as f64 / f64::from(SAMPLE_RATE)).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_chroma() {
+        let strongest = |freq: f64| {
+            let row = chroma(&tone(freq, 1))[0];
+            (0..row.len())
+                .max_by(|a, b| row[*a].total_cmp(&row[*b]))
+                .unwrap()
+        };
+        // bands start at their note, so these sit a quarter tone above A, C and G#
+        assert_eq!(strongest(452.9), 0);
+        assert_eq!(strongest(538.6), 3);
+        assert_eq!(strongest(855.2), 11);
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        // 10 seconds give 78 frames, 4 of them go to the chroma filter and 15 to the
+        // classifiers
+        let a = fingerprint(&tone(440.0, 10));
+        assert_eq!(a.len(), 59);
+        assert_eq!(a, fingerprint(&tone(440.0, 10)));
+        assert_ne!(a, fingerprint(&tone(523.25, 10)));
+        assert!(fingerprint(&tone(440.0, 2)).is_empty());
+    }
+
+    #[test]
+    fn test_compress() {
+        assert_eq!(compress(&[1, 3]), vec![1, 0, 0, 2, 0x81, 0]);
+        // a gap of 10 bits does not fit into 3 bits
+        assert_eq!(compress(&[1 << 9]), vec![1, 0, 0, 1, 7, 3]);
+        let fingerprint = Fingerprint {
+            duration: 0,
+            raw: vec![1, 3],
+        };
+        assert_eq!(fingerprint.encode(), "AQAAAoEA");
+    }
+
+    /// Answers with one candidate for every second of the song
+    struct Stub;
+
+    impl Resolver for Stub {
+        fn lookup(&self, fingerprint: &Fingerprint) -> Result<Vec<SongTag>> {
+            Ok((0..fingerprint.duration)
+                .map(|_| SongTag::default())
+                .collect())
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn test_identify() {
+        // 16 bit mono wav at 22050 Hz, so the samples are resampled first
+        let samples: Vec<i16> = tone(220.0, 6).iter().map(|s| *s as i16).collect();
+        let data_len = u32::try_from(samples.len() * 2).unwrap();
+        let mut wav = vec![];
+        wav.extend(b"RIFF");
+        wav.extend((36 + data_len).to_le_bytes());
+        wav.extend(b"WAVEfmt ");
+        wav.extend(16_u32.to_le_bytes());
+        wav.extend(1_u16.to_le_bytes());
+        wav.extend(1_u16.to_le_bytes());
+        wav.extend(22050_u32.to_le_bytes());
+        wav.extend(44100_u32.to_le_bytes());
+        wav.extend(2_u16.to_le_bytes());
+        wav.extend(16_u16.to_le_bytes());
+        wav.extend(b"data");
+        wav.extend(data_len.to_le_bytes());
+        samples.iter().for_each(|s| wav.extend(s.to_le_bytes()));
+        let path = std::env::temp_dir().join("termusic_fingerprint_test.wav");
+        std::fs::write(&path, wav).unwrap();
+
+        let candidates = identify(&path, &Stub);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(candidates.unwrap().len(), 3);
+        assert!(identify(Path::new("nonexistent.mp3"), &Stub).is_err());
+    }
+}
@@ -6,8 +6,21 @@
 #[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
 pub mod config;
 #[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+pub mod cover;
+#[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+pub mod duplicates;
+#[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+pub mod filename_tags;
+#[cfg(feature = "fingerprint")]
+#[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+pub mod fingerprint;
+#[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
 pub mod invidious;
 #[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+pub mod organize;
+#[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+pub mod parallel;
+#[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
 pub mod playlist;
 #[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
 pub mod podcast;
@@ -15,6 +28,14 @@ pub mod podcast;
 pub mod songtag;
 #[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
 pub mod sqlite;
+#[allow(
+    clippy::missing_errors_doc,
+    clippy::must_use_candidate,
+    clippy::module_name_repetitions
+)]
+pub mod stats;
+#[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+pub mod tag_batch;
 #[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
 pub mod track;
 #[allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
@@ -0,0 +1,554 @@
+/**
+ * MIT License
+ *
+ * termusic - Copyright (c) 2021 Larry Hao
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// moving tracks into folders and file names built from their tags
+use crate::config::Settings;
+use crate::cover;
+use crate::sqlite::TrackForDB;
+use crate::stats::is_missing;
+use crate::track::{file_stem, lyric_sidecar_lang};
+use crate::utils::filetype_supported;
+use anyhow::{bail, Result};
+use sanitize_filename::{sanitize_with_options, Options};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PATTERN: &str = "{albumartist}/{year} - {album}/{disc}{track:02} {title}";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Artist,
+    AlbumArtist,
+    Album,
+    Title,
+    Genre,
+    Composer,
+    Year,
+    Track,
+    Disc,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "artist" => Self::Artist,
+            "albumartist" => Self::AlbumArtist,
+            "album" => Self::Album,
+            "title" => Self::Title,
+            "genre" => Self::Genre,
+            "composer" => Self::Composer,
+            "year" => Self::Year,
+            "track" => Self::Track,
+            "disc" => Self::Disc,
+            _ => return None,
+        })
+    }
+
+    const fn is_number(self) -> bool {
+        matches!(self, Self::Year | Self::Track | Self::Disc)
+    }
+
+    /// Missing text becomes "Unknown ...", missing numbers are left out
+    fn value(self, record: &TrackForDB, width: usize) -> String {
+        let (text, unknown) = match self {
+            Self::Artist => (&record.artist, "Unknown Artist"),
+            Self::AlbumArtist => (&record.album_artist, "Unknown Artist"),
+            Self::Album => (&record.album, "Unknown Album"),
+            Self::Title => (&record.title, "Unknown Title"),
+            Self::Genre => (&record.genre, "Unknown Genre"),
+            Self::Composer => (&record.composer, "Unknown Composer"),
+            Self::Year => return pad(record.year, width),
+            Self::Track => return pad(record.track_number, width),
+            Self::Disc => return pad(record.disc_number, width),
+        };
+        if is_missing(text) {
+            return unknown.to_string();
+        }
+        sanitize(text.trim())
+    }
+}
+
+fn pad(number: Option<u32>, width: usize) -> String {
+    number.map_or_else(String::new, |n| format!("{n:0width$}"))
+}
+
+fn sanitize(name: &str) -> String {
+    sanitize_with_options(
+        name,
+        Options {
+            truncate: true,
+            windows: true,
+            replacement: "",
+        },
+    )
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Text(String),
+    Field(Field, usize),
+    Separator,
+}
+
+/// A relative path with `{field}` placeholders, like `{albumartist}/{album}/{track:02} {title}`.
+/// The last component is the file name, the extension is kept from the track.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    tokens: Vec<Token>,
+}
+
+impl Pattern {
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let mut tokens = vec![];
+        let mut text = String::new();
+        let mut chars = pattern.trim().chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' => {
+                    let placeholder: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                    let (name, width) = placeholder
+                        .split_once(':')
+                        .map_or((placeholder.as_str(), "0"), |(n, w)| (n, w));
+                    let Some(field) = Field::from_name(name.trim()) else {
+                        bail!("unknown placeholder {{{placeholder}}}");
+                    };
+                    let Ok(width) = width.parse() else {
+                        bail!("bad width in {{{placeholder}}}");
+                    };
+                    if width > 0 && !field.is_number() {
+                        bail!("only numbers can be padded, not {{{placeholder}}}");
+                    }
+                    if !text.is_empty() {
+                        tokens.push(Token::Text(std::mem::take(&mut text)));
+                    }
+                    tokens.push(Token::Field(field, width));
+                }
+                '}' => bail!("unmatched }} in pattern"),
+                '/' | '\\' => {
+                    if !text.is_empty() {
+                        tokens.push(Token::Text(std::mem::take(&mut text)));
+                    }
+                    tokens.push(Token::Separator);
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            tokens.push(Token::Text(text));
+        }
+        if !tokens.iter().any(|t| matches!(t, Token::Field(..))) {
+            bail!("pattern has no placeholder");
+        }
+        if matches!(tokens.last(), Some(Token::Separator)) {
+            bail!("pattern ends without a file name");
+        }
+        Ok(Self { tokens })
+    }
+
+    /// The new path of `record`, relative to the music root. Components that end up empty
+    /// are dropped, and an empty file name falls back to the current one.
+    pub fn render(&self, record: &TrackForDB) -> PathBuf {
+        let mut dirs = vec![];
+        let mut current = String::new();
+        for token in &self.tokens {
+            match token {
+                Token::Text(text) => current.push_str(text),
+                Token::Field(field, width) => current.push_str(&field.value(record, *width)),
+                Token::Separator => dirs.push(tidy(&std::mem::take(&mut current))),
+            }
+        }
+
+        let mut path: PathBuf = dirs.into_iter().filter(|d| !d.is_empty()).collect();
+        let mut name = tidy(&current);
+        if name.is_empty() {
+            name = file_stem(&record.file);
+        }
+        if !record.ext.is_empty() {
+            name = format!("{name}.{}", record.ext);
+        }
+        path.push(name);
+        path
+    }
+}
+
+/// Separators left around a placeholder that came out empty, as in `{year} - {album}`,
+/// are dropped along with leading and trailing dots
+fn tidy(component: &str) -> String {
+    sanitize(component.trim_matches(|c: char| c.is_whitespace() || c == '-' || c == '.'))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+#[derive(Debug, Default)]
+pub struct Plan {
+    /// The music root the new paths are under
+    pub root: PathBuf,
+    pub moves: Vec<Move>,
+    /// Moves left out because the target exists or another track already goes there
+    pub conflicts: Vec<Move>,
+}
+
+/// Where `records` would go under `root`. Tracks already in place are left out.
+pub fn plan(records: &[TrackForDB], root: &Path, pattern: &Pattern) -> Plan {
+    let mut records: Vec<&TrackForDB> = records.iter().collect();
+    records.sort_by(|a, b| a.file.cmp(&b.file));
+
+    let mut plan = Plan {
+        root: root.to_path_buf(),
+        ..Plan::default()
+    };
+    let mut targets = HashSet::new();
+    let mut pending = vec![];
+    for record in records {
+        let from = PathBuf::from(&record.file);
+        let to = root.join(pattern.render(record));
+        if from == to {
+            targets.insert(to);
+        } else {
+            pending.push(Move { from, to });
+        }
+    }
+    for m in pending {
+        if m.to.exists() || !targets.insert(m.to.clone()) {
+            plan.conflicts.push(m);
+        } else {
+            plan.moves.push(m);
+        }
+    }
+    plan
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub moved: Vec<Move>,
+    /// The file and error of every move that failed
+    pub failed: Vec<(String, String)>,
+    /// Lyric files and pictures that did not go along with their moved track, and why
+    pub left_behind: Vec<(String, String)>,
+}
+
+/// Carry out `moves`. The `.lrc` files of a track go along with it, also the ones in
+/// `lyric_dir`, which are renamed in place. Pictures in a folder are copied to every folder its
+/// tracks went to, and a folder left without tracks is cleaned up, keeping pictures that are
+/// not in all of those folders.
+pub fn apply(moves: &[Move], lyric_dir: &str) -> Report {
+    let mut report = Report::default();
+    let mut folders: BTreeMap<PathBuf, BTreeSet<PathBuf>> = BTreeMap::new();
+    for m in moves {
+        match move_track(m, lyric_dir) {
+            Ok(left_behind) => {
+                report.left_behind.extend(left_behind);
+                if let (Some(from_dir), Some(to_dir)) = (m.from.parent(), m.to.parent()) {
+                    folders
+                        .entry(from_dir.to_path_buf())
+                        .or_default()
+                        .insert(to_dir.to_path_buf());
+                }
+                report.moved.push(m.clone());
+            }
+            Err(e) => report
+                .failed
+                .push((m.from.to_string_lossy().to_string(), e.to_string())),
+        }
+    }
+
+    for (from_dir, to_dirs) in folders {
+        let pictures = pictures_in(&from_dir);
+        let mut kept = vec![];
+        for picture in &pictures {
+            let Some(name) = picture.file_name() else {
+                continue;
+            };
+            let mut copied = true;
+            for to_dir in to_dirs.iter().filter(|d| **d != from_dir) {
+                let target = to_dir.join(name);
+                if !target.exists() {
+                    if let Err(e) = fs::copy(picture, &target) {
+                        report
+                            .left_behind
+                            .push((picture.to_string_lossy().to_string(), e.to_string()));
+                    }
+                }
+                copied &= same_content(picture, &target);
+            }
+            if !copied {
+                kept.push(picture);
+            }
+        }
+        if !has_tracks(&from_dir) {
+            for picture in pictures.iter().filter(|p| !kept.contains(p)) {
+                fs::remove_file(picture).ok();
+            }
+            // only succeeds if nothing else is left in there
+            fs::remove_dir(&from_dir).ok();
+        }
+    }
+    report
+}
+
+/// Move the track and its lyric files. Only a track that could not be moved is an error, lyric
+/// files that stay behind are returned with the reason.
+fn move_track(m: &Move, lyric_dir: &str) -> Result<Vec<(String, String)>> {
+    if m.to.exists() {
+        bail!("{} already exists", m.to.display());
+    }
+    move_file(&m.from, &m.to)?;
+
+    let old_stem = file_stem(&m.from);
+    let new_stem = file_stem(&m.to);
+    let mut sidecars = vec![];
+    if let (Some(from_dir), Some(to_dir)) = (m.from.parent(), m.to.parent()) {
+        for (sidecar, rest) in lyric_sidecars(from_dir, &old_stem) {
+            sidecars.push((sidecar, to_dir.join(format!("{new_stem}{rest}"))));
+        }
+    }
+    if !lyric_dir.is_empty() {
+        let lyric_dir = Path::new(lyric_dir);
+        for (sidecar, rest) in lyric_sidecars(lyric_dir, &old_stem) {
+            sidecars.push((sidecar, lyric_dir.join(format!("{new_stem}{rest}"))));
+        }
+    }
+    Ok(sidecars
+        .into_iter()
+        .filter_map(|(from, to)| {
+            let e = move_file(&from, &to).err()?;
+            Some((from.to_string_lossy().to_string(), e.to_string()))
+        })
+        .collect())
+}
+
+/// The `.lrc` files of the song named `stem` in `dir`, with what follows the stem in their name
+fn lyric_sidecars(dir: &Path, stem: &str) -> Vec<(PathBuf, String)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            lyric_sidecar_lang(stem, &name)?;
+            let rest = name.strip_prefix(stem)?.to_string();
+            Some((e.path(), rest))
+        })
+        .collect()
+}
+
+/// Rename, or copy and delete when `to` is on another file system
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if let Some(dir) = to.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+fn same_content(a: &Path, b: &Path) -> bool {
+    match (fs::read(a), fs::read(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn pictures_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| cover::is_image(p))
+        .collect()
+}
+
+fn has_tracks(dir: &Path) -> bool {
+    fs::read_dir(dir).map_or(false, |entries| {
+        entries
+            .flatten()
+            .any(|e| filetype_supported(&e.path().to_string_lossy()))
+    })
+}
+
+/// The music root `path` is in, or `path` itself if it is in none of them
+pub fn root_of(path: &Path, config: &Settings) -> PathBuf {
+    config
+        .music_dir
+        .iter()
+        .chain(config.music_dir_from_cli.iter())
+        .map(|dir| PathBuf::from(shellexpand::tilde(dir).to_string()))
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.components().count())
+        .unwrap_or_else(|| {
+            if path.is_dir() {
+                path.to_path_buf()
+            } else {
+                path.parent().map_or_else(PathBuf::new, Path::to_path_buf)
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, plan, Move, Pattern, DEFAULT_PATTERN};
+    use crate::sqlite::TrackForDB;
+    use pretty_assertions::assert_eq;
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    fn record(file: &str, title: &str, year: Option<u32>, disc: Option<u32>) -> TrackForDB {
+        TrackForDB {
+            id: 0,
+            artist: "AC/DC".to_string(),
+            title: title.to_string(),
+            album: "Back in Black".to_string(),
+            genre: "no type".to_string(),
+            file: file.to_string(),
+            duration: Duration::from_secs(200),
+            name: String::new(),
+            ext: "flac".to_string(),
+            directory: String::new(),
+            last_modified: String::new(),
+            last_position: Duration::from_secs(0),
+            album_artist: "AC/DC".to_string(),
+            year,
+            track_number: Some(6),
+            disc_number: disc,
+            composer: String::new(),
+            comment: String::new(),
+            bitrate: None,
+            sample_rate: None,
+            file_size: 0,
+            has_picture: false,
+            has_lyrics: false,
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let pattern = Pattern::parse(DEFAULT_PATTERN).unwrap();
+        assert_eq!(
+            pattern.render(&record("/m/a.flac", "Back in Black", Some(1980), Some(1))),
+            PathBuf::from("ACDC/1980 - Back in Black/106 Back in Black.flac")
+        );
+        assert_eq!(
+            pattern.render(&record("/m/a.flac", "Shoot to Thrill?", None, None)),
+            PathBuf::from("ACDC/Back in Black/06 Shoot to Thrill.flac")
+        );
+
+        let pattern = Pattern::parse("{genre}/{title}").unwrap();
+        assert_eq!(
+            pattern.render(&record("/m/a.flac", "Unknown Title", None, None)),
+            PathBuf::from("Unknown Genre/Unknown Title.flac")
+        );
+
+        assert!(Pattern::parse("{artist}/{name}").is_err());
+        assert!(Pattern::parse("{title:02}").is_err());
+        assert!(Pattern::parse("{artist}/").is_err());
+        assert!(Pattern::parse("music").is_err());
+    }
+
+    #[test]
+    fn test_plan() {
+        let pattern = Pattern::parse("{artist} - {title}").unwrap();
+        let records = vec![
+            record("/nonexistent/x.flac", "Hells Bells", None, None),
+            record(
+                "/nonexistent/ACDC - Hells Bells.flac",
+                "Hells Bells",
+                None,
+                None,
+            ),
+            record("/nonexistent/y.flac", "Hells Bells", None, None),
+            record("/nonexistent/z.flac", "Thunderstruck", None, None),
+        ];
+        let plan = plan(&records, Path::new("/nonexistent"), &pattern);
+        // the second record is already in place and takes the name
+        assert_eq!(
+            plan.moves,
+            vec![Move {
+                from: PathBuf::from("/nonexistent/z.flac"),
+                to: PathBuf::from("/nonexistent/ACDC - Thunderstruck.flac"),
+            }]
+        );
+        assert_eq!(plan.conflicts.len(), 2);
+    }
+
+    #[test]
+    fn test_apply_leaves_behind() {
+        let root = std::env::temp_dir().join(format!("termusic-organize-{}", std::process::id()));
+        std::fs::remove_dir_all(&root).ok();
+        let (from_dir, to_dir) = (root.join("old"), root.join("new"));
+        std::fs::create_dir_all(&from_dir).unwrap();
+        std::fs::write(from_dir.join("x.mp3"), "audio").unwrap();
+        std::fs::write(from_dir.join("x.lrc"), "lyrics").unwrap();
+        std::fs::write(from_dir.join("cover.jpg"), "old cover").unwrap();
+        // a folder in the way of the lyric file, and a different cover already there
+        std::fs::create_dir_all(to_dir.join("y.lrc")).unwrap();
+        std::fs::write(to_dir.join("cover.jpg"), "new cover").unwrap();
+
+        let m = Move {
+            from: from_dir.join("x.mp3"),
+            to: to_dir.join("y.mp3"),
+        };
+        let report = apply(&[m.clone()], "");
+        assert_eq!(report.moved, vec![m]);
+        assert!(report.failed.is_empty());
+        assert_eq!(
+            report.left_behind.first().map(|(file, _)| file.clone()),
+            Some(from_dir.join("x.lrc").to_string_lossy().to_string())
+        );
+        assert!(to_dir.join("y.mp3").exists());
+        assert!(from_dir.join("x.lrc").exists());
+        assert!(from_dir.join("cover.jpg").exists());
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_apply_carries_pictures() {
+        let root = std::env::temp_dir().join(format!("termusic-pictures-{}", std::process::id()));
+        std::fs::remove_dir_all(&root).ok();
+        let (from_dir, to_dir) = (root.join("old"), root.join("new"));
+        std::fs::create_dir_all(&from_dir).unwrap();
+        std::fs::write(from_dir.join("x.mp3"), "audio").unwrap();
+        std::fs::write(from_dir.join("Folder.JPG"), "folder").unwrap();
+        std::fs::write(from_dir.join("cover.jpeg"), "cover").unwrap();
+
+        let m = Move {
+            from: from_dir.join("x.mp3"),
+            to: to_dir.join("x.mp3"),
+        };
+        let report = apply(&[m], "");
+        assert!(report.left_behind.is_empty());
+        assert_eq!(std::fs::read(to_dir.join("Folder.JPG")).unwrap(), b"folder");
+        assert_eq!(std::fs::read(to_dir.join("cover.jpeg")).unwrap(), b"cover");
+        assert!(!from_dir.exists());
+        std::fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,141 @@
+// Asking several online sources at once, each with its own deadline.
+
+use anyhow::{anyhow, Result};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+struct Source<P: ?Sized> {
+    name: &'static str,
+    provider: Arc<P>,
+    timeout: Duration,
+}
+
+/// Providers of one kind, in the order their answers are listed.
+pub struct Sources<P: ?Sized> {
+    sources: Vec<Source<P>>,
+}
+
+impl<P: ?Sized> Default for Sources<P> {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+        }
+    }
+}
+
+impl<P: ?Sized + Send + Sync + 'static> Sources<P> {
+    pub fn push(&mut self, name: &'static str, provider: Arc<P>, timeout: Duration) {
+        self.sources.push(Source {
+            name,
+            provider,
+            timeout,
+        });
+    }
+
+    pub fn get(&self, name: &str) -> Option<&P> {
+        self.sources
+            .iter()
+            .find(|s| s.name == name)
+            .map(|s| s.provider.as_ref())
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.sources.iter().map(|s| s.name).collect()
+    }
+
+    /// Run `ask` against every provider, each on its own thread.
+    pub fn spawn<T, F>(&self, ask: F) -> Pending<T>
+    where
+        T: Send + 'static,
+        F: Fn(&P) -> Result<T> + Send + Sync + 'static,
+    {
+        let start = Instant::now();
+        let ask = Arc::new(ask);
+        let answers = self
+            .sources
+            .iter()
+            .map(|s| {
+                let (tx, rx) = mpsc::channel();
+                let provider = Arc::clone(&s.provider);
+                let ask = Arc::clone(&ask);
+                thread::spawn(move || {
+                    tx.send(ask(provider.as_ref())).ok();
+                });
+                (s.name, rx, start + s.timeout)
+            })
+            .collect();
+        Pending { answers }
+    }
+}
+
+/// Answers still to come from [`Sources::spawn`].
+pub struct Pending<T> {
+    answers: Vec<(&'static str, Receiver<Result<T>>, Instant)>,
+}
+
+impl<T> Pending<T> {
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+
+    /// Wait for every answer, in provider order. A provider that misses its deadline
+    /// counts as an error.
+    pub fn collect(self) -> Vec<(&'static str, Result<T>)> {
+        self.answers
+            .into_iter()
+            .map(|(name, rx, deadline)| {
+                let wait = deadline.saturating_duration_since(Instant::now());
+                let answer = rx
+                    .recv_timeout(wait)
+                    .unwrap_or_else(|_| Err(anyhow!("no answer in time")));
+                (name, answer)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sources;
+    use anyhow::bail;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    #[test]
+    fn test_collect_in_order_with_deadlines() {
+        let mut sources: Sources<u64> = Sources::default();
+        sources.push("slow", Arc::new(50), Duration::from_secs(5));
+        sources.push("fast", Arc::new(0), Duration::from_secs(5));
+        sources.push("broken", Arc::new(u64::MAX), Duration::from_secs(5));
+        sources.push("late", Arc::new(2000), Duration::from_millis(100));
+        assert_eq!(sources.names(), vec!["slow", "fast", "broken", "late"]);
+        assert_eq!(sources.get("late"), Some(&2000));
+
+        let answers: Vec<(&str, Result<u64, String>)> = sources
+            .spawn(|delay_ms| {
+                if *delay_ms == u64::MAX {
+                    bail!("offline");
+                }
+                sleep(Duration::from_millis(*delay_ms));
+                Ok(*delay_ms)
+            })
+            .collect()
+            .into_iter()
+            .map(|(name, answer)| (name, answer.map_err(|e| e.to_string())))
+            .collect();
+        assert_eq!(
+            answers,
+            vec![
+                ("slow", Ok(50)),
+                ("fast", Ok(0)),
+                ("broken", Err("offline".to_string())),
+                ("late", Err("no answer in time".to_string())),
+            ]
+        );
+        assert!(Sources::<u64>::default().spawn(|_| Ok(())).is_empty());
+    }
+}
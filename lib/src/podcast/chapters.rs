@@ -0,0 +1,736 @@
+// Chapter markers of episodes, read from the feed or from downloaded files.
+
+use super::db::Database;
+use anyhow::{bail, Result};
+use reqwest::blocking::ClientBuilder;
+use rss::extension::Extension;
+use rss::Item;
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::Duration;
+
+/// Going back within this much of a chapter start goes to the chapter before
+const PREVIOUS_GRACE: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    pub start: Duration,
+    pub title: String,
+}
+
+impl Chapter {
+    /// Formats the start into an HH:MM:SS format.
+    pub fn format_start(&self) -> String {
+        let seconds = self.start.as_secs();
+        format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
+
+/// Podlove Simple Chapters written inline in a feed item
+pub fn from_podlove(item: &Item) -> Vec<Chapter> {
+    let Some(chapters) = item
+        .extensions()
+        .get("psc")
+        .and_then(|psc| psc.get("chapters"))
+        .and_then(|list| list.first())
+        .and_then(|chapters| chapters.children().get("chapter"))
+    else {
+        return Vec::new();
+    };
+    let mut chapters: Vec<Chapter> = chapters
+        .iter()
+        .filter_map(|chapter| {
+            Some(Chapter {
+                start: parse_npt(chapter.attrs().get("start")?)?,
+                title: chapter.attrs().get("title").cloned().unwrap_or_default(),
+            })
+        })
+        .collect();
+    chapters.sort_by_key(|c| c.start);
+    chapters
+}
+
+/// Address of the Podcasting 2.0 chapters file linked from a feed item
+pub fn json_url(item: &Item) -> Option<String> {
+    item.extensions()
+        .get("podcast")
+        .and_then(|podcast| podcast.get("chapters"))
+        .and_then(|list| list.first())
+        .and_then(|chapters: &Extension| chapters.attrs().get("url").cloned())
+}
+
+/// Podcasting 2.0 chapters file. Chapters marked `"toc": false` are not meant for
+/// navigation and are left out.
+pub fn from_json(text: &str) -> Result<Vec<Chapter>> {
+    let json: Value = serde_json::from_str(text)?;
+    let Some(list) = json.get("chapters").and_then(Value::as_array) else {
+        bail!("no chapters in json");
+    };
+    let mut chapters: Vec<Chapter> = list
+        .iter()
+        .filter(|chapter| chapter.get("toc").and_then(Value::as_bool) != Some(false))
+        .filter_map(|chapter| {
+            let start = chapter.get("startTime").and_then(Value::as_f64)?;
+            Some(Chapter {
+                start: Duration::try_from_secs_f64(start).ok()?,
+                title: chapter
+                    .get("title")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+        })
+        .collect();
+    chapters.sort_by_key(|c| c.start);
+    Ok(chapters)
+}
+
+pub fn fetch_json(url: &str) -> Result<Vec<Chapter>> {
+    let client = ClientBuilder::new()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+    let text = client.get(url).send()?.error_for_status()?.text()?;
+    from_json(&text)
+}
+
+/// Chapters of a downloaded file: ID3 `CHAP` frames in mp3 files, or the chapter text
+/// track or Nero `chpl` box of mp4 files. Files without chapters give an empty list.
+pub fn from_file(path: &Path) -> Result<Vec<Chapter>> {
+    let ext = path
+        .extension()
+        .and_then(std::ffi::OsStr::to_str)
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    match ext.as_str() {
+        "mp3" => from_id3(path),
+        "m4a" | "m4b" | "mp4" | "m4v" | "mov" => {
+            let file = File::open(path)?;
+            let len = file.metadata()?.len();
+            from_mp4(&mut BufReader::new(file), len)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// `CHAP` frames in the order of the top-level `CTOC` table of contents, with the chapters of
+/// nested tables in their place and chapters it does not list left out. Without a table of
+/// contents they are ordered by start.
+fn from_id3(path: &Path) -> Result<Vec<Chapter>> {
+    let tag = match id3::Tag::read_from_path(path) {
+        Ok(tag) => tag,
+        Err(id3::Error {
+            kind: id3::ErrorKind::NoTag,
+            ..
+        }) => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut chapters: Vec<(&str, Chapter)> = tag
+        .chapters()
+        .map(|chapter| {
+            let title = chapter
+                .frames
+                .iter()
+                .find(|f| f.id() == "TIT2")
+                .and_then(|f| f.content().text())
+                .unwrap_or(&chapter.element_id)
+                .to_string();
+            let start = Duration::from_millis(u64::from(chapter.start_time));
+            (chapter.element_id.as_str(), Chapter { start, title })
+        })
+        .collect();
+    let Some(order) = toc_order(&tag) else {
+        chapters.sort_by_key(|(_, c)| c.start);
+        return Ok(chapters.into_iter().map(|(_, c)| c).collect());
+    };
+    Ok(order
+        .iter()
+        .filter_map(|id| chapters.iter().find(|(e, _)| e == id))
+        .map(|(_, c)| c.clone())
+        .collect())
+}
+
+/// A `CTOC` frame: its element id, whether it is the top-level one, and its entries
+struct Toc {
+    id: String,
+    top_level: bool,
+    entries: Vec<String>,
+}
+
+/// `CTOC` frames are not decoded by the id3 crate, so their bytes are read here
+fn parse_toc(data: &[u8]) -> Option<Toc> {
+    let mut parts = data.splitn(2, |b| *b == 0);
+    let id = String::from_utf8_lossy(parts.next()?).to_string();
+    let rest = parts.next()?;
+    let (&flags, rest) = rest.split_first()?;
+    let (&count, mut rest) = rest.split_first()?;
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let end = rest.iter().position(|b| *b == 0)?;
+        entries.push(String::from_utf8_lossy(&rest[..end]).to_string());
+        rest = &rest[end + 1..];
+    }
+    Some(Toc {
+        id,
+        top_level: flags & 0x02 != 0,
+        entries,
+    })
+}
+
+/// Element ids of the chapters listed by the top-level table of contents, if there is one
+fn toc_order(tag: &id3::Tag) -> Option<Vec<String>> {
+    fn flatten(tables: &[Toc], entries: &[String], depth: usize, order: &mut Vec<String>) {
+        for entry in entries {
+            match tables.iter().find(|t| t.id == *entry) {
+                // a table listing itself somewhere below would never end
+                Some(table) if depth < 8 => flatten(tables, &table.entries, depth + 1, order),
+                Some(_) => {}
+                None => order.push(entry.clone()),
+            }
+        }
+    }
+    let tables: Vec<Toc> = tag
+        .frames()
+        .filter(|f| f.id() == "CTOC")
+        .filter_map(|f| parse_toc(&f.content().to_unknown().ok()?.data))
+        .collect();
+    let top = tables.iter().find(|t| t.top_level)?;
+    let mut order = Vec::new();
+    flatten(&tables, &top.entries, 0, &mut order);
+    Some(order)
+}
+
+/// Chapters of the chapter text track, the one most m4a and m4b files use, or else of
+/// the Nero `chpl` box
+fn from_mp4<R: Read + Seek>(reader: &mut R, len: u64) -> Result<Vec<Chapter>> {
+    let chapters = from_chapter_track(reader, len)?;
+    if !chapters.is_empty() {
+        return Ok(chapters);
+    }
+    reader.seek(SeekFrom::Start(0))?;
+    if find_box(reader, len, &[b"moov", b"udta", b"chpl"])?.is_none() {
+        return Ok(Vec::new());
+    }
+    let mut version_flags = [0_u8; 4];
+    reader.read_exact(&mut version_flags)?;
+    if version_flags[0] > 0 {
+        reader.seek(SeekFrom::Current(4))?;
+    }
+    let mut count = [0_u8; 1];
+    reader.read_exact(&mut count)?;
+
+    let mut chapters = Vec::new();
+    for _ in 0..count[0] {
+        let mut start = [0_u8; 8];
+        reader.read_exact(&mut start)?;
+        let mut title_len = [0_u8; 1];
+        reader.read_exact(&mut title_len)?;
+        let mut title = vec![0_u8; usize::from(title_len[0])];
+        reader.read_exact(&mut title)?;
+        chapters.push(Chapter {
+            // stored in units of 100 nanoseconds
+            start: Duration::from_nanos(u64::from_be_bytes(start).saturating_mul(100)),
+            title: String::from_utf8_lossy(&title).to_string(),
+        });
+    }
+    chapters.sort_by_key(|c| c.start);
+    Ok(chapters)
+}
+
+/// Tracks list the track with their chapter titles under `tref/chap`. Its samples are the
+/// titles, each lasting until the next chapter.
+fn from_chapter_track<R: Read + Seek>(reader: &mut R, len: u64) -> Result<Vec<Chapter>> {
+    let Some(moov) = descend(reader, 0, len, &[b"moov"])? else {
+        return Ok(Vec::new());
+    };
+    let traks: Vec<Mp4Box> = boxes(reader, moov.payload, moov.end)?
+        .into_iter()
+        .filter(|b| &b.name == b"trak")
+        .collect();
+    let mut chapter_ids = Vec::new();
+    for trak in &traks {
+        if let Some(chap) = descend(reader, trak.payload, trak.end, &[b"tref", b"chap"])? {
+            let ids = read_payload(reader, &chap)?;
+            chapter_ids.extend((0..ids.len() / 4).filter_map(|i| be_u32(&ids, i * 4)));
+        }
+    }
+    if chapter_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    for trak in &traks {
+        let Some(tkhd) = descend(reader, trak.payload, trak.end, &[b"tkhd"])? else {
+            continue;
+        };
+        let tkhd = read_payload(reader, &tkhd)?;
+        let id_at = if tkhd.first() == Some(&1) { 20 } else { 12 };
+        if be_u32(&tkhd, id_at).map_or(false, |id| chapter_ids.contains(&id)) {
+            return text_samples(reader, trak);
+        }
+    }
+    Ok(Vec::new())
+}
+
+fn text_samples<R: Read + Seek>(reader: &mut R, trak: &Mp4Box) -> Result<Vec<Chapter>> {
+    let mut table = |path: &[&[u8; 4]]| -> Result<Vec<u8>> {
+        match descend(reader, trak.payload, trak.end, path)? {
+            Some(found) => read_payload(reader, &found),
+            None => Ok(Vec::new()),
+        }
+    };
+    let mdhd = table(&[b"mdia", b"mdhd"])?;
+    let timescale = be_u32(&mdhd, if mdhd.first() == Some(&1) { 20 } else { 12 })
+        .filter(|t| *t > 0)
+        .ok_or_else(|| anyhow::anyhow!("no timescale in chapter track"))?;
+    let times = table(&[b"mdia", b"minf", b"stbl", b"stts"])?;
+    let size_table = table(&[b"mdia", b"minf", b"stbl", b"stsz"])?;
+    let chunk_table = table(&[b"mdia", b"minf", b"stbl", b"stsc"])?;
+    let chunk_offsets = table(&[b"mdia", b"minf", b"stbl", b"stco"])?;
+    let large_chunk_offsets = table(&[b"mdia", b"minf", b"stbl", b"co64"])?;
+
+    let count = be_u32(&size_table, 8).unwrap_or(0) as usize;
+    let sizes: Vec<u32> = match be_u32(&size_table, 4) {
+        Some(0) => (0..count)
+            .filter_map(|i| be_u32(&size_table, 12 + i * 4))
+            .collect(),
+        Some(size) => vec![size; count],
+        None => Vec::new(),
+    };
+    let mut durations = Vec::new();
+    for i in 0..be_u32(&times, 4).unwrap_or(0) as usize {
+        let (Some(n), Some(delta)) = (be_u32(&times, 8 + i * 8), be_u32(&times, 12 + i * 8)) else {
+            break;
+        };
+        durations.extend(std::iter::repeat(delta).take((n as usize).min(sizes.len())));
+    }
+    let per_chunk: Vec<(u32, u32)> = (0..be_u32(&chunk_table, 4).unwrap_or(0) as usize)
+        .map_while(|i| {
+            Some((
+                be_u32(&chunk_table, 8 + i * 12)?,
+                be_u32(&chunk_table, 12 + i * 12)?,
+            ))
+        })
+        .collect();
+    let chunks: Vec<u64> = if chunk_offsets.is_empty() {
+        (0..be_u32(&large_chunk_offsets, 4).unwrap_or(0) as usize)
+            .map_while(|i| be_u64(&large_chunk_offsets, 8 + i * 8))
+            .collect()
+    } else {
+        (0..be_u32(&chunk_offsets, 4).unwrap_or(0) as usize)
+            .map_while(|i| be_u32(&chunk_offsets, 8 + i * 4).map(u64::from))
+            .collect()
+    };
+
+    let mut offsets = Vec::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let number = u32::try_from(index + 1)?;
+        let samples = per_chunk
+            .iter()
+            .rev()
+            .find(|(first, _)| *first <= number)
+            .map_or(0, |(_, n)| *n);
+        let mut offset = *chunk;
+        for _ in 0..samples {
+            let Some(size) = sizes.get(offsets.len()) else {
+                break;
+            };
+            offsets.push((offset, *size));
+            offset += u64::from(*size);
+        }
+    }
+
+    let mut chapters = Vec::new();
+    let mut elapsed = 0_u64;
+    for ((offset, size), duration) in offsets.into_iter().zip(durations) {
+        reader.seek(SeekFrom::Start(offset))?;
+        let mut text = vec![0_u8; size as usize];
+        reader.read_exact(&mut text)?;
+        let text_len = be_u16(&text, 0).map_or(0, usize::from);
+        chapters.push(Chapter {
+            start: Duration::from_millis(elapsed * 1000 / u64::from(timescale)),
+            title: decode_text(text.get(2..2 + text_len).unwrap_or_default()),
+        });
+        elapsed += u64::from(duration);
+    }
+    Ok(chapters)
+}
+
+/// Text samples are UTF-8, or UTF-16 when they start with a byte order mark
+fn decode_text(text: &[u8]) -> String {
+    match text {
+        [0xFE, 0xFF, rest @ ..] => String::from_utf16_lossy(
+            &rest
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect::<Vec<u16>>(),
+        ),
+        _ => String::from_utf8_lossy(text).to_string(),
+    }
+}
+
+fn be_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn be_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn be_u64(data: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(data.get(at..at + 8)?.try_into().ok()?))
+}
+
+/// An mp4 box: its name, where its payload starts and where it ends
+#[derive(Clone, Copy)]
+struct Mp4Box {
+    name: [u8; 4],
+    payload: u64,
+    end: u64,
+}
+
+/// Payloads larger than this are not read whole, no box needed here comes near it
+const MAX_PAYLOAD: u64 = 16 * 1024 * 1024;
+
+fn read_payload<R: Read + Seek>(reader: &mut R, found: &Mp4Box) -> Result<Vec<u8>> {
+    let len = found.end - found.payload;
+    if len > MAX_PAYLOAD {
+        bail!("mp4 box of {len} bytes is too large");
+    }
+    reader.seek(SeekFrom::Start(found.payload))?;
+    let mut data = vec![0_u8; usize::try_from(len)?];
+    reader.read_exact(&mut data)?;
+    Ok(data)
+}
+
+/// The box found from `start` up to `end` by descending along `path`
+fn descend<R: Read + Seek>(
+    reader: &mut R,
+    mut start: u64,
+    mut end: u64,
+    path: &[&[u8; 4]],
+) -> Result<Option<Mp4Box>> {
+    let mut found = None;
+    for name in path {
+        let Some(next) = boxes(reader, start, end)?
+            .into_iter()
+            .find(|b| &b.name == *name)
+        else {
+            return Ok(None);
+        };
+        (start, end) = (next.payload, next.end);
+        found = Some(next);
+    }
+    Ok(found)
+}
+
+/// Walk the boxes from the current position up to `end`, descending along `path`.
+/// Leaves the reader at the payload of the last box and returns where that box ends.
+fn find_box<R: Read + Seek>(reader: &mut R, end: u64, path: &[&[u8; 4]]) -> Result<Option<u64>> {
+    let start = reader.stream_position()?;
+    let Some(found) = descend(reader, start, end, path)? else {
+        return Ok(None);
+    };
+    reader.seek(SeekFrom::Start(found.payload))?;
+    Ok(Some(found.end))
+}
+
+/// The boxes one after another from `start` up to `end`
+fn boxes<R: Read + Seek>(reader: &mut R, mut start: u64, end: u64) -> Result<Vec<Mp4Box>> {
+    let mut list = Vec::new();
+    while start + 8 <= end {
+        reader.seek(SeekFrom::Start(start))?;
+        let mut box_header = [0_u8; 8];
+        reader.read_exact(&mut box_header)?;
+        let mut size = u64::from(u32::from_be_bytes([
+            box_header[0],
+            box_header[1],
+            box_header[2],
+            box_header[3],
+        ]));
+        let mut header_len = 8;
+        if size == 1 {
+            let mut large = [0_u8; 8];
+            reader.read_exact(&mut large)?;
+            size = u64::from_be_bytes(large);
+            header_len = 16;
+        } else if size == 0 {
+            size = end - start;
+        }
+        if size < header_len {
+            bail!("broken mp4 box at {start}");
+        }
+        let box_end = start + size;
+        list.push(Mp4Box {
+            name: [box_header[4], box_header[5], box_header[6], box_header[7]],
+            payload: start + header_len,
+            end: box_end.min(end),
+        });
+        start = box_end;
+    }
+    Ok(list)
+}
+
+/// Normal play time as used by Podlove: `HH:MM:SS.mmm`, `MM:SS` or plain seconds
+fn parse_npt(text: &str) -> Option<Duration> {
+    let mut secs = 0.0;
+    for part in text.trim().split(':') {
+        secs = secs * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// Index of the chapter playing at `position`
+pub fn current(chapters: &[Chapter], position: Duration) -> Option<usize> {
+    chapters.iter().rposition(|c| c.start <= position)
+}
+
+pub fn next_start(chapters: &[Chapter], position: Duration) -> Option<Duration> {
+    chapters
+        .iter()
+        .find(|c| c.start > position)
+        .map(|c| c.start)
+}
+
+/// Start of the current chapter, or of the one before when playback is just past the start
+pub fn previous_start(chapters: &[Chapter], position: Duration) -> Option<Duration> {
+    let index = current(chapters, position)?;
+    if position - chapters[index].start >= PREVIOUS_GRACE || index == 0 {
+        return Some(chapters[index].start);
+    }
+    Some(chapters[index - 1].start)
+}
+
+/// Read the chapters of a downloaded episode into the database. Chapters that came with
+/// the feed are kept when the file has none.
+pub fn save_from_file(db: &Database, episode_id: i64, path: &Path) -> Result<()> {
+    let chapters = from_file(path)?;
+    if !chapters.is_empty() {
+        db.set_chapters(episode_id, &chapters)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{current, find_box, from_id3, from_json, from_mp4, next_start, previous_start};
+    use super::{parse_npt, Chapter};
+    use pretty_assertions::assert_eq;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    fn chapter(secs: u64, title: &str) -> Chapter {
+        Chapter {
+            start: Duration::from_secs(secs),
+            title: title.to_string(),
+        }
+    }
+
+    fn mp4_box(name: [u8; 4], payload: &[u8]) -> Vec<u8> {
+        let size = u32::try_from(payload.len() + 8).unwrap();
+        let mut data = size.to_be_bytes().to_vec();
+        data.extend_from_slice(&name);
+        data.extend_from_slice(payload);
+        data
+    }
+
+    #[test]
+    fn test_parse_npt() {
+        assert_eq!(
+            parse_npt("01:02:03.500"),
+            Some(Duration::from_millis(3_723_500))
+        );
+        assert_eq!(parse_npt("02:03"), Some(Duration::from_secs(123)));
+        assert_eq!(parse_npt("42"), Some(Duration::from_secs(42)));
+        assert_eq!(parse_npt("intro"), None);
+    }
+
+    #[test]
+    fn test_from_json() {
+        let json = r#"{"version": "1.2.0", "chapters": [
+            {"startTime": 95.5, "title": "News"},
+            {"startTime": 0, "title": "Intro"},
+            {"startTime": 60, "title": "Ad", "toc": false}
+        ]}"#;
+        assert_eq!(
+            from_json(json).unwrap(),
+            vec![
+                chapter(0, "Intro"),
+                Chapter {
+                    start: Duration::from_millis(95_500),
+                    title: "News".to_string(),
+                },
+            ]
+        );
+        assert!(from_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_from_mp4() {
+        let mut chpl = vec![1, 0, 0, 0, 0, 0, 0, 0, 2];
+        for (start, title) in [(0_u64, "Intro"), (90, "Interview")] {
+            chpl.extend_from_slice(&(start * 10_000_000).to_be_bytes());
+            chpl.push(u8::try_from(title.len()).unwrap());
+            chpl.extend_from_slice(title.as_bytes());
+        }
+        let moov = mp4_box(
+            *b"moov",
+            &[
+                mp4_box(*b"mvhd", &[0; 12]),
+                mp4_box(*b"udta", &mp4_box(*b"chpl", &chpl)),
+            ]
+            .concat(),
+        );
+        let data = [
+            mp4_box(*b"ftyp", b"M4A "),
+            moov,
+            mp4_box(*b"mdat", &[0; 32]),
+        ]
+        .concat();
+        let len = data.len() as u64;
+
+        let chapters = from_mp4(&mut Cursor::new(&data), len).unwrap();
+        assert_eq!(
+            chapters,
+            vec![chapter(0, "Intro"), chapter(90, "Interview")]
+        );
+
+        let data = [mp4_box(*b"ftyp", b"M4A "), mp4_box(*b"mdat", &[0; 32])].concat();
+        let len = data.len() as u64;
+        assert!(find_box(&mut Cursor::new(&data), len, &[b"moov"])
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_from_id3() {
+        use id3::TagLike;
+        let mut tag = id3::Tag::new();
+        for (index, (start, title)) in [(0_u32, "Intro"), (61_000, "Interview")]
+            .into_iter()
+            .enumerate()
+        {
+            let mut frame = id3::Tag::new();
+            frame.set_title(title);
+            tag.add_frame(id3::frame::Chapter {
+                element_id: format!("chp{index}"),
+                start_time: start,
+                end_time: start + 1000,
+                start_offset: u32::MAX,
+                end_offset: u32::MAX,
+                frames: frame.frames().cloned().collect(),
+            });
+        }
+        let path = std::env::temp_dir().join("termusic_test_chapters.mp3");
+        std::fs::write(&path, []).unwrap();
+        tag.write_to_path(&path, id3::Version::Id3v24).unwrap();
+
+        let chapters = from_id3(&path).unwrap();
+        assert_eq!(
+            chapters,
+            vec![chapter(0, "Intro"), chapter(61, "Interview")]
+        );
+
+        // the top-level table lists the second chapter first and the first one through a
+        // nested table
+        for (id, flags, entries) in [("toc", 0x03, ["chp1", "sub"]), ("sub", 0x01, ["chp0", ""])] {
+            let entries: Vec<&str> = entries.into_iter().filter(|e| !e.is_empty()).collect();
+            let mut data = format!("{id}\0").into_bytes();
+            data.push(flags);
+            data.push(u8::try_from(entries.len()).unwrap());
+            for entry in entries {
+                data.extend(format!("{entry}\0").bytes());
+            }
+            tag.add_frame(id3::Frame::with_content(
+                "CTOC",
+                id3::Content::Unknown(id3::frame::Unknown {
+                    data,
+                    version: id3::Version::Id3v24,
+                }),
+            ));
+        }
+        tag.write_to_path(&path, id3::Version::Id3v24).unwrap();
+        let chapters = from_id3(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            chapters,
+            vec![chapter(61, "Interview"), chapter(0, "Intro")]
+        );
+    }
+
+    #[test]
+    fn test_from_chapter_track() {
+        fn full_box(name: [u8; 4], fields: &[u32]) -> Vec<u8> {
+            let payload: Vec<u8> = [0]
+                .iter()
+                .chain(fields)
+                .flat_map(|f| f.to_be_bytes())
+                .collect();
+            mp4_box(name, &payload)
+        }
+        let mut samples = 5_u16.to_be_bytes().to_vec();
+        samples.extend_from_slice(b"Intro");
+        samples.extend_from_slice(&10_u16.to_be_bytes());
+        samples.extend_from_slice(&[0xFE, 0xFF, 0, b'N', 0, b'e', 0, b'w', 0, b's']);
+        let moov = |offset: u32| {
+            let stbl = [
+                full_box(*b"stts", &[2, 1, 90_000, 1, 30_000]),
+                full_box(*b"stsz", &[0, 2, 7, 12]),
+                full_box(*b"stsc", &[1, 1, 2, 1]),
+                full_box(*b"stco", &[1, offset]),
+            ]
+            .concat();
+            let text = [
+                // version, flags, creation and modification time, track id 2
+                full_box(*b"tkhd", &[0, 0, 2]),
+                mp4_box(
+                    *b"mdia",
+                    &[
+                        full_box(*b"mdhd", &[0, 0, 1000]),
+                        mp4_box(*b"minf", &mp4_box(*b"stbl", &stbl)),
+                    ]
+                    .concat(),
+                ),
+            ]
+            .concat();
+            let audio = [
+                full_box(*b"tkhd", &[0, 0, 1]),
+                mp4_box(*b"tref", &mp4_box(*b"chap", &2_u32.to_be_bytes())),
+            ]
+            .concat();
+            mp4_box(
+                *b"moov",
+                &[mp4_box(*b"trak", &audio), mp4_box(*b"trak", &text)].concat(),
+            )
+        };
+        let ftyp = mp4_box(*b"ftyp", b"M4A ");
+        let offset = u32::try_from(ftyp.len() + moov(0).len() + 8).unwrap();
+        let data = [ftyp, moov(offset), mp4_box(*b"mdat", &samples)].concat();
+        let len = data.len() as u64;
+
+        let chapters = from_mp4(&mut Cursor::new(&data), len).unwrap();
+        assert_eq!(chapters, vec![chapter(0, "Intro"), chapter(90, "News")]);
+    }
+
+    #[test]
+    fn test_navigation() {
+        let chapters = vec![
+            chapter(0, "Intro"),
+            chapter(60, "News"),
+            chapter(300, "End"),
+        ];
+        let at = Duration::from_secs;
+
+        assert_eq!(current(&chapters, at(90)), Some(1));
+        assert_eq!(next_start(&chapters, at(90)), Some(at(300)));
+        assert_eq!(next_start(&chapters, at(400)), None);
+        assert_eq!(previous_start(&chapters, at(90)), Some(at(60)));
+        assert_eq!(previous_start(&chapters, at(61)), Some(at(0)));
+        assert_eq!(previous_start(&chapters, at(1)), Some(at(0)));
+        assert_eq!(current(&[], at(1)), None);
+    }
+}
@@ -10,6 +10,10 @@ use rusqlite::{params, Connection};
 use semver::Version;
 use std::time::Duration;
 
+use super::chapters::Chapter;
+use super::filter::Filter;
+use super::gpodder::{EpisodeState, SyncState};
+use super::policy::Policy;
 use super::{Episode, EpisodeNoId, NewEpisode, Podcast, PodcastNoId};
 
 lazy_static! {
@@ -90,6 +94,7 @@ impl Database {
     /// Creates the necessary database tables, if they do not already
     /// exist. Panics if database cannot be accessed, or if tables cannot
     /// be created.
+    #[allow(clippy::too_many_lines)]
     pub fn create(&self) -> Result<()> {
         let conn = self.conn.as_ref().expect("Error connecting to database.");
 
@@ -124,12 +129,58 @@ impl Database {
                 hidden INTEGER,
                 last_position INTERGER,
                 image_url TEXT,
+                played_at INTEGER,
+                chapters_url TEXT,
+                transcript_url TEXT,
+                enclosure_length INTEGER,
                 FOREIGN KEY(podcast_id) REFERENCES podcasts(id) ON DELETE CASCADE
             );",
             params![],
         )
         .with_context(|| "Could not create episodes database table")?;
 
+        // older databases do not know when episodes were played; count
+        // them as played now, so retention policies do not delete them
+        // right away
+        if conn.prepare("SELECT played_at FROM episodes;").is_err() {
+            conn.execute(
+                "ALTER TABLE episodes ADD COLUMN played_at INTEGER;",
+                params![],
+            )
+            .with_context(|| "Could not add played_at to episodes database table")?;
+            conn.execute(
+                "UPDATE episodes SET played_at = ? WHERE played = 1;",
+                params![Utc::now().timestamp()],
+            )?;
+        }
+        if conn.prepare("SELECT chapters_url FROM episodes;").is_err() {
+            conn.execute(
+                "ALTER TABLE episodes ADD COLUMN chapters_url TEXT;",
+                params![],
+            )
+            .with_context(|| "Could not add chapters_url to episodes database table")?;
+        }
+        if conn
+            .prepare("SELECT transcript_url FROM episodes;")
+            .is_err()
+        {
+            conn.execute(
+                "ALTER TABLE episodes ADD COLUMN transcript_url TEXT;",
+                params![],
+            )
+            .with_context(|| "Could not add transcript_url to episodes database table")?;
+        }
+        if conn
+            .prepare("SELECT enclosure_length FROM episodes;")
+            .is_err()
+        {
+            conn.execute(
+                "ALTER TABLE episodes ADD COLUMN enclosure_length INTEGER;",
+                params![],
+            )
+            .with_context(|| "Could not add enclosure_length to episodes database table")?;
+        }
+
         // create files table
         conn.execute(
             "CREATE TABLE IF NOT EXISTS files (
@@ -142,6 +193,111 @@ impl Database {
         )
         .with_context(|| "Could not create files database table")?;
 
+        // create chapters table
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS chapters (
+                id INTEGER PRIMARY KEY NOT NULL,
+                episode_id INTEGER NOT NULL,
+                start INTEGER NOT NULL,
+                title TEXT NOT NULL,
+                FOREIGN KEY (episode_id) REFERENCES episodes(id) ON DELETE CASCADE
+            );",
+            params![],
+        )
+        .with_context(|| "Could not create chapters database table")?;
+
+        // create transcripts table
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS transcripts (
+                episode_id INTEGER PRIMARY KEY NOT NULL,
+                text TEXT NOT NULL,
+                FOREIGN KEY (episode_id) REFERENCES episodes(id) ON DELETE CASCADE
+            );",
+            params![],
+        )
+        .with_context(|| "Could not create transcripts database table")?;
+
+        // create policies table
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS policies (
+                podcast_id INTEGER PRIMARY KEY NOT NULL,
+                auto_download INTEGER NOT NULL,
+                delete_played_after INTEGER NOT NULL,
+                keep_downloaded INTEGER NOT NULL,
+                FOREIGN KEY (podcast_id) REFERENCES podcasts(id) ON DELETE CASCADE
+            );",
+            params![],
+        )
+        .with_context(|| "Could not create policies database table")?;
+
+        // create queue table
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS queue (
+                episode_id INTEGER PRIMARY KEY NOT NULL,
+                position INTEGER NOT NULL,
+                FOREIGN KEY (episode_id) REFERENCES episodes(id) ON DELETE CASCADE
+            );",
+            params![],
+        )
+        .with_context(|| "Could not create queue database table")?;
+
+        // create table of downloads that failed, kept until one of them
+        // succeeds or is dropped
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS failed_downloads (
+                episode_id INTEGER PRIMARY KEY NOT NULL,
+                error TEXT NOT NULL,
+                failed_at INTEGER NOT NULL,
+                FOREIGN KEY (episode_id) REFERENCES episodes(id) ON DELETE CASCADE
+            );",
+            params![],
+        )
+        .with_context(|| "Could not create failed_downloads database table")?;
+
+        // create filters table, where a filter without podcast is for
+        // all podcasts
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS filters (
+                id INTEGER PRIMARY KEY NOT NULL,
+                podcast_id INTEGER UNIQUE,
+                rules TEXT NOT NULL,
+                FOREIGN KEY (podcast_id) REFERENCES podcasts(id) ON DELETE CASCADE
+            );",
+            params![],
+        )
+        .with_context(|| "Could not create filters database table")?;
+
+        // create tables remembering what was last synced with a gpodder server
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS sync_state (
+                id INTEGER PRIMARY KEY NOT NULL,
+                account TEXT NOT NULL,
+                subscriptions_since INTEGER NOT NULL,
+                episodes_since INTEGER NOT NULL
+            );",
+            params![],
+        )
+        .with_context(|| "Could not create sync_state database table")?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS sync_subscriptions (
+                url TEXT PRIMARY KEY NOT NULL
+            );",
+            params![],
+        )
+        .with_context(|| "Could not create sync_subscriptions database table")?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS sync_episodes (
+                url TEXT PRIMARY KEY NOT NULL,
+                podcast_url TEXT NOT NULL,
+                played INTEGER NOT NULL,
+                position INTEGER NOT NULL,
+                duration INTEGER,
+                downloaded INTEGER NOT NULL
+            );",
+            params![],
+        )
+        .with_context(|| "Could not create sync_episodes database table")?;
+
         conn.execute(
             "CREATE TABLE IF NOT EXISTS version (
                 id INTEGER PRIMARY KEY NOT NULL,
@@ -233,8 +389,9 @@ impl Database {
 
         let mut stmt = conn.prepare_cached(
             "INSERT INTO episodes (podcast_id, title, url, guid,
-                description, pubdate, duration, played, hidden, last_position, image_url)
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?);",
+                description, pubdate, duration, played, hidden, last_position, image_url,
+                chapters_url, transcript_url, enclosure_length)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?);",
         )?;
         stmt.execute(params![
             podcast_id,
@@ -248,8 +405,28 @@ impl Database {
             false,
             0,
             episode.image_url,
+            episode.chapters_url,
+            episode.transcript_url,
+            episode.enclosure_length,
         ])?;
-        Ok(conn.last_insert_rowid())
+        let id = conn.last_insert_rowid();
+        Self::insert_chapters(conn, id, &episode.chapters)?;
+        Ok(id)
+    }
+
+    /// Replaces the chapters of an episode.
+    fn insert_chapters(conn: &Connection, episode_id: i64, chapters: &[Chapter]) -> Result<()> {
+        let mut stmt = conn.prepare_cached("DELETE FROM chapters WHERE episode_id = ?;")?;
+        stmt.execute(params![episode_id])?;
+        let mut stmt = conn.prepare_cached(
+            "INSERT INTO chapters (episode_id, start, title)
+                VALUES (?, ?, ?);",
+        )?;
+        for chapter in chapters {
+            let start = i64::try_from(chapter.start.as_millis()).unwrap_or(i64::MAX);
+            stmt.execute(params![episode_id, start, chapter.title])?;
+        }
+        Ok(())
     }
 
     /// Inserts a filepath to a downloaded episode.
@@ -275,17 +452,14 @@ impl Database {
 
     /// Removes all file listings for the selected episode ids.
     pub fn remove_files(&self, episode_ids: &[i64]) -> Result<()> {
-        let conn = self.conn.as_ref().expect("Error connecting to database.");
-
-        // convert list of episode ids into a comma-separated String
-        let episode_list: Vec<String> = episode_ids
-            .iter()
-            .map(std::string::ToString::to_string)
-            .collect();
-        let episodes = episode_list.join(", ");
+        let mut conn = Connection::open(&self.path).expect("Error connecting to database.");
+        let tx = conn.transaction()?;
 
-        let mut stmt = conn.prepare_cached("DELETE FROM files WHERE episode_id = (?);")?;
-        stmt.execute(params![episodes])?;
+        for episode_id in episode_ids {
+            let mut stmt = tx.prepare_cached("DELETE FROM files WHERE episode_id = ?;")?;
+            stmt.execute(params![episode_id])?;
+        }
+        tx.commit()?;
         Ok(())
     }
 
@@ -398,7 +572,9 @@ impl Database {
                     let mut stmt = tx.prepare_cached(
                         "UPDATE episodes SET title = ?, url = ?,
                                 guid = ?, description = ?, pubdate = ?,
-                                duration = ? WHERE id = ?;",
+                                duration = ?, chapters_url = ?, transcript_url = ?,
+                                enclosure_length = ?
+                                WHERE id = ?;",
                     )?;
                     stmt.execute(params![
                         new_ep.title,
@@ -407,10 +583,17 @@ impl Database {
                         new_ep.description,
                         new_pd,
                         new_ep.duration,
+                        new_ep.chapters_url,
+                        new_ep.transcript_url,
+                        new_ep.enclosure_length,
                         id,
                     ])?;
                     update_ep.push(id);
                 }
+                // chapters written into the feed may be added to old episodes
+                if !new_ep.chapters.is_empty() {
+                    Self::insert_chapters(&tx, id, &new_ep.chapters)?;
+                }
             } else {
                 let id = Self::insert_episode(&tx, podcast_id, new_ep)?;
                 let new_ep = NewEpisode {
@@ -446,6 +629,9 @@ impl Database {
             && new_ep.guid == old_ep.guid
             && new_ep.description == old_ep.description
             && new_ep.duration == old_ep.duration
+            && new_ep.chapters_url == old_ep.chapters_url
+            && new_ep.transcript_url == old_ep.transcript_url
+            && new_ep.enclosure_length == old_ep.enclosure_length
             && pd_match)
         {
             return true;
@@ -453,12 +639,14 @@ impl Database {
         false
     }
 
-    /// Updates an episode to mark it as played or unplayed.
+    /// Updates an episode to mark it as played or unplayed, and
+    /// remembers when it was played.
     pub fn set_played_status(&self, episode_id: i64, played: bool) -> Result<()> {
         let conn = self.conn.as_ref().expect("Error connecting to database.");
 
-        let mut stmt = conn.prepare_cached("UPDATE episodes SET played = ? WHERE id = ?;")?;
-        stmt.execute(params![played, episode_id])?;
+        let mut stmt =
+            conn.prepare_cached("UPDATE episodes SET played = ?, played_at = ? WHERE id = ?;")?;
+        stmt.execute(params![played, played_at(played), episode_id])?;
         Ok(())
     }
 
@@ -468,8 +656,9 @@ impl Database {
         let tx = conn.transaction()?;
 
         for episode_id in episode_id_vec {
-            let mut stmt = tx.prepare_cached("UPDATE episodes SET played = ? WHERE id = ?;")?;
-            stmt.execute(params![played, episode_id])?;
+            let mut stmt =
+                tx.prepare_cached("UPDATE episodes SET played = ?, played_at = ? WHERE id = ?;")?;
+            stmt.execute(params![played, played_at(played), episode_id])?;
         }
         tx.commit()?;
         Ok(())
@@ -486,6 +675,377 @@ impl Database {
         Ok(())
     }
 
+    /// Replaces the chapters of an episode, e.g. with the ones read
+    /// from its downloaded file.
+    pub fn set_chapters(&self, episode_id: i64, chapters: &[Chapter]) -> Result<()> {
+        let mut conn = Connection::open(&self.path).expect("Error connecting to database.");
+        let tx = conn.transaction()?;
+        Self::insert_chapters(&tx, episode_id, chapters)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Generates list of chapters for an episode, in order.
+    pub fn get_chapters(&self, episode_id: i64) -> Result<Vec<Chapter>> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached(
+            "SELECT * FROM chapters WHERE episode_id = ?
+                ORDER BY start;",
+        )?;
+        let chapter_iter = stmt.query_map(params![episode_id], row_to_chapter)?;
+        Ok(chapter_iter.flatten().collect())
+    }
+
+    /// Generates list of chapters for the episode with the given
+    /// url, which is how the player knows podcast tracks.
+    pub fn get_chapters_by_url(&self, url: &str) -> Result<Vec<Chapter>> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached(
+            "SELECT chapters.* FROM chapters
+                JOIN episodes ON episodes.id = chapters.episode_id
+                WHERE episodes.url = ?
+                ORDER BY start;",
+        )?;
+        let chapter_iter = stmt.query_map(params![url], row_to_chapter)?;
+        Ok(chapter_iter.flatten().collect())
+    }
+
+    /// Stores the transcript of an episode as it was downloaded.
+    pub fn set_transcript(&self, episode_id: i64, text: &str) -> Result<()> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached(
+            "INSERT OR REPLACE INTO transcripts (episode_id, text)
+                VALUES (?, ?);",
+        )?;
+        stmt.execute(params![episode_id, text])?;
+        Ok(())
+    }
+
+    /// Returns the stored transcript of an episode, if any.
+    pub fn get_transcript(&self, episode_id: i64) -> Result<Option<String>> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached("SELECT text FROM transcripts WHERE episode_id = ?;")?;
+        match stmt.query_row(params![episode_id], |row| row.get("text")) {
+            Ok(text) => Ok(Some(text)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Generates list of episode ids and transcripts that contain the
+    /// given text, ignoring the case of ASCII letters.
+    pub fn get_transcripts_containing(&self, query: &str) -> Result<Vec<(i64, String)>> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let pattern = format!(
+            "%{}%",
+            query
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        let mut stmt = conn.prepare_cached(
+            "SELECT episode_id, text FROM transcripts
+                WHERE text LIKE ? ESCAPE '\\';",
+        )?;
+        let transcript_iter = stmt.query_map(params![pattern], |row| {
+            Ok((row.get("episode_id")?, row.get("text")?))
+        })?;
+        Ok(transcript_iter.flatten().collect())
+    }
+
+    /// Returns the retention policy of a podcast, which is all off
+    /// unless one was set.
+    pub fn get_policy(&self, podcast_id: i64) -> Result<Policy> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached("SELECT * FROM policies WHERE podcast_id = ?;")?;
+        match stmt.query_row(params![podcast_id], row_to_policy) {
+            Ok(policy) => Ok(policy),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(Policy::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns the policies of all podcasts that have one.
+    pub fn get_policies(&self) -> Result<AHashMap<i64, Policy>> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached("SELECT * FROM policies;")?;
+        let policy_iter = stmt.query_map(params![], |row| {
+            Ok((row.get("podcast_id")?, row_to_policy(row)?))
+        })?;
+        let mut policies = AHashMap::new();
+        for item in policy_iter {
+            let (podcast_id, policy) = item?;
+            policies.insert(podcast_id, policy);
+        }
+        Ok(policies)
+    }
+
+    /// Stores the retention policy of a podcast. A policy with every
+    /// rule off is removed instead.
+    pub fn set_policy(&self, podcast_id: i64, policy: &Policy) -> Result<()> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        if policy.is_off() {
+            let mut stmt = conn.prepare_cached("DELETE FROM policies WHERE podcast_id = ?;")?;
+            stmt.execute(params![podcast_id])?;
+            return Ok(());
+        }
+
+        let mut stmt = conn.prepare_cached(
+            "INSERT OR REPLACE INTO policies (podcast_id, auto_download,
+                delete_played_after, keep_downloaded)
+                VALUES (?, ?, ?, ?);",
+        )?;
+        stmt.execute(params![
+            podcast_id,
+            policy.auto_download,
+            policy.delete_played_after,
+            policy.keep_downloaded,
+        ])?;
+        Ok(())
+    }
+
+    /// Returns the view filter of a podcast, or the one for all
+    /// podcasts if `podcast_id` is None.
+    pub fn get_filter(&self, podcast_id: Option<i64>) -> Result<Filter> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached("SELECT rules FROM filters WHERE podcast_id IS ?;")?;
+        match stmt.query_row(params![podcast_id], |row| row.get::<usize, String>(0)) {
+            Ok(rules) => rules.parse(),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(Filter::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns the view filters of all podcasts that have their own.
+    pub fn get_filters(&self) -> Result<AHashMap<i64, Filter>> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn
+            .prepare_cached("SELECT podcast_id, rules FROM filters WHERE podcast_id NOT NULL;")?;
+        let filter_iter = stmt.query_map(params![], |row| {
+            Ok((row.get::<usize, i64>(0)?, row.get::<usize, String>(1)?))
+        })?;
+        let mut filters = AHashMap::new();
+        for item in filter_iter {
+            let (podcast_id, rules) = item?;
+            filters.insert(podcast_id, rules.parse()?);
+        }
+        Ok(filters)
+    }
+
+    /// Stores the view filter of a podcast, or the one for all podcasts
+    /// if `podcast_id` is None. A filter that hides nothing is removed
+    /// instead.
+    pub fn set_filter(&self, podcast_id: Option<i64>, filter: &Filter) -> Result<()> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached("DELETE FROM filters WHERE podcast_id IS ?;")?;
+        stmt.execute(params![podcast_id])?;
+        if filter.is_off() {
+            return Ok(());
+        }
+
+        let mut stmt =
+            conn.prepare_cached("INSERT INTO filters (podcast_id, rules) VALUES (?, ?);")?;
+        stmt.execute(params![podcast_id, filter.to_string()])?;
+        Ok(())
+    }
+
+    /// Generates list of queued episodes, in the order they play.
+    pub fn get_queue(&self) -> Result<Vec<Episode>> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached(
+            "SELECT episodes.*, files.path FROM queue
+                JOIN episodes ON episodes.id = queue.episode_id
+                LEFT JOIN files ON episodes.id = files.episode_id
+                ORDER BY queue.position;",
+        )?;
+        let episode_iter = stmt.query_map(params![], row_to_episode)?;
+        Ok(episode_iter.flatten().collect())
+    }
+
+    /// Replaces the queue with the given episodes, in order.
+    pub fn set_queue(&self, episode_ids: &[i64]) -> Result<()> {
+        let mut conn = Connection::open(&self.path).expect("Error connecting to database.");
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM queue;", params![])?;
+        {
+            let mut stmt =
+                tx.prepare_cached("INSERT INTO queue (episode_id, position) VALUES (?, ?);")?;
+            for (position, episode_id) in episode_ids.iter().enumerate() {
+                stmt.execute(params![episode_id, position])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Takes an episode off the queue.
+    pub fn remove_from_queue(&self, episode_id: i64) -> Result<()> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached("DELETE FROM queue WHERE episode_id = ?;")?;
+        stmt.execute(params![episode_id])?;
+        Ok(())
+    }
+
+    /// Remembers that downloading an episode failed, and why.
+    pub fn set_download_failed(&self, episode_id: i64, error: &str) -> Result<()> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached(
+            "INSERT OR REPLACE INTO failed_downloads (episode_id, error, failed_at)
+                VALUES (?, ?, ?);",
+        )?;
+        stmt.execute(params![episode_id, error, Utc::now().timestamp()])?;
+        Ok(())
+    }
+
+    /// Takes an episode off the failed downloads, after it was downloaded
+    /// or the user gave up on it.
+    pub fn remove_download_failed(&self, episode_id: i64) -> Result<()> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached("DELETE FROM failed_downloads WHERE episode_id = ?;")?;
+        stmt.execute(params![episode_id])?;
+        Ok(())
+    }
+
+    pub fn clear_download_failed(&self) -> Result<()> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        conn.execute("DELETE FROM failed_downloads;", params![])?;
+        Ok(())
+    }
+
+    /// Returns the episodes whose download failed with the error of each,
+    /// the latest failure first.
+    pub fn get_failed_downloads(&self) -> Result<Vec<(Episode, String)>> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached(
+            "SELECT episodes.*, files.path, failed_downloads.error FROM failed_downloads
+                JOIN episodes ON episodes.id = failed_downloads.episode_id
+                LEFT JOIN files ON episodes.id = files.episode_id
+                ORDER BY failed_downloads.failed_at DESC;",
+        )?;
+        let failed_iter = stmt.query_map(params![], |row| {
+            Ok((row_to_episode(row)?, row.get("error")?))
+        })?;
+        Ok(failed_iter.flatten().collect())
+    }
+
+    /// Returns the episode with the given url, which is how the
+    /// player knows podcast tracks.
+    pub fn get_episode_by_url(&self, url: &str) -> Result<Option<Episode>> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached(
+            "SELECT * FROM episodes
+                LEFT JOIN files ON episodes.id = files.episode_id
+                WHERE episodes.url = ?;",
+        )?;
+        match stmt.query_row(params![url], row_to_episode) {
+            Ok(episode) => Ok(Some(episode)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns the state of all episodes as it is synced with a
+    /// gpodder server.
+    pub fn get_episode_states(&self) -> Result<Vec<EpisodeState>> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached(
+            "SELECT podcasts.url AS podcast_url, episodes.url, episodes.played,
+                episodes.last_position, episodes.duration, files.path IS NOT NULL AS downloaded
+                FROM episodes
+                JOIN podcasts ON podcasts.id = episodes.podcast_id
+                LEFT JOIN files ON episodes.id = files.episode_id;",
+        )?;
+        let state_iter = stmt.query_map(params![], |row| {
+            Ok(EpisodeState {
+                podcast: row.get("podcast_url")?,
+                episode: row.get("url")?,
+                played: row.get::<&str, Option<bool>>("played")?.unwrap_or(false),
+                position: row.get::<&str, Option<u64>>("last_position")?.unwrap_or(0),
+                duration: row.get("duration")?,
+                downloaded: row.get("downloaded")?,
+            })
+        })?;
+        Ok(state_iter.flatten().collect())
+    }
+
+    /// Returns what was last synced with the gpodder `account`, or
+    /// an empty state if the last sync was with another account.
+    pub fn get_sync_state(&self, account: &str) -> Result<SyncState> {
+        let conn = self.conn.as_ref().expect("Error connecting to database.");
+        let mut stmt = conn.prepare_cached(
+            "SELECT subscriptions_since, episodes_since FROM sync_state
+                WHERE id = 1 AND account = ?;",
+        )?;
+        let (subscriptions_since, episodes_since) =
+            match stmt.query_row(params![account], |row| Ok((row.get(0)?, row.get(1)?))) {
+                Ok(since) => since,
+                Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(SyncState::default()),
+                Err(e) => return Err(e.into()),
+            };
+
+        let mut stmt = conn.prepare_cached("SELECT url FROM sync_subscriptions;")?;
+        let subscriptions = stmt
+            .query_map(params![], |row| row.get(0))?
+            .flatten()
+            .collect();
+        let mut stmt = conn.prepare_cached("SELECT * FROM sync_episodes;")?;
+        let episodes = stmt
+            .query_map(params![], |row| {
+                Ok(EpisodeState {
+                    podcast: row.get("podcast_url")?,
+                    episode: row.get("url")?,
+                    played: row.get("played")?,
+                    position: row.get("position")?,
+                    duration: row.get("duration")?,
+                    downloaded: row.get("downloaded")?,
+                })
+            })?
+            .flatten()
+            .collect();
+        Ok(SyncState {
+            subscriptions_since,
+            episodes_since,
+            subscriptions,
+            episodes,
+        })
+    }
+
+    /// Remembers what was synced with the gpodder `account`.
+    pub fn set_sync_state(&self, account: &str, state: &SyncState) -> Result<()> {
+        let mut conn = Connection::open(&self.path).expect("Error connecting to database.");
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO sync_state (id, account, subscriptions_since, episodes_since)
+                VALUES (1, ?, ?, ?);",
+            params![account, state.subscriptions_since, state.episodes_since],
+        )?;
+        tx.execute("DELETE FROM sync_subscriptions;", params![])?;
+        tx.execute("DELETE FROM sync_episodes;", params![])?;
+        {
+            let mut stmt = tx.prepare_cached("INSERT INTO sync_subscriptions (url) VALUES (?);")?;
+            for url in &state.subscriptions {
+                stmt.execute(params![url])?;
+            }
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR REPLACE INTO sync_episodes (url, podcast_url, played, position,
+                    duration, downloaded)
+                    VALUES (?, ?, ?, ?, ?, ?);",
+            )?;
+            for ep in &state.episodes {
+                stmt.execute(params![
+                    ep.episode,
+                    ep.podcast,
+                    ep.played,
+                    ep.position,
+                    ep.duration,
+                    ep.downloaded
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     /// Generates list of all podcasts in database.
     /// TODO: This should probably use a JOIN statement instead.
     pub fn get_podcasts(&self) -> Result<Vec<Podcast>> {
@@ -548,26 +1108,7 @@ impl Database {
                         ORDER BY pubdate DESC;",
             )?
         };
-        let episode_iter = stmt.query_map(params![pod_id], |row| {
-            let path = match row.get::<&str, String>("path") {
-                Ok(val) => Some(PathBuf::from(val)),
-                Err(_) => None,
-            };
-            Ok(Episode {
-                id: row.get("id")?,
-                pod_id: row.get("podcast_id")?,
-                title: row.get("title")?,
-                url: row.get("url")?,
-                guid: row.get::<&str, Option<String>>("guid")?.unwrap_or_default(),
-                description: row.get("description")?,
-                pubdate: convert_date(&row.get("pubdate")),
-                duration: row.get("duration")?,
-                path,
-                played: row.get("played")?,
-                last_position: row.get("last_position")?,
-                image_url: row.get("image_url")?,
-            })
-        })?;
+        let episode_iter = stmt.query_map(params![pod_id], row_to_episode)?;
         let episodes = episode_iter.flatten().collect();
         Ok(episodes)
     }
@@ -575,6 +1116,15 @@ impl Database {
     /// Deletes all rows in all tables
     pub fn clear_db(&self) -> Result<()> {
         let conn = self.conn.as_ref().expect("Error connecting to database.");
+        conn.execute("DELETE FROM queue;", params![])?;
+        conn.execute("DELETE FROM failed_downloads;", params![])?;
+        conn.execute("DELETE FROM filters;", params![])?;
+        conn.execute("DELETE FROM policies;", params![])?;
+        conn.execute("DELETE FROM chapters;", params![])?;
+        conn.execute("DELETE FROM transcripts;", params![])?;
+        conn.execute("DELETE FROM sync_episodes;", params![])?;
+        conn.execute("DELETE FROM sync_subscriptions;", params![])?;
+        conn.execute("DELETE FROM sync_state;", params![])?;
         conn.execute("DELETE FROM files;", params![])?;
         conn.execute("DELETE FROM episodes;", params![])?;
         conn.execute("DELETE FROM podcasts;", params![])?;
@@ -622,6 +1172,57 @@ impl Database {
     }
 }
 
+/// Helper function building an `Episode` from a row of the episodes
+/// table joined with its file
+fn row_to_episode(row: &rusqlite::Row<'_>) -> Result<Episode, rusqlite::Error> {
+    let path = match row.get::<&str, String>("path") {
+        Ok(val) => Some(PathBuf::from(val)),
+        Err(_) => None,
+    };
+    Ok(Episode {
+        id: row.get("id")?,
+        pod_id: row.get("podcast_id")?,
+        title: row.get("title")?,
+        url: row.get("url")?,
+        guid: row.get::<&str, Option<String>>("guid")?.unwrap_or_default(),
+        description: row.get("description")?,
+        pubdate: convert_date(&row.get("pubdate")),
+        duration: row.get("duration")?,
+        path,
+        played: row.get("played")?,
+        played_at: convert_date(&row.get("played_at")),
+        chapters_url: row.get("chapters_url")?,
+        transcript_url: row.get("transcript_url")?,
+        enclosure_length: row.get("enclosure_length")?,
+        last_position: row.get("last_position")?,
+        image_url: row.get("image_url")?,
+    })
+}
+
+/// Helper function building a `Chapter` from a row of the chapters table
+fn row_to_chapter(row: &rusqlite::Row<'_>) -> Result<Chapter, rusqlite::Error> {
+    let start: i64 = row.get("start")?;
+    Ok(Chapter {
+        start: Duration::from_millis(u64::try_from(start).unwrap_or_default()),
+        title: row.get("title")?,
+    })
+}
+
+/// Helper function building a `Policy` from a row of the policies table
+fn row_to_policy(row: &rusqlite::Row<'_>) -> Result<Policy, rusqlite::Error> {
+    Ok(Policy {
+        auto_download: row.get("auto_download")?,
+        delete_played_after: row.get("delete_played_after")?,
+        keep_downloaded: row.get("keep_downloaded")?,
+    })
+}
+
+/// Helper function giving the timestamp to store when an episode is
+/// marked played, or none when it is marked unplayed
+fn played_at(played: bool) -> Option<i64> {
+    played.then(|| Utc::now().timestamp())
+}
+
 /// Helper function converting an (optional) Unix timestamp to a
 /// `DateTime`<Utc> object
 fn convert_date(result: &Result<i64, rusqlite::Error>) -> Option<DateTime<Utc>> {
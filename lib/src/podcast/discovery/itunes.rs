@@ -0,0 +1,98 @@
+// Podcast search of the iTunes store.
+
+use super::DirectoryProvider;
+use crate::podcast::PodcastFeed;
+use anyhow::{anyhow, Result};
+use reqwest::blocking::{Client, ClientBuilder};
+use serde_json::Value;
+use std::time::Duration;
+
+static URL_SEARCH_ITUNES: &str = "https://itunes.apple.com/search";
+
+pub const NAME: &str = "itunes";
+
+pub struct Provider {
+    client: Client,
+}
+
+impl Provider {
+    pub fn new(timeout: Duration) -> Self {
+        let client = ClientBuilder::new()
+            .timeout(timeout)
+            .build()
+            .expect("failed to build reqwest client.");
+
+        Self { client }
+    }
+}
+
+impl DirectoryProvider for Provider {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn search(&self, query: &str, limit: u16) -> Result<Vec<PodcastFeed>> {
+        let limit = limit.to_string();
+        let text = self
+            .client
+            .get(URL_SEARCH_ITUNES)
+            .query(&[
+                ("media", "podcast"),
+                ("entity", "podcast"),
+                ("limit", &limit),
+                ("term", query),
+            ])
+            .send()?
+            .error_for_status()?
+            .text()?;
+        parse(&text)
+    }
+}
+
+/// Results without a feed url cannot be subscribed to and are skipped.
+fn parse(text: &str) -> Result<Vec<PodcastFeed>> {
+    let value: Value = serde_json::from_str(text)?;
+    let results = value
+        .get("results")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("no results in answer"))?;
+    Ok(results
+        .iter()
+        .filter_map(|v| {
+            let url = v.get("feedUrl")?.as_str()?;
+            let title = v
+                .get("collectionName")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            Some(PodcastFeed::new(None, url, title))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::podcast::PodcastFeed;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let feeds = parse(include_str!("test-data/itunes.json")).unwrap();
+        assert_eq!(
+            feeds,
+            vec![
+                PodcastFeed::new(
+                    None,
+                    "https://feeds.simplecast.com/54nAGcIl",
+                    Some("The Daily".to_string())
+                ),
+                PodcastFeed::new(
+                    None,
+                    "https://feeds.npr.org/510318/podcast.xml",
+                    Some("Up First".to_string())
+                ),
+            ]
+        );
+        assert!(parse("{}").is_err());
+    }
+}
@@ -0,0 +1,243 @@
+// Finding podcast feeds to add, from online directories and local OPML files.
+
+mod itunes;
+mod opml_file;
+mod podcast_index;
+
+use super::PodcastFeed;
+use crate::config::PodcastDirectorySettings;
+use crate::parallel::{Pending, Sources};
+use crate::types::{Msg, PCMsg};
+use anyhow::Result;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Number of results asked from each directory
+const SEARCH_LIMIT: u16 = 50;
+
+/// A place to search for podcast feeds by name.
+pub trait DirectoryProvider: Send + Sync {
+    /// Name used in `podcast_directories` of config.toml
+    fn name(&self) -> &'static str;
+
+    fn search(&self, query: &str, limit: u16) -> Result<Vec<PodcastFeed>>;
+}
+
+/// Create the directory described by `settings`. Directories missing the key or file
+/// they need are left out.
+fn build(settings: &PodcastDirectorySettings) -> Option<Arc<dyn DirectoryProvider>> {
+    let timeout = Duration::from_secs(settings.timeout_secs);
+    match settings.name.as_str() {
+        itunes::NAME => Some(Arc::new(itunes::Provider::new(timeout))),
+        podcast_index::NAME if !settings.api_key.is_empty() => Some(Arc::new(
+            podcast_index::Provider::new(&settings.api_key, &settings.api_secret, timeout),
+        )),
+        opml_file::NAME if !settings.path.is_empty() => {
+            Some(Arc::new(opml_file::Provider::new(&settings.path)))
+        }
+        _ => None,
+    }
+}
+
+/// The enabled directories, in the order their search results are listed.
+#[derive(Default)]
+pub struct Directories {
+    providers: Sources<dyn DirectoryProvider>,
+}
+
+impl Directories {
+    /// Build from config. Disabled and unknown directories are left out.
+    pub fn new(settings: &[PodcastDirectorySettings]) -> Self {
+        let mut directories = Self::default();
+        for s in settings.iter().filter(|s| s.enabled) {
+            if let Some(provider) = build(s) {
+                directories.push(provider, Duration::from_secs(s.timeout_secs));
+            }
+        }
+        directories
+    }
+
+    pub fn push(&mut self, provider: Arc<dyn DirectoryProvider>, timeout: Duration) {
+        self.providers.push(provider.name(), provider, timeout);
+    }
+
+    /// Search all directories in parallel and send the merged results to the podcast
+    /// view when done.
+    pub fn search(&self, query: &str, tx_to_main: Sender<Msg>) {
+        let pending = self.spawn_searches(query);
+        thread::spawn(move || {
+            let msg = match collect(pending) {
+                Ok(feeds) => PCMsg::SearchSuccess(feeds),
+                Err(e) => PCMsg::SearchError(e),
+            };
+            tx_to_main.send(Msg::Podcast(msg)).ok();
+        });
+    }
+
+    fn spawn_searches(&self, query: &str) -> Pending<Vec<PodcastFeed>> {
+        let query = query.to_string();
+        self.providers
+            .spawn(move |provider| provider.search(&query, SEARCH_LIMIT))
+    }
+}
+
+/// Results merged in directory order. It is an error only when no directory answered.
+fn collect(pending: Pending<Vec<PodcastFeed>>) -> Result<Vec<PodcastFeed>, String> {
+    if pending.is_empty() {
+        return Err("no podcast directory is enabled".to_string());
+    }
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    for (name, answer) in pending.collect() {
+        match answer {
+            Ok(feeds) => results.push(feeds),
+            Err(e) => errors.push(format!("{name}: {e}")),
+        }
+    }
+    if results.is_empty() {
+        return Err(errors.join(", "));
+    }
+    Ok(merge(results))
+}
+
+/// Feeds listed by more than one directory are kept once, where they first appear.
+fn merge(results: Vec<Vec<PodcastFeed>>) -> Vec<PodcastFeed> {
+    let mut merged: Vec<PodcastFeed> = Vec::new();
+    for feed in results.into_iter().flatten() {
+        let key = feed_key(&feed.url);
+        match merged.iter_mut().find(|f| feed_key(&f.url) == key) {
+            Some(existing) => {
+                if existing.title.is_none() {
+                    existing.title = feed.title;
+                }
+            }
+            None => merged.push(feed),
+        }
+    }
+    merged
+}
+
+/// Directories write the same feed url with or without tls or a trailing slash
+fn feed_key(url: &str) -> String {
+    let url = url.trim();
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    url.trim_end_matches('/').to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect, merge, Directories, DirectoryProvider, PodcastFeed};
+    use crate::config::Settings;
+    use anyhow::{bail, Result};
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Lists the given feed urls, or fails when there are none
+    struct Fake(&'static str, Vec<&'static str>);
+
+    impl DirectoryProvider for Fake {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+        fn search(&self, query: &str, _limit: u16) -> Result<Vec<PodcastFeed>> {
+            if self.1.is_empty() {
+                bail!("offline");
+            }
+            Ok(self
+                .1
+                .iter()
+                .map(|url| PodcastFeed::new(None, url, Some(format!("{query} {}", self.0))))
+                .collect())
+        }
+    }
+
+    fn directories(fakes: Vec<Fake>) -> Directories {
+        let mut directories = Directories::default();
+        for fake in fakes {
+            directories.push(Arc::new(fake), Duration::from_secs(5));
+        }
+        directories
+    }
+
+    fn urls(feeds: &[PodcastFeed]) -> Vec<&str> {
+        feeds.iter().map(|f| f.url.as_str()).collect()
+    }
+
+    #[test]
+    fn test_directories_from_config() {
+        let mut settings = Settings::default().podcast_directories;
+        // the others need a key or file first
+        assert_eq!(
+            Directories::new(&settings).providers.names(),
+            vec!["itunes"]
+        );
+
+        for s in &mut settings {
+            s.api_key = "key".to_string();
+            s.path = "directory.opml".to_string();
+        }
+        settings[0].enabled = false;
+        assert_eq!(
+            Directories::new(&settings).providers.names(),
+            vec!["podcastindex", "opml"]
+        );
+    }
+
+    #[test]
+    fn test_search_merges() {
+        let merging = directories(vec![
+            Fake(
+                "first",
+                vec!["https://a.example/feed", "https://b.example/rss"],
+            ),
+            Fake(
+                "second",
+                vec!["http://A.example/feed/", "https://c.example/rss"],
+            ),
+            Fake("broken", vec![]),
+        ]);
+        let feeds = collect(merging.spawn_searches("news")).unwrap();
+        assert_eq!(
+            urls(&feeds),
+            vec![
+                "https://a.example/feed",
+                "https://b.example/rss",
+                "https://c.example/rss"
+            ]
+        );
+        assert_eq!(feeds[0].title.as_deref(), Some("news first"));
+
+        let failing = directories(vec![Fake("broken", vec![])]);
+        assert_eq!(
+            collect(failing.spawn_searches("news")),
+            Err("broken: offline".to_string())
+        );
+        assert!(collect(Directories::default().spawn_searches("news")).is_err());
+    }
+
+    #[test]
+    fn test_merge_fills_title() {
+        let feeds = merge(vec![
+            vec![PodcastFeed::new(None, "https://a.example/feed", None)],
+            vec![PodcastFeed::new(
+                None,
+                "https://a.example/feed",
+                Some("A".to_string()),
+            )],
+        ]);
+        assert_eq!(
+            feeds,
+            vec![PodcastFeed::new(
+                None,
+                "https://a.example/feed",
+                Some("A".to_string())
+            )]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+// A local OPML file used as a podcast directory, like a list shared by friends.
+
+use super::DirectoryProvider;
+use crate::podcast::PodcastFeed;
+use anyhow::{Context, Result};
+use opml::{Outline, OPML};
+use std::path::PathBuf;
+
+pub const NAME: &str = "opml";
+
+pub struct Provider {
+    path: PathBuf,
+}
+
+impl Provider {
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: PathBuf::from(shellexpand::tilde(path).to_string()),
+        }
+    }
+}
+
+impl DirectoryProvider for Provider {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn search(&self, query: &str, limit: u16) -> Result<Vec<PodcastFeed>> {
+        let text = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("cannot read {}", self.path.display()))?;
+        search(&text, query, limit)
+    }
+}
+
+/// Feeds of the document, also those nested in folders, that contain every word of
+/// `query` in their title, text or description.
+fn search(text: &str, query: &str, limit: u16) -> Result<Vec<PodcastFeed>> {
+    let opml = OPML::from_str(text)?;
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let mut feeds = Vec::new();
+    collect(&opml.body.outlines, &words, &mut feeds);
+    feeds.truncate(limit.into());
+    Ok(feeds)
+}
+
+fn collect(outlines: &[Outline], words: &[String], feeds: &mut Vec<PodcastFeed>) {
+    for outline in outlines {
+        collect(&outline.outlines, words, feeds);
+        let Some(url) = &outline.xml_url else {
+            continue;
+        };
+        let haystack = [
+            Some(&outline.text),
+            outline.title.as_ref(),
+            outline.description.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|s| s.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+        if words.iter().all(|w| haystack.contains(w.as_str())) {
+            let title = outline
+                .title
+                .clone()
+                .filter(|t| !t.is_empty())
+                .or_else(|| Some(outline.text.clone()).filter(|t| !t.is_empty()));
+            feeds.push(PodcastFeed::new(None, url, title));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::search;
+    use crate::podcast::PodcastFeed;
+    use pretty_assertions::assert_eq;
+
+    const DIRECTORY: &str = include_str!("test-data/directory.opml");
+
+    #[test]
+    fn test_search() {
+        assert_eq!(
+            search(DIRECTORY, "Daily", 50).unwrap(),
+            vec![PodcastFeed::new(
+                None,
+                "https://feeds.simplecast.com/54nAGcIl",
+                Some("The Daily".to_string())
+            )]
+        );
+        assert_eq!(
+            search(DIRECTORY, "linux talk", 50).unwrap(),
+            vec![PodcastFeed::new(
+                None,
+                "https://feeds.jupiterbroadcasting.com/lup",
+                Some("Linux Unplugged".to_string())
+            )]
+        );
+        // the folder itself is no feed
+        assert_eq!(search(DIRECTORY, "news", 50).unwrap().len(), 1);
+        assert_eq!(search(DIRECTORY, "", 2).unwrap().len(), 2);
+        assert!(search(DIRECTORY, "feed", 50).unwrap().is_empty());
+        assert!(search("not opml", "news", 50).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+// Podcast search of podcastindex.org, which needs a free API key.
+
+use super::DirectoryProvider;
+use crate::podcast::PodcastFeed;
+use anyhow::{anyhow, Result};
+use reqwest::blocking::{Client, ClientBuilder};
+use serde_json::Value;
+use sha1_smol::Sha1;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+static URL_SEARCH_PODCAST_INDEX: &str = "https://api.podcastindex.org/api/1.0/search/byterm";
+
+pub const NAME: &str = "podcastindex";
+
+pub struct Provider {
+    client: Client,
+    api_key: String,
+    api_secret: String,
+}
+
+impl Provider {
+    pub fn new(api_key: &str, api_secret: &str, timeout: Duration) -> Self {
+        let client = ClientBuilder::new()
+            .timeout(timeout)
+            .user_agent(concat!("termusic/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .expect("failed to build reqwest client.");
+
+        Self {
+            client,
+            api_key: api_key.to_string(),
+            api_secret: api_secret.to_string(),
+        }
+    }
+}
+
+impl DirectoryProvider for Provider {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn search(&self, query: &str, limit: u16) -> Result<Vec<PodcastFeed>> {
+        let date = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_secs()
+            .to_string();
+        let limit = limit.to_string();
+        let text = self
+            .client
+            .get(URL_SEARCH_PODCAST_INDEX)
+            .query(&[("q", query), ("max", &limit)])
+            .header("X-Auth-Key", &self.api_key)
+            .header("X-Auth-Date", &date)
+            .header(
+                "Authorization",
+                authorization(&self.api_key, &self.api_secret, &date),
+            )
+            .send()?
+            .error_for_status()?
+            .text()?;
+        parse(&text)
+    }
+}
+
+/// Requests are signed with the sha1 of key, secret and the date sent along
+fn authorization(api_key: &str, api_secret: &str, date: &str) -> String {
+    Sha1::from(format!("{api_key}{api_secret}{date}"))
+        .digest()
+        .to_string()
+}
+
+/// Feeds without a url are skipped, an empty title counts as none.
+fn parse(text: &str) -> Result<Vec<PodcastFeed>> {
+    let value: Value = serde_json::from_str(text)?;
+    let feeds = value
+        .get("feeds")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("no feeds in answer"))?;
+    Ok(feeds
+        .iter()
+        .filter_map(|v| {
+            let url = v.get("url")?.as_str()?;
+            let title = v
+                .get("title")
+                .and_then(Value::as_str)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string);
+            Some(PodcastFeed::new(None, url, title))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{authorization, parse};
+    use crate::podcast::PodcastFeed;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse() {
+        let feeds = parse(include_str!("test-data/podcast_index.json")).unwrap();
+        assert_eq!(
+            feeds,
+            vec![
+                PodcastFeed::new(
+                    None,
+                    "https://mp3s.nashownotes.com/pc20rss.xml",
+                    Some("Podcasting 2.0".to_string())
+                ),
+                PodcastFeed::new(None, "https://feeds.example.com/untitled.xml", None),
+            ]
+        );
+        assert!(parse(r#"{"status": "false"}"#).is_err());
+    }
+
+    #[test]
+    fn test_authorization() {
+        assert_eq!(
+            authorization("key", "secret", "1700000000"),
+            "abaf71c02050c31e4d4e6b08c1625173af0445ba"
+        );
+    }
+}
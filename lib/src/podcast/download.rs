@@ -0,0 +1,478 @@
+// Downloading episode files, resuming partial downloads and sharing one speed limit.
+
+use super::db::Database;
+use super::EpData;
+use crate::types::PCMsg;
+use anyhow::Result;
+use base64::{engine::general_purpose, Engine as _};
+use lazy_static::lazy_static;
+use reqwest::blocking::{Client, ClientBuilder};
+use reqwest::header::{HeaderMap, CONTENT_RANGE, CONTENT_TYPE, RANGE};
+use reqwest::StatusCode;
+use sanitize_filename::{sanitize_with_options, Options};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Bytes read between checks of the speed limit
+const CHUNK: usize = 64 * 1024;
+
+lazy_static! {
+    /// Shared by all downloads, so the limit holds however many run at once
+    static ref LIMITER: Mutex<Limiter> = Mutex::new(Limiter::default());
+}
+
+/// Spaces out reads so all downloads together stay under a rate
+#[derive(Default)]
+struct Limiter {
+    /// Bytes per second, 0 is unlimited
+    rate: u64,
+    next_free: Option<Instant>,
+}
+
+impl Limiter {
+    /// How long to wait after reading `bytes` at `now`
+    fn reserve(&mut self, bytes: u64, now: Instant) -> Duration {
+        if self.rate == 0 {
+            self.next_free = None;
+            return Duration::ZERO;
+        }
+        let start = self.next_free.map_or(now, |next| next.max(now));
+        let next = start + Duration::from_micros(bytes.saturating_mul(1_000_000) / self.rate);
+        self.next_free = Some(next);
+        next - now
+    }
+}
+
+enum Failure {
+    /// No answer, a broken connection, or not all of the file
+    Response(String),
+    Create,
+    Write,
+}
+
+/// Name of the episode file without extension, from its title and publication date
+fn file_stem(ep_data: &EpData) -> String {
+    let file_name = sanitize_with_options(
+        &ep_data.title,
+        Options {
+            truncate: true,
+            windows: true, // for simplicity, we'll just use Windows-friendly paths for everyone
+            replacement: "",
+        },
+    );
+    match ep_data.pubdate {
+        Some(pubdate) => format!("{file_name}_{}", pubdate.format("%Y%m%d_%H%M%S")),
+        None => file_name,
+    }
+}
+
+/// Assume .mp3 unless the content type says otherwise
+fn extension(content_type: Option<&str>) -> &'static str {
+    match content_type {
+        Some("audio/x-m4a") => "m4a",
+        Some("video/quicktime") => "mov",
+        Some("video/mp4") => "mp4",
+        Some("video/x-m4v") => "m4v",
+        _ => "mp3",
+    }
+}
+
+/// Start and total size from a `Content-Range` like `bytes 100-999/1000`. The total
+/// can be unknown.
+fn content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _) = range.split_once('-')?;
+    Some((start.trim().parse().ok()?, total.trim().parse().ok()))
+}
+
+/// MD5 of the body the server vouches for in `Content-MD5`, or in a `Digest` header
+fn checksum(headers: &HeaderMap) -> Option<Vec<u8>> {
+    let value = match headers.get("content-md5") {
+        Some(value) => value.to_str().ok()?.trim().to_string(),
+        None => headers
+            .get("digest")?
+            .to_str()
+            .ok()?
+            .split(',')
+            .find_map(|digest| {
+                let (algorithm, value) = digest.trim().split_once('=')?;
+                algorithm
+                    .eq_ignore_ascii_case("md5")
+                    .then(|| value.to_string())
+            })?,
+    };
+    general_purpose::STANDARD
+        .decode(value)
+        .ok()
+        .filter(|sum| sum.len() == 16)
+}
+
+fn md5_of(path: &Path) -> Result<Vec<u8>, Failure> {
+    let mut file = File::open(path).map_err(|_| Failure::Write)?;
+    let mut context = md5::Context::new();
+    let mut buf = vec![0; CHUNK];
+    loop {
+        match file.read(&mut buf).map_err(|_| Failure::Write)? {
+            0 => return Ok(context.compute().to_vec()),
+            read => context.consume(&buf[..read]),
+        }
+    }
+}
+
+/// A file that arrived whole, and whether anything proved it is
+struct Fetched {
+    content_type: Option<String>,
+    checked: bool,
+}
+
+/// Copy the body to the file, waiting as the speed limit asks
+fn copy_limited(body: &mut impl Read, file: &mut File) -> Result<(), Failure> {
+    let mut buf = vec![0; CHUNK];
+    loop {
+        let read = match body.read(&mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(read) => read,
+            Err(e) => return Err(Failure::Response(e.to_string())),
+        };
+        file.write_all(&buf[..read]).map_err(|_| Failure::Write)?;
+        let wait = LIMITER.lock().map_or(Duration::ZERO, |mut l| {
+            l.reserve(read as u64, Instant::now())
+        });
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+    }
+}
+
+/// Fetch the rest of `url` into `part`, asking only for what is missing there. The file is
+/// checked against the checksum and size the server gives, and the size in the feed when the
+/// server gives none.
+fn fetch_rest(
+    client: &Client,
+    url: &str,
+    part: &Path,
+    enclosure_length: Option<u64>,
+) -> Result<Fetched, Failure> {
+    let have = fs::metadata(part).map_or(0, |m| m.len());
+    let mut request = client.get(url);
+    if have > 0 {
+        request = request.header(RANGE, format!("bytes={have}-"));
+    }
+    let response = request
+        .send()
+        .map_err(|e| Failure::Response(e.to_string()))?;
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        // the file on the server is not the one the part started from
+        fs::remove_file(part).ok();
+        return Err(Failure::Response("partial file does not match".to_string()));
+    }
+    let mut response = response
+        .error_for_status()
+        .map_err(|e| Failure::Response(e.to_string()))?;
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let resumed = have > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+    let total = if resumed {
+        let range = response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(content_range);
+        match range {
+            Some((start, total)) if start == have => total,
+            _ => {
+                fs::remove_file(part).ok();
+                return Err(Failure::Response("unexpected range in answer".to_string()));
+            }
+        }
+    } else {
+        response.content_length()
+    };
+    // a checksum of a range says nothing about the rest of the file
+    let checksum = if resumed {
+        None
+    } else {
+        checksum(response.headers())
+    };
+    let mut file = if resumed {
+        OpenOptions::new().append(true).open(part)
+    } else {
+        File::create(part)
+    }
+    .map_err(|_| Failure::Create)?;
+    copy_limited(&mut response, &mut file)?;
+
+    let len = file.metadata().map_err(|_| Failure::Write)?.len();
+    if len == 0 {
+        return Err(Failure::Response("empty file".to_string()));
+    }
+    if let Some(sum) = checksum {
+        if md5_of(part)? != sum {
+            fs::remove_file(part).ok();
+            return Err(Failure::Response("checksum does not match".to_string()));
+        }
+        return Ok(Fetched {
+            content_type,
+            checked: true,
+        });
+    }
+    let checked = match (total, enclosure_length) {
+        (Some(total), _) if len > total => {
+            fs::remove_file(part).ok();
+            return Err(Failure::Response(format!("got {len} of {total} bytes")));
+        }
+        (Some(total), _) | (None, Some(total)) if len < total => {
+            return Err(Failure::Response(format!("got {len} of {total} bytes")));
+        }
+        (Some(total), feed) => {
+            if feed.map_or(false, |feed| feed != total) {
+                debug!("{url} has {total} bytes, its feed says {feed:?}");
+            }
+            true
+        }
+        // the feed may be out of date, and the server did not say
+        (None, Some(feed)) => len == feed,
+        (None, None) => false,
+    };
+    Ok(Fetched {
+        content_type,
+        checked,
+    })
+}
+
+/// Downloads an episode into `dest`, returning a `PCMsg` telling whether it worked.
+/// Every try continues the `.part` file left by the one before, also from earlier runs,
+/// and the file only gets its real name once it is complete. A file nothing could be
+/// checked against is kept but reported with `DLUnchecked`.
+pub fn fetch_episode(
+    mut ep_data: EpData,
+    dest: &Path,
+    max_retries: usize,
+    speed_limit_kib: u64,
+) -> PCMsg {
+    if let Ok(mut limiter) = LIMITER.lock() {
+        limiter.rate = speed_limit_kib.saturating_mul(1024);
+    }
+    let client = ClientBuilder::new()
+        .connect_timeout(Duration::from_secs(10))
+        .gzip(false)
+        .build()
+        .expect("reqwest client build failed");
+
+    let stem = file_stem(&ep_data);
+    let part = dest.join(format!("{stem}.part"));
+    for attempt in 0..max_retries.max(1) {
+        if attempt > 0 {
+            thread::sleep(Duration::from_secs(attempt as u64));
+        }
+        match fetch_rest(&client, &ep_data.url, &part, ep_data.enclosure_length) {
+            Ok(fetched) => {
+                let path: PathBuf = dest.join(format!(
+                    "{stem}.{}",
+                    extension(fetched.content_type.as_deref())
+                ));
+                if fs::rename(&part, &path).is_err() {
+                    return PCMsg::DLFileWriteError(ep_data);
+                }
+                ep_data.file_path = Some(path);
+                if !fetched.checked {
+                    return PCMsg::DLUnchecked(ep_data);
+                }
+                return PCMsg::DLComplete(ep_data);
+            }
+            Err(Failure::Response(e)) => warn!("downloading {} failed: {e}", ep_data.url),
+            Err(Failure::Create) => return PCMsg::DLFileCreateError(ep_data),
+            Err(Failure::Write) => return PCMsg::DLFileWriteError(ep_data),
+        }
+    }
+    PCMsg::DLResponseError(ep_data)
+}
+
+/// Keep the list of failed downloads up to date with how a download ended
+pub fn record_result(db: &Database, msg: &PCMsg) -> Result<()> {
+    match msg {
+        PCMsg::DLComplete(ep) | PCMsg::DLUnchecked(ep) => db.remove_download_failed(ep.id),
+        PCMsg::DLResponseError(ep) => db.set_download_failed(ep.id, "server did not send it all"),
+        PCMsg::DLFileCreateError(ep) => db.set_download_failed(ep.id, "cannot create the file"),
+        PCMsg::DLFileWriteError(ep) => db.set_download_failed(ep.id, "cannot write the file"),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checksum, content_range, fetch_episode, Limiter};
+    use crate::podcast::EpData;
+    use crate::types::PCMsg;
+    use pretty_assertions::assert_eq;
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_content_range() {
+        assert_eq!(content_range("bytes 100-999/1000"), Some((100, Some(1000))));
+        assert_eq!(content_range("bytes 100-999/*"), Some((100, None)));
+        assert_eq!(content_range("items 1-2/3"), None);
+    }
+
+    #[test]
+    fn test_checksum() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(checksum(&headers), None);
+        headers.insert(
+            "digest",
+            HeaderValue::from_static("sha-256=X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=, MD5=rL0Y20zC+Fzt72VPzMSk2A=="),
+        );
+        assert_eq!(
+            checksum(&headers).map(hex::encode),
+            Some("acbd18db4cc2f85cedef654fccc4a4d8".to_string())
+        );
+        headers.insert("content-md5", HeaderValue::from_static("not base64"));
+        assert_eq!(checksum(&headers), None);
+    }
+
+    #[test]
+    fn test_limiter() {
+        let now = Instant::now();
+        let mut limiter = Limiter {
+            rate: 1000,
+            next_free: None,
+        };
+        assert_eq!(limiter.reserve(500, now), Duration::from_millis(500));
+        // a second download reading at the same time waits for the first
+        assert_eq!(limiter.reserve(500, now), Duration::from_millis(1000));
+        let later = now + Duration::from_secs(5);
+        assert_eq!(limiter.reserve(1000, later), Duration::from_secs(1));
+
+        limiter.rate = 0;
+        assert_eq!(limiter.reserve(1000, later), Duration::ZERO);
+    }
+
+    /// Serves `body` once cut off halfway, then the rest when asked for the range
+    fn flaky_server(body: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/episode", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for stream in listener.incoming().take(2) {
+                let mut stream = stream.unwrap();
+                let mut range = None;
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = line.to_lowercase().strip_prefix("range: bytes=") {
+                        range = value.trim().trim_end_matches('-').parse::<usize>().ok();
+                    }
+                }
+                let len = body.len();
+                let head = match range {
+                    None => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: audio/x-m4a\r\nContent-Length: {len}\r\n\r\n"
+                    ),
+                    Some(start) => format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Type: audio/x-m4a\r\nContent-Length: {}\r\nContent-Range: bytes {start}-{}/{len}\r\n\r\n",
+                        len - start,
+                        len - 1
+                    ),
+                };
+                stream.write_all(head.as_bytes()).unwrap();
+                match range {
+                    None => stream.write_all(&body[..len / 2]).unwrap(),
+                    Some(start) => stream.write_all(&body[start..]).unwrap(),
+                }
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_download_resumes() {
+        let body: &'static [u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+        let dest = std::env::temp_dir().join(format!("termusic-download-{}", std::process::id()));
+        std::fs::create_dir_all(&dest).unwrap();
+        let ep_data = EpData {
+            id: 1,
+            pod_id: 1,
+            title: "Episode".to_string(),
+            url: flaky_server(body),
+            pubdate: None,
+            enclosure_length: None,
+            file_path: None,
+        };
+
+        let PCMsg::DLComplete(ep_data) = fetch_episode(ep_data, &dest, 3, 0) else {
+            panic!("download failed");
+        };
+        let path = ep_data.file_path.unwrap();
+        assert_eq!(path, dest.join("Episode.m4a"));
+        assert_eq!(std::fs::read(&path).unwrap(), body);
+        assert!(!dest.join("Episode.part").exists());
+        std::fs::remove_dir_all(&dest).ok();
+    }
+
+    /// Serves `body` once without saying how long it is
+    fn unsized_server(body: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/episode", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let mut stream = listener.incoming().next().unwrap().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            stream.write_all(body).unwrap();
+        });
+        url
+    }
+
+    #[test]
+    fn test_download_checks_size() {
+        let body: &'static [u8] = b"0123456789";
+        let dest = std::env::temp_dir().join(format!("termusic-unsized-{}", std::process::id()));
+        std::fs::create_dir_all(&dest).unwrap();
+        let ep_data = |url, enclosure_length| EpData {
+            id: 1,
+            pod_id: 1,
+            title: "Episode".to_string(),
+            url,
+            pubdate: None,
+            enclosure_length,
+            file_path: None,
+        };
+
+        let msg = fetch_episode(ep_data(unsized_server(body), Some(10)), &dest, 1, 0);
+        assert!(matches!(msg, PCMsg::DLComplete(_)));
+        std::fs::remove_file(dest.join("Episode.mp3")).unwrap();
+
+        let msg = fetch_episode(ep_data(unsized_server(body), None), &dest, 1, 0);
+        assert!(matches!(msg, PCMsg::DLUnchecked(_)));
+        std::fs::remove_file(dest.join("Episode.mp3")).unwrap();
+
+        // the feed promises more than came, so the part is kept to resume from
+        let msg = fetch_episode(ep_data(unsized_server(body), Some(20)), &dest, 1, 0);
+        assert!(matches!(msg, PCMsg::DLResponseError(_)));
+        assert!(dest.join("Episode.part").exists());
+        std::fs::remove_dir_all(&dest).ok();
+    }
+}
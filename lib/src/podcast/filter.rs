@@ -0,0 +1,188 @@
+// View options deciding which episodes of a feed are listed, and in which order.
+
+use super::Episode;
+use anyhow::{bail, Result};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Sort {
+    /// The order of the feed
+    #[default]
+    Newest,
+    Oldest,
+    Longest,
+    Shortest,
+}
+
+/// Which episodes of a feed are listed. Keywords match anywhere in the title, ignoring
+/// case.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    pub unplayed_only: bool,
+    pub downloaded_only: bool,
+    pub sort: Sort,
+    /// When not empty, only episodes with one of these in their title are listed
+    pub include: Vec<String>,
+    /// Episodes with one of these in their title are hidden, like trailers
+    pub exclude: Vec<String>,
+}
+
+impl Filter {
+    pub fn is_off(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Drop the episodes the filter hides and sort the rest
+    pub fn apply(&self, episodes: &mut Vec<Episode>) {
+        episodes.retain(|ep| {
+            let title = ep.title.to_lowercase();
+            (!self.unplayed_only || !ep.played)
+                && (!self.downloaded_only || ep.path.is_some())
+                && (self.include.is_empty() || self.include.iter().any(|w| title.contains(w)))
+                && !self.exclude.iter().any(|w| title.contains(w))
+        });
+        match self.sort {
+            Sort::Newest => {}
+            Sort::Oldest => episodes.sort_by(|a, b| a.pubdate.cmp(&b.pubdate)),
+            Sort::Longest => episodes.sort_by(|a, b| b.duration.cmp(&a.duration)),
+            Sort::Shortest => episodes.sort_by(|a, b| a.duration.cmp(&b.duration)),
+        }
+    }
+}
+
+/// Written as words, like `unplayed oldest +interview -trailer`
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words = Vec::new();
+        if self.unplayed_only {
+            words.push("unplayed".to_string());
+        }
+        if self.downloaded_only {
+            words.push("downloaded".to_string());
+        }
+        match self.sort {
+            Sort::Newest => {}
+            Sort::Oldest => words.push("oldest".to_string()),
+            Sort::Longest => words.push("longest".to_string()),
+            Sort::Shortest => words.push("shortest".to_string()),
+        }
+        words.extend(self.include.iter().map(|w| format!("+{w}")));
+        words.extend(self.exclude.iter().map(|w| format!("-{w}")));
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+impl FromStr for Filter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut filter = Self::default();
+        for word in s.split_whitespace() {
+            let word = word.to_lowercase();
+            if let Some(keyword) = word.strip_prefix('+').filter(|w| !w.is_empty()) {
+                filter.include.push(keyword.to_string());
+                continue;
+            }
+            if let Some(keyword) = word.strip_prefix('-').filter(|w| !w.is_empty()) {
+                filter.exclude.push(keyword.to_string());
+                continue;
+            }
+            match word.as_str() {
+                "unplayed" => filter.unplayed_only = true,
+                "downloaded" => filter.downloaded_only = true,
+                "newest" => filter.sort = Sort::Newest,
+                "oldest" => filter.sort = Sort::Oldest,
+                "longest" => filter.sort = Sort::Longest,
+                "shortest" => filter.sort = Sort::Shortest,
+                _ => bail!(
+                    "expected unplayed, downloaded, newest, oldest, longest, shortest, +keyword or -keyword"
+                ),
+            }
+        }
+        Ok(filter)
+    }
+}
+
+/// Reads view options typed for one feed, or for all feeds when they include the word
+/// `all`. Returns the filter and whether it is for all feeds.
+pub fn parse_scoped(s: &str) -> Result<(Filter, bool)> {
+    let mut all_feeds = false;
+    let words: Vec<&str> = s
+        .split_whitespace()
+        .filter(|w| {
+            let all = w.eq_ignore_ascii_case("all");
+            all_feeds |= all;
+            !all
+        })
+        .collect();
+    Ok((words.join(" ").parse()?, all_feeds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_scoped, Filter, Sort};
+    use crate::podcast::Episode;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn episode(id: i64, title: &str, duration: i64, played: bool, downloaded: bool) -> Episode {
+        Episode {
+            id,
+            title: title.to_string(),
+            duration: Some(duration),
+            played,
+            path: downloaded.then(|| PathBuf::from("ep.mp3")),
+            ..Episode::default()
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let filter: Filter = "Unplayed longest +Interview -trailer -BONUS"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            filter,
+            Filter {
+                unplayed_only: true,
+                downloaded_only: false,
+                sort: Sort::Longest,
+                include: vec!["interview".to_string()],
+                exclude: vec!["trailer".to_string(), "bonus".to_string()],
+            }
+        );
+        assert_eq!(
+            filter.to_string(),
+            "unplayed longest +interview -trailer -bonus"
+        );
+        assert!("".parse::<Filter>().unwrap().is_off());
+        assert!("played".parse::<Filter>().is_err());
+        assert!("-".parse::<Filter>().is_err());
+
+        let (filter, all_feeds) = parse_scoped("downloaded All").unwrap();
+        assert!(all_feeds);
+        assert_eq!(filter.to_string(), "downloaded");
+        assert!(!parse_scoped("downloaded").unwrap().1);
+    }
+
+    #[test]
+    fn test_apply() {
+        let episodes = vec![
+            episode(1, "Trailer: season 2", 120, false, false),
+            episode(2, "An interview", 3000, true, true),
+            episode(3, "BONUS episode", 900, false, true),
+            episode(4, "Regular episode", 1800, false, false),
+        ];
+        let ids = |filter: &str| {
+            let mut episodes = episodes.clone();
+            filter.parse::<Filter>().unwrap().apply(&mut episodes);
+            episodes.iter().map(|ep| ep.id).collect::<Vec<i64>>()
+        };
+        assert_eq!(ids(""), vec![1, 2, 3, 4]);
+        assert_eq!(ids("-trailer -bonus"), vec![2, 4]);
+        assert_eq!(ids("unplayed shortest"), vec![1, 3, 4]);
+        assert_eq!(ids("downloaded longest"), vec![2, 3]);
+        assert_eq!(ids("+episode +interview -bonus"), vec![2, 4]);
+    }
+}
@@ -0,0 +1,588 @@
+// Syncing subscriptions and episode progress with gpodder.net or a compatible server.
+
+use super::db::Database;
+use super::{check_feed, PodcastFeed, Threadpool};
+use crate::config::{PodcastSyncSettings, Settings};
+use crate::track::Track;
+use crate::types::{Msg, PCMsg};
+use ahash::{AHashMap, AHashSet};
+use anyhow::Result;
+use chrono::Utc;
+use reqwest::blocking::{Client, ClientBuilder};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Episodes played up to this many seconds before their end count as played
+const PLAYED_MARGIN_SECS: u64 = 30;
+
+/// What the server knows of an episode, as this device last sent or received it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EpisodeState {
+    pub podcast: String,
+    pub episode: String,
+    pub played: bool,
+    /// Seconds
+    pub position: u64,
+    pub duration: Option<i64>,
+    pub downloaded: bool,
+}
+
+/// Where the last sync with an account stopped
+#[derive(Debug, Default)]
+pub struct SyncState {
+    pub subscriptions_since: i64,
+    pub episodes_since: i64,
+    pub subscriptions: Vec<String>,
+    pub episodes: Vec<EpisodeState>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Action {
+    Play,
+    Download,
+    Delete,
+    New,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct EpisodeAction {
+    podcast: String,
+    episode: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    device: Option<String>,
+    action: Action,
+    /// Like `2023-01-31T20:15:00`, in UTC
+    #[serde(default)]
+    timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    started: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SubscriptionChanges {
+    add: Vec<String>,
+    remove: Vec<String>,
+    #[serde(default, skip_serializing)]
+    timestamp: i64,
+}
+
+#[derive(Deserialize)]
+struct EpisodeActions {
+    actions: Vec<EpisodeAction>,
+    timestamp: i64,
+}
+
+#[derive(Deserialize)]
+struct Uploaded {
+    timestamp: i64,
+}
+
+/// What a remote episode action does to the local episode
+#[derive(Debug, PartialEq, Eq)]
+enum Change {
+    Position(u64),
+    Played,
+    Unplayed,
+}
+
+/// Client for the subscription and episode action parts of the gpodder api
+struct Api {
+    client: Client,
+    url: String,
+    username: String,
+    password: String,
+    device: String,
+}
+
+impl Api {
+    fn new(settings: &PodcastSyncSettings) -> Result<Self> {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(settings.timeout_secs))
+            .build()?;
+        Ok(Self {
+            client,
+            url: settings.url.trim_end_matches('/').to_string(),
+            username: settings.username.clone(),
+            password: settings.password.clone(),
+            device: settings.device.clone(),
+        })
+    }
+
+    fn subscriptions_url(&self) -> String {
+        format!(
+            "{}/api/2/subscriptions/{}/{}.json",
+            self.url, self.username, self.device
+        )
+    }
+
+    fn episodes_url(&self) -> String {
+        format!("{}/api/2/episodes/{}.json", self.url, self.username)
+    }
+
+    fn get_subscriptions(&self, since: i64) -> Result<SubscriptionChanges> {
+        Ok(self
+            .client
+            .get(self.subscriptions_url())
+            .basic_auth(&self.username, Some(&self.password))
+            .query(&[("since", since)])
+            .send()?
+            .error_for_status()?
+            .json()?)
+    }
+
+    fn upload_subscriptions(&self, changes: &SubscriptionChanges) -> Result<i64> {
+        let uploaded: Uploaded = self
+            .client
+            .post(self.subscriptions_url())
+            .basic_auth(&self.username, Some(&self.password))
+            .json(changes)
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(uploaded.timestamp)
+    }
+
+    fn get_episode_actions(&self, since: i64) -> Result<EpisodeActions> {
+        Ok(self
+            .client
+            .get(self.episodes_url())
+            .basic_auth(&self.username, Some(&self.password))
+            .query(&[("since", since)])
+            .send()?
+            .error_for_status()?
+            .json()?)
+    }
+
+    fn upload_episode_actions(&self, actions: &[EpisodeAction]) -> Result<()> {
+        self.client
+            .post(self.episodes_url())
+            .basic_auth(&self.username, Some(&self.password))
+            .json(actions)
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Feeds subscribed and unsubscribed here since the last sync
+fn subscription_changes(synced: &[String], current: &[String]) -> SubscriptionChanges {
+    SubscriptionChanges {
+        add: current
+            .iter()
+            .filter(|url| !synced.contains(url))
+            .cloned()
+            .collect(),
+        remove: synced
+            .iter()
+            .filter(|url| !current.contains(url))
+            .cloned()
+            .collect(),
+        timestamp: 0,
+    }
+}
+
+/// Actions telling the server how episodes changed here since the last sync. Being
+/// marked played is sent as playing to the end.
+fn local_actions(
+    synced: &[EpisodeState],
+    current: &[EpisodeState],
+    device: &str,
+    timestamp: &str,
+) -> Vec<EpisodeAction> {
+    let synced: AHashMap<&str, &EpisodeState> =
+        synced.iter().map(|s| (s.episode.as_str(), s)).collect();
+    let unknown = EpisodeState::default();
+    let mut actions = Vec::new();
+    for state in current {
+        let old = synced
+            .get(state.episode.as_str())
+            .copied()
+            .unwrap_or(&unknown);
+        let action = |action, position: Option<u64>| EpisodeAction {
+            podcast: state.podcast.clone(),
+            episode: state.episode.clone(),
+            device: Some(device.to_string()),
+            action,
+            timestamp: timestamp.to_string(),
+            started: position.map(|_| 0),
+            position,
+            total: position.and(state.duration.and_then(|d| u64::try_from(d).ok())),
+        };
+        if state.played && !old.played {
+            let end = state
+                .duration
+                .and_then(|d| u64::try_from(d).ok())
+                .unwrap_or(state.position);
+            actions.push(action(Action::Play, Some(end)));
+        } else if !state.played && old.played {
+            actions.push(action(Action::New, None));
+        } else if !state.played && state.position != old.position {
+            actions.push(action(Action::Play, Some(state.position)));
+        }
+        if state.downloaded != old.downloaded {
+            let file_action = if state.downloaded {
+                Action::Download
+            } else {
+                Action::Delete
+            };
+            actions.push(action(file_action, None));
+        }
+    }
+    actions
+}
+
+/// The latest progress of each episode from other devices. Episodes with changes of
+/// their own are left out, those are sent to the server instead. Downloads and deletes
+/// are about files of the other device and change nothing here.
+fn remote_changes(actions: &[EpisodeAction], local: &AHashSet<&str>) -> AHashMap<String, Change> {
+    let mut latest: AHashMap<&str, &EpisodeAction> = AHashMap::new();
+    for action in actions {
+        if !matches!(action.action, Action::Play | Action::New)
+            || local.contains(action.episode.as_str())
+        {
+            continue;
+        }
+        let newer = latest
+            .get(action.episode.as_str())
+            .map_or(true, |seen| seen.timestamp <= action.timestamp);
+        if newer {
+            latest.insert(&action.episode, action);
+        }
+    }
+    latest
+        .into_iter()
+        .map(|(episode, action)| {
+            let change = match (action.action, action.position, action.total) {
+                (Action::New, _, _) => Change::Unplayed,
+                (_, Some(position), Some(total))
+                    if total > 0 && position + PLAYED_MARGIN_SECS >= total =>
+                {
+                    Change::Played
+                }
+                (_, position, _) => Change::Position(position.unwrap_or(0)),
+            };
+            (episode.to_string(), change)
+        })
+        .collect()
+}
+
+/// Subscribe to feeds added on other devices and drop those removed there. Returns how
+/// many feeds changed, and how many added ones could not be fetched.
+fn apply_subscriptions(
+    db: &Database,
+    config: &Settings,
+    remote: &SubscriptionChanges,
+    local: &SubscriptionChanges,
+) -> Result<(usize, usize)> {
+    let podcasts = db.get_podcasts()?;
+    let mut changed = 0;
+    for url in &remote.remove {
+        if local.add.contains(url) {
+            continue;
+        }
+        if let Some(pod) = podcasts.iter().find(|pod| &pod.url == url) {
+            db.remove_podcast(pod.id)?;
+            changed += 1;
+        }
+    }
+
+    let new_feeds: Vec<&String> = remote
+        .add
+        .iter()
+        .filter(|url| !local.remove.contains(url) && !podcasts.iter().any(|pod| &pod.url == *url))
+        .collect();
+    if new_feeds.is_empty() {
+        return Ok((changed, 0));
+    }
+    let threadpool = Threadpool::new(config.podcast_simultanious_download);
+    let (tx_to_main, rx_to_main) = mpsc::channel();
+    for url in &new_feeds {
+        check_feed(
+            PodcastFeed::new(None, url, None),
+            config.podcast_max_retries,
+            &threadpool,
+            tx_to_main.clone(),
+        );
+    }
+    drop(tx_to_main);
+    let (added, failed) = insert_new_feeds(db, &rx_to_main, new_feeds.len());
+    Ok((changed + added, failed))
+}
+
+/// Save the feeds fetched by `check_feed` until each of the `count` feeds has answered
+/// with its data or an error. Returns how many feeds were added and how many failed.
+fn insert_new_feeds(
+    db: &Database,
+    rx_to_main: &mpsc::Receiver<Msg>,
+    count: usize,
+) -> (usize, usize) {
+    let mut added = 0;
+    let mut failed = 0;
+    let mut remaining = count;
+    while remaining > 0 {
+        let Ok(message) = rx_to_main.recv() else {
+            break;
+        };
+        match message {
+            Msg::Podcast(PCMsg::NewData(pod)) => {
+                remaining -= 1;
+                match db.insert_podcast(&pod) {
+                    Ok(_) => added += 1,
+                    Err(e) => {
+                        failed += 1;
+                        error!("error saving synced feed {}: {e}", pod.url);
+                    }
+                }
+            }
+            Msg::Podcast(PCMsg::Error(url, _)) => {
+                remaining -= 1;
+                failed += 1;
+                warn!("cannot fetch synced feed {url}");
+            }
+            _ => {}
+        }
+    }
+    failed += remaining;
+    (added, failed)
+}
+
+/// Mark episodes played or move them to where other devices stopped. Returns how many
+/// episodes changed.
+fn apply_episodes(db: &mut Database, remote: &AHashMap<String, Change>) -> Result<usize> {
+    let mut changed = 0;
+    for (url, change) in remote {
+        let Some(ep) = db.get_episode_by_url(url)? else {
+            continue;
+        };
+        match change {
+            Change::Played if !ep.played => db.set_played_status(ep.id, true)?,
+            Change::Unplayed if ep.played => db.set_played_status(ep.id, false)?,
+            Change::Position(position) if ep.last_position != i64::try_from(*position).ok() => {
+                db.set_last_position(&Track::from_episode(&ep), Duration::from_secs(*position));
+            }
+            _ => continue,
+        }
+        changed += 1;
+    }
+    Ok(changed)
+}
+
+/// Account the sync state belongs to, a different one starts over
+fn account(settings: &PodcastSyncSettings) -> String {
+    format!(
+        "{}@{} {}",
+        settings.username,
+        settings.url.trim_end_matches('/'),
+        settings.device
+    )
+}
+
+/// Exchange the changes since the last sync with the server. Changes made here win over
+/// those of other devices. Returns how many feeds and episodes changed here.
+pub fn sync(db: &mut Database, config: &Settings) -> Result<usize> {
+    let settings = &config.podcast_sync;
+    let api = Api::new(settings)?;
+    let account = account(settings);
+    let state = db.get_sync_state(&account)?;
+
+    let current: Vec<String> = db.get_podcasts()?.into_iter().map(|pod| pod.url).collect();
+    let local = subscription_changes(&state.subscriptions, &current);
+    let remote = api.get_subscriptions(state.subscriptions_since)?;
+    let (mut changed, failed) = apply_subscriptions(db, config, &remote, &local)?;
+    let mut subscriptions_since = remote.timestamp;
+    if !local.add.is_empty() || !local.remove.is_empty() {
+        subscriptions_since = api.upload_subscriptions(&local)?.max(subscriptions_since);
+    }
+    if failed > 0 {
+        // ask for the same changes again next time, so those feeds are tried once more
+        warn!("{failed} synced feeds not added, trying them again next sync");
+        subscriptions_since = state.subscriptions_since;
+    }
+
+    let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+    let actions = local_actions(
+        &state.episodes,
+        &db.get_episode_states()?,
+        &settings.device,
+        &timestamp,
+    );
+    let remote = api.get_episode_actions(state.episodes_since)?;
+    let local_episodes: AHashSet<&str> = actions.iter().map(|a| a.episode.as_str()).collect();
+    changed += apply_episodes(db, &remote_changes(&remote.actions, &local_episodes))?;
+    if !actions.is_empty() {
+        api.upload_episode_actions(&actions)?;
+    }
+
+    db.set_sync_state(
+        &account,
+        &SyncState {
+            subscriptions_since,
+            episodes_since: remote.timestamp,
+            subscriptions: db.get_podcasts()?.into_iter().map(|pod| pod.url).collect(),
+            episodes: db.get_episode_states()?,
+        },
+    )?;
+    info!(
+        "synced with {}: sent {} feed changes and {} episode actions, {changed} changes here",
+        settings.url,
+        local.add.len() + local.remove.len(),
+        actions.len()
+    );
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        insert_new_feeds, local_actions, remote_changes, subscription_changes, Action, Change,
+        EpisodeAction, EpisodeState,
+    };
+    use crate::podcast::db::Database;
+    use crate::podcast::{PodcastFeed, PodcastNoId};
+    use crate::types::{Msg, PCMsg};
+    use ahash::AHashSet;
+    use chrono::Utc;
+    use pretty_assertions::assert_eq;
+    use std::sync::mpsc;
+
+    fn state(episode: &str, played: bool, position: u64, downloaded: bool) -> EpisodeState {
+        EpisodeState {
+            podcast: "https://example.com/feed".to_string(),
+            episode: episode.to_string(),
+            played,
+            position,
+            duration: Some(600),
+            downloaded,
+        }
+    }
+
+    #[test]
+    fn test_subscription_changes() {
+        let synced = vec!["a".to_string(), "b".to_string()];
+        let current = vec!["b".to_string(), "c".to_string()];
+        let changes = subscription_changes(&synced, &current);
+        assert_eq!(changes.add, vec!["c".to_string()]);
+        assert_eq!(changes.remove, vec!["a".to_string()]);
+        assert_eq!(
+            serde_json::to_string(&changes).unwrap(),
+            r#"{"add":["c"],"remove":["a"]}"#
+        );
+    }
+
+    #[test]
+    fn test_local_actions() {
+        let synced = vec![
+            state("played", false, 100, false),
+            state("moved", false, 100, true),
+            state("unplayed", true, 0, false),
+            state("same", false, 100, false),
+        ];
+        let current = vec![
+            state("played", true, 0, false),
+            state("moved", false, 250, false),
+            state("unplayed", false, 0, false),
+            state("same", false, 100, false),
+            state("new", false, 0, true),
+        ];
+        let actions = local_actions(&synced, &current, "laptop", "2023-01-31T20:15:00");
+        let summary: Vec<(&str, Action, Option<u64>)> = actions
+            .iter()
+            .map(|a| (a.episode.as_str(), a.action, a.position))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("played", Action::Play, Some(600)),
+                ("moved", Action::Play, Some(250)),
+                ("moved", Action::Delete, None),
+                ("unplayed", Action::New, None),
+                ("new", Action::Download, None),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&actions[1]).unwrap(),
+            r#"{"podcast":"https://example.com/feed","episode":"moved","device":"laptop","action":"play","timestamp":"2023-01-31T20:15:00","started":0,"position":250,"total":600}"#
+        );
+    }
+
+    #[test]
+    fn test_remote_changes() {
+        let actions: Vec<EpisodeAction> = serde_json::from_str(
+            r#"[
+                {"podcast": "p", "episode": "a", "action": "play", "timestamp": "2023-01-02T10:00:00", "position": 120, "total": 600},
+                {"podcast": "p", "episode": "a", "action": "play", "timestamp": "2023-01-01T10:00:00", "position": 60, "total": 600},
+                {"podcast": "p", "episode": "b", "action": "play", "timestamp": "2023-01-01T10:00:00", "position": 590, "total": 600},
+                {"podcast": "p", "episode": "c", "action": "new", "timestamp": "2023-01-01T10:00:00"},
+                {"podcast": "p", "episode": "d", "action": "download", "timestamp": "2023-01-01T10:00:00"},
+                {"podcast": "p", "episode": "e", "action": "play", "timestamp": "2023-01-01T10:00:00", "position": 30},
+                {"podcast": "p", "episode": "f", "action": "flattr", "timestamp": "2023-01-01T10:00:00"}
+            ]"#,
+        )
+        .unwrap();
+        let mut local = AHashSet::new();
+        local.insert("e");
+        let changes = remote_changes(&actions, &local);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes["a"], Change::Position(120));
+        assert_eq!(changes["b"], Change::Played);
+        assert_eq!(changes["c"], Change::Unplayed);
+
+        let changes = remote_changes(&actions, &AHashSet::new());
+        assert_eq!(changes["e"], Change::Position(30));
+    }
+
+    #[test]
+    fn test_insert_new_feeds() {
+        let dir = std::env::temp_dir().join(format!("termusic-gpodder-{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        let db = Database::connect(&dir).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let urls = [
+            "https://a.example/feed",
+            "https://b.example/feed",
+            "https://c.example/feed",
+        ];
+        for url in urls {
+            tx.send(Msg::Podcast(PCMsg::FetchPodcastStart(url.to_string())))
+                .unwrap();
+        }
+        for url in &urls[..2] {
+            tx.send(Msg::Podcast(PCMsg::NewData(PodcastNoId {
+                title: (*url).to_string(),
+                url: (*url).to_string(),
+                description: None,
+                author: None,
+                explicit: None,
+                last_checked: Utc::now(),
+                episodes: Vec::new(),
+                image_url: None,
+            })))
+            .unwrap();
+        }
+        tx.send(Msg::Podcast(PCMsg::Error(
+            urls[2].to_string(),
+            PodcastFeed::new(None, urls[2], None),
+        )))
+        .unwrap();
+
+        assert_eq!(insert_new_feeds(&db, &rx, urls.len()), (2, 1));
+        let mut saved: Vec<String> = db
+            .get_podcasts()
+            .unwrap()
+            .into_iter()
+            .map(|pod| pod.url)
+            .collect();
+        saved.sort();
+        assert_eq!(saved, vec![urls[0].to_string(), urls[1].to_string()]);
+        drop(db);
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -1,7 +1,17 @@
 // Thanks to the author of shellcaster(https://github.com/jeff-hughes/shellcaster). Most parts of following code are taken from it.
 
+pub mod chapters;
 #[allow(unused)]
 pub mod db;
+pub mod discovery;
+pub mod download;
+pub mod filter;
+pub mod gpodder;
+pub mod notes;
+pub mod policy;
+pub mod queue;
+pub mod schedule;
+pub mod transcript;
 
 use crate::config::Settings;
 use crate::types::{Msg, PCMsg};
@@ -15,7 +25,6 @@ use regex::{Match, Regex};
 use reqwest::blocking::ClientBuilder;
 use rfc822_sanitizer::parse_from_rfc2822_with_fallback;
 use rss::{Channel, Item};
-use sanitize_filename::{sanitize_with_options, Options};
 use std::cmp::Ordering;
 use std::fs::File;
 use std::io::{Read, Write};
@@ -151,8 +160,13 @@ pub struct Episode {
     pub duration: Option<i64>,
     pub path: Option<PathBuf>,
     pub played: bool,
+    pub played_at: Option<DateTime<Utc>>,
     pub last_position: Option<i64>,
     pub image_url: Option<String>,
+    pub chapters_url: Option<String>,
+    pub transcript_url: Option<String>,
+    /// Size of the file in bytes as the feed gives it
+    pub enclosure_length: Option<i64>,
 }
 
 impl Episode {
@@ -255,6 +269,10 @@ pub struct EpisodeNoId {
     pub pubdate: Option<DateTime<Utc>>,
     pub duration: Option<i64>,
     pub image_url: Option<String>,
+    pub chapters: Vec<chapters::Chapter>,
+    pub chapters_url: Option<String>,
+    pub transcript_url: Option<String>,
+    pub enclosure_length: Option<i64>,
 }
 
 /// Struct holding data about an individual podcast episode, specifically
@@ -412,6 +430,11 @@ fn parse_episode_data(item: &Item) -> EpisodeNoId {
         Some(enc) => enc.url().to_string(),
         None => String::new(),
     };
+    // many feeds put 0 or a made up number here
+    let enclosure_length = item
+        .enclosure()
+        .and_then(|enc| enc.length().trim().parse::<i64>().ok())
+        .filter(|length| *length > 0);
     let guid = match item.guid() {
         Some(guid) => guid.value().to_string(),
         None => String::new(),
@@ -450,6 +473,10 @@ fn parse_episode_data(item: &Item) -> EpisodeNoId {
         pubdate,
         duration,
         image_url,
+        chapters: chapters::from_podlove(item),
+        chapters_url: chapters::json_url(item),
+        transcript_url: transcript::url(item),
+        enclosure_length,
     }
 }
 
@@ -809,6 +836,8 @@ pub struct EpData {
     pub title: String,
     pub url: String,
     pub pubdate: Option<DateTime<Utc>>,
+    /// Size of the file in bytes as the feed gives it
+    pub enclosure_length: Option<u64>,
     pub file_path: Option<PathBuf>,
 }
 
@@ -819,10 +848,12 @@ pub struct EpData {
 pub fn download_list(
     episodes: Vec<EpData>,
     dest: &Path,
-    max_retries: usize,
+    config: &Settings,
     threadpool: &Threadpool,
     tx_to_main: &Sender<Msg>,
 ) {
+    let max_retries = config.podcast_max_retries;
+    let speed_limit = config.podcast_download_speed_limit;
     // parse episode details and push to queue
     for ep in episodes {
         let tx = tx_to_main.clone();
@@ -830,90 +861,9 @@ pub fn download_list(
         threadpool.execute(move || {
             tx.send(Msg::Podcast(PCMsg::DLStart(ep.clone())))
                 .expect("Thread messaging error when start download");
-            let result = download_file(ep, dest2, max_retries);
+            let result = download::fetch_episode(ep, &dest2, max_retries, speed_limit);
             tx.send(Msg::Podcast(result))
                 .expect("Thread messaging error");
         });
     }
 }
-
-/// Downloads a file to a local filepath, returning `DownloadMsg` variant
-/// indicating success or failure.
-#[allow(clippy::single_match_else)]
-fn download_file(mut ep_data: EpData, destination_path: PathBuf, mut max_retries: usize) -> PCMsg {
-    let agent = ClientBuilder::new()
-        .connect_timeout(Duration::from_secs(10))
-        .build()
-        .expect("reqwest client build failed");
-
-    let request: Result<reqwest::blocking::Response, ()> = loop {
-        let response = agent.get(&ep_data.url).send();
-        match response {
-            Ok(resp) => break Ok(resp),
-            Err(_) => {
-                max_retries -= 1;
-                if max_retries == 0 {
-                    break Err(());
-                }
-            }
-        }
-    };
-
-    if request.is_err() {
-        return PCMsg::DLResponseError(ep_data);
-    };
-
-    let mut response = request.unwrap();
-
-    // figure out the file type
-    let mut ext = "mp3";
-    match response.headers().get("content-type") {
-        Some(content_type) => match content_type.to_str() {
-            Ok("audio/x-m4a") => ext = "m4a",
-            // Ok("audio/mpeg") => ext = "mp3",
-            Ok("video/quicktime") => ext = "mov",
-            Ok("video/mp4") => ext = "mp4",
-            Ok("video/x-m4v") => ext = "m4v",
-            Ok(_) | Err(_) => ext = "mp3",
-        },
-        None => error!("The response doesn't contain a content type"),
-        // Some("audio/x-m4a") => "m4a",
-        // // Some("audio/mpeg") => "mp3",
-        // Some("video/quicktime") => "mov",
-        // Some("video/mp4") => "mp4",
-        // Some("video/x-m4v") => "m4v",
-        // _ => "mp3", // assume .mp3 unless we figure out otherwise
-    };
-
-    let mut file_name = sanitize_with_options(
-        &ep_data.title,
-        Options {
-            truncate: true,
-            windows: true, // for simplicity, we'll just use Windows-friendly paths for everyone
-            replacement: "",
-        },
-    );
-
-    if let Some(pubdate) = ep_data.pubdate {
-        file_name = format!("{file_name}_{}", pubdate.format("%Y%m%d_%H%M%S"));
-    }
-
-    let mut file_path = destination_path;
-    file_path.push(format!("{file_name}.{ext}"));
-
-    let dst = File::create(&file_path);
-    if dst.is_err() {
-        return PCMsg::DLFileCreateError(ep_data);
-    };
-
-    ep_data.file_path = Some(file_path);
-
-    let mut resp_data = Vec::new();
-    let _drop = response.read_to_end(&mut resp_data);
-
-    match dst.unwrap().write_all(&resp_data) {
-        // match std::io::copy(&mut resp_data, &mut dst.unwrap()) {
-        Ok(_) => PCMsg::DLComplete(ep_data),
-        Err(_) => PCMsg::DLFileWriteError(ep_data),
-    }
-}
@@ -0,0 +1,360 @@
+// Show notes of an episode turned into terminal text, with numbered links and timestamps.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::time::Duration;
+
+lazy_static! {
+    /// An HTML tag, with its name when it is an opening or closing tag
+    static ref RE_TAG: Regex = Regex::new(r"<(/?)([a-zA-Z][a-zA-Z0-9]*)([^<>]*)>|<[^<>]*>").expect("Regex error");
+
+    /// The `href` of a link tag
+    static ref RE_HREF: Regex = Regex::new(r#"(?i)href\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#).expect("Regex error");
+
+    /// White space, newlines included
+    static ref RE_SPACES: Regex = Regex::new(r"\s+").expect("Regex error");
+
+    /// The number given to a link, like `[1]`
+    static ref RE_LINK_NUMBER: Regex = Regex::new(r" \[(\d+)\]").expect("Regex error");
+
+    /// A link written out in the text
+    static ref RE_URL: Regex = Regex::new(r"https?://[^\s<>()\[\]]+[^\s<>()\[\].,;:!?'\x22]").expect("Regex error");
+
+    /// A timestamp like 1:02:03 or 12:34, not part of a longer run of digits and colons
+    static ref RE_TIMESTAMP: Regex = Regex::new(r"(?:^|[^\d:])((?:(\d{1,2}):)?(\d{1,2}):(\d{2}))(?:$|[^\d:])").expect("Regex error");
+}
+
+/// One line of the notes, before it is wrapped to the panel
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Line {
+    pub text: String,
+    /// A heading, or a line that is bold all through
+    pub bold: bool,
+    /// Where the first timestamp of the line points to
+    pub seek: Option<Duration>,
+    /// Number of the first link in the line, counting from 1
+    pub link: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Notes {
+    pub lines: Vec<Line>,
+    /// Links in the order they are numbered, each only once
+    pub links: Vec<String>,
+}
+
+impl Notes {
+    /// Renders an episode description, which can be HTML or plain text. Paragraphs are
+    /// separated by a blank line, list items get a bullet or their number, and every
+    /// link gets a number like `[1]` after its text.
+    pub fn parse(description: &str) -> Self {
+        let mut builder = Builder::default();
+        if !RE_TAG.is_match(description) {
+            for line in description.lines() {
+                builder.text(line, false);
+                builder.end_line();
+            }
+            return builder.finish();
+        }
+
+        let mut last = 0;
+        for tag in RE_TAG.captures_iter(description) {
+            let whole = tag.get(0).expect("match without text");
+            builder.text(&description[last..whole.start()], true);
+            last = whole.end();
+            let Some(name) = tag.get(2) else {
+                continue;
+            };
+            let closing = !tag[1].is_empty() || tag[3].trim_end().ends_with('/');
+            builder.tag(&name.as_str().to_lowercase(), closing, &tag[3]);
+        }
+        builder.text(&description[last..], true);
+        builder.finish()
+    }
+}
+
+#[derive(Default)]
+struct Builder {
+    notes: Notes,
+    line: String,
+    /// Text of the line so far written bold, and not bold
+    has_bold: bool,
+    has_plain: bool,
+    bold_depth: usize,
+    /// Open lists, holding the number of the last item of numbered ones
+    lists: Vec<Option<usize>>,
+    /// Link being written, to be numbered when it ends
+    link: Option<String>,
+}
+
+impl Builder {
+    fn text(&mut self, text: &str, html: bool) {
+        let decoded = escaper::decode_html(text).unwrap_or_else(|_| text.to_string());
+        // in HTML any run of white space is one space
+        let text = if html {
+            RE_SPACES.replace_all(&decoded, " ").into_owned()
+        } else {
+            decoded
+        };
+        let text = if self.line.is_empty() || self.line.ends_with(' ') {
+            text.trim_start()
+        } else {
+            &text
+        };
+        if text.is_empty() {
+            return;
+        }
+        if !text.trim().is_empty() {
+            if self.bold_depth > 0 {
+                self.has_bold = true;
+            } else {
+                self.has_plain = true;
+            }
+        }
+        if self.link.is_some() {
+            self.line.push_str(text);
+            return;
+        }
+        // links written out in the text are numbered too
+        let mut last = 0;
+        for url in RE_URL.find_iter(text) {
+            self.line.push_str(&text[last..url.end()]);
+            let number = self.number(url.as_str());
+            self.line.push_str(&format!(" [{number}]"));
+            last = url.end();
+        }
+        self.line.push_str(&text[last..]);
+    }
+
+    fn tag(&mut self, name: &str, closing: bool, attributes: &str) {
+        match (name, closing) {
+            ("br", _) => self.end_line(),
+            ("p" | "div" | "blockquote" | "pre" | "table" | "tr" | "hr", _) => self.block(),
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                self.block();
+                self.bold_depth += 1;
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => {
+                self.bold_depth = self.bold_depth.saturating_sub(1);
+                self.block();
+            }
+            ("b" | "strong", false) => self.bold_depth += 1,
+            ("b" | "strong", true) => self.bold_depth = self.bold_depth.saturating_sub(1),
+            ("ul" | "ol", false) => {
+                if self.lists.is_empty() {
+                    self.block();
+                } else {
+                    self.break_line();
+                }
+                self.lists.push((name == "ol").then_some(0));
+            }
+            ("ul" | "ol", true) => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.block();
+                } else {
+                    self.break_line();
+                }
+            }
+            ("li", false) => {
+                self.break_line();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{number}. ")
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.line = format!("{indent}{marker}");
+            }
+            ("li", true) => self.break_line(),
+            ("a", false) => {
+                self.link = RE_HREF.captures(attributes).and_then(|c| {
+                    c.get(1)
+                        .or_else(|| c.get(2))
+                        .or_else(|| c.get(3))
+                        .map(|m| escaper::decode_html(m.as_str()).unwrap_or_default())
+                        .filter(|href| href.starts_with("http"))
+                });
+            }
+            ("a", true) => {
+                if let Some(href) = self.link.take() {
+                    let number = self.number(&href);
+                    self.line = self.line.trim_end().to_string();
+                    self.line.push_str(&format!(" [{number}]"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Number of a link, the one it already got when it came up before
+    fn number(&mut self, url: &str) -> usize {
+        let index = self
+            .notes
+            .links
+            .iter()
+            .position(|link| link == url)
+            .unwrap_or_else(|| {
+                self.notes.links.push(url.to_string());
+                self.notes.links.len() - 1
+            });
+        index + 1
+    }
+
+    fn end_line(&mut self) {
+        let text = self.line.trim_end().to_string();
+        self.notes.lines.push(Line {
+            link: first_link(&text),
+            seek: first_timestamp(&text),
+            bold: self.has_bold && !self.has_plain,
+            text,
+        });
+        self.line.clear();
+        self.has_bold = false;
+        self.has_plain = false;
+    }
+
+    /// Ends the line unless nothing is written on it yet
+    fn break_line(&mut self) {
+        if self.line.trim().is_empty() {
+            self.line.clear();
+        } else {
+            self.end_line();
+        }
+    }
+
+    /// Ends the line and leaves a blank one before whatever comes next
+    fn block(&mut self) {
+        self.break_line();
+        if self
+            .notes
+            .lines
+            .last()
+            .map_or(false, |l| !l.text.is_empty())
+        {
+            self.notes.lines.push(Line::default());
+        }
+    }
+
+    fn finish(mut self) -> Notes {
+        self.break_line();
+        // no more than one blank line in a row, and none around the text
+        let mut lines: Vec<Line> = Vec::new();
+        for line in self.notes.lines {
+            let blank = line.text.trim().is_empty();
+            if blank && lines.last().map_or(true, |l| l.text.is_empty()) {
+                continue;
+            }
+            lines.push(if blank { Line::default() } else { line });
+        }
+        if lines.last().map_or(false, |l| l.text.is_empty()) {
+            lines.pop();
+        }
+        self.notes.lines = lines;
+        self.notes
+    }
+}
+
+fn first_link(text: &str) -> Option<usize> {
+    RE_LINK_NUMBER
+        .captures(text)
+        .and_then(|c| c[1].parse().ok())
+}
+
+fn first_timestamp(text: &str) -> Option<Duration> {
+    RE_TIMESTAMP.captures_iter(text).find_map(|c| {
+        let hours: u64 = c.get(2).map_or(Some(0), |h| h.as_str().parse().ok())?;
+        let minutes: u64 = c[3].parse().ok()?;
+        let seconds: u64 = c[4].parse().ok()?;
+        if seconds >= 60 || (c.get(2).is_some() && minutes >= 60) {
+            return None;
+        }
+        Some(Duration::from_secs(hours * 3600 + minutes * 60 + seconds))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Line, Notes};
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn texts(notes: &Notes) -> Vec<&str> {
+        notes.lines.iter().map(|l| l.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_html() {
+        let notes = Notes::parse(
+            "<p>We talk about <b>Rust</b> &amp; more.</p>\n\
+             <p><strong>Topics:</strong></p>\
+             <ul><li>00:00 Intro</li><li>12:34 The <a href=\"https://www.rust-lang.org\">language</a></li>\
+             <li>1:02:03 Wrap up, see <a href='https://example.com/a?b=1&amp;c=2'>notes</a></li></ul>\
+             <ol><li>First</li><li>Second<br>line</li></ol>\
+             <p>Also https://www.rust-lang.org and https://example.org/x.</p>",
+        );
+        assert_eq!(
+            texts(&notes),
+            vec![
+                "We talk about Rust & more.",
+                "",
+                "Topics:",
+                "",
+                "• 00:00 Intro",
+                "• 12:34 The language [1]",
+                "• 1:02:03 Wrap up, see notes [2]",
+                "",
+                "1. First",
+                "2. Second",
+                "line",
+                "",
+                "Also https://www.rust-lang.org [1] and https://example.org/x [3].",
+            ]
+        );
+        assert_eq!(
+            notes.links,
+            vec![
+                "https://www.rust-lang.org".to_string(),
+                "https://example.com/a?b=1&c=2".to_string(),
+                "https://example.org/x".to_string(),
+            ]
+        );
+        assert_eq!(
+            notes.lines[2],
+            Line {
+                text: "Topics:".to_string(),
+                bold: true,
+                seek: None,
+                link: None,
+            }
+        );
+        assert!(!notes.lines[0].bold);
+        assert_eq!(notes.lines[4].seek, Some(Duration::ZERO));
+        assert_eq!(notes.lines[5].seek, Some(Duration::from_secs(754)));
+        assert_eq!(notes.lines[5].link, Some(1));
+        assert_eq!(notes.lines[6].seek, Some(Duration::from_secs(3723)));
+        assert_eq!(notes.lines[6].link, Some(2));
+        assert_eq!(notes.lines[12].link, Some(1));
+    }
+
+    #[test]
+    fn test_parse_plain() {
+        let notes =
+            Notes::parse("Intro at 0:45.\n\n\n\nLinks: https://example.com\nAt 10:30:75 or 123:45");
+        assert_eq!(
+            texts(&notes),
+            vec![
+                "Intro at 0:45.",
+                "",
+                "Links: https://example.com [1]",
+                "At 10:30:75 or 123:45"
+            ]
+        );
+        assert_eq!(notes.lines[0].seek, Some(Duration::from_secs(45)));
+        assert_eq!(notes.lines[2].link, Some(1));
+        // neither is a time in the episode
+        assert_eq!(notes.lines[3].seek, None);
+    }
+}
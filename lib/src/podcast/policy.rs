@@ -0,0 +1,316 @@
+// Per-feed rules deciding which episodes are kept on disk.
+
+use super::chapters;
+use super::db::Database;
+use super::{download, download_list, EpData, Episode, Threadpool};
+use crate::config::Settings;
+use crate::types::{Msg, PCMsg};
+use crate::utils::create_podcast_dir;
+use ahash::AHashSet;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use sanitize_filename::{sanitize_with_options, Options};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::mpsc;
+
+/// Retention policy of one feed. A rule set to 0 is off.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Policy {
+    /// Download the newest this many episodes that are not played yet
+    pub auto_download: u32,
+    /// Delete a downloaded file this many days after the episode was marked played
+    pub delete_played_after: u32,
+    /// Keep at most this many downloaded episodes, deleting the oldest
+    pub keep_downloaded: u32,
+}
+
+impl Policy {
+    pub fn is_off(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Written as the three numbers in field order, like `3 7 10`
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.auto_download, self.delete_played_after, self.keep_downloaded
+        )
+    }
+}
+
+impl FromStr for Policy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let numbers = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<u32>, _>>();
+        let Ok(&[auto_download, delete_played_after, keep_downloaded]) = numbers.as_deref() else {
+            bail!(
+                "expected three numbers: download newest, delete played after days, keep at most"
+            );
+        };
+        Ok(Self {
+            auto_download,
+            delete_played_after,
+            keep_downloaded,
+        })
+    }
+}
+
+/// What a policy wants done for one feed
+#[derive(Debug, Default)]
+struct Plan {
+    download: Vec<EpData>,
+    delete: Vec<(i64, PathBuf)>,
+}
+
+/// Episodes are expected newest first, the way `Database::get_episodes` returns them.
+/// Downloads that would be over `keep_downloaded` right away are not planned.
+fn plan(episodes: &[Episode], policy: &Policy, now: DateTime<Utc>) -> Plan {
+    let mut plan = Plan::default();
+    let delete_after = Duration::days(i64::from(policy.delete_played_after));
+    let mut kept = Vec::new();
+    let mut unplayed = 0;
+    for ep in episodes {
+        if !ep.played {
+            unplayed += 1;
+        }
+        match &ep.path {
+            Some(path) => {
+                let expired = policy.delete_played_after > 0
+                    && ep.played
+                    && ep.played_at.map_or(false, |at| now - at >= delete_after);
+                if expired {
+                    plan.delete.push((ep.id, path.clone()));
+                } else {
+                    kept.push(ep);
+                }
+            }
+            None => {
+                if !ep.played && unplayed <= policy.auto_download {
+                    kept.push(ep);
+                }
+            }
+        }
+    }
+
+    let keep = match policy.keep_downloaded {
+        0 => kept.len(),
+        n => n as usize,
+    };
+    for (index, ep) in kept.into_iter().enumerate() {
+        match &ep.path {
+            Some(path) if index >= keep => plan.delete.push((ep.id, path.clone())),
+            Some(_) => {}
+            None if index >= keep => {}
+            None => plan.download.push(EpData {
+                id: ep.id,
+                pod_id: ep.pod_id,
+                title: ep.title.clone(),
+                url: ep.url.clone(),
+                pubdate: ep.pubdate,
+                enclosure_length: ep.enclosure_length.and_then(|l| u64::try_from(l).ok()),
+                file_path: None,
+            }),
+        }
+    }
+    plan
+}
+
+/// Apply the policies of all feeds: delete the files that are due and download the
+/// missing episodes, waiting for the downloads to finish. Episodes whose download fails
+/// are added to `failed` and skipped from then on. Returns how many files changed.
+pub fn enforce(db: &Database, config: &Settings, failed: &mut AHashSet<i64>) -> Result<usize> {
+    let policies = db.get_policies()?;
+    if policies.is_empty() {
+        return Ok(0);
+    }
+
+    let now = Utc::now();
+    let mut changed = 0;
+    let threadpool = Threadpool::new(config.podcast_simultanious_download);
+    let (tx_to_main, rx_to_main) = mpsc::channel();
+    for podcast in db.get_podcasts()? {
+        let Some(policy) = policies.get(&podcast.id) else {
+            continue;
+        };
+        let plan = plan(&podcast.episodes, policy, now);
+
+        let mut deleted = Vec::new();
+        for (id, path) in plan.delete {
+            match std::fs::remove_file(&path) {
+                Ok(()) => deleted.push(id),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => deleted.push(id),
+                Err(e) => warn!("cannot delete {}: {e}", path.display()),
+            }
+        }
+        db.remove_files(&deleted)?;
+        changed += deleted.len();
+
+        let download: Vec<EpData> = plan
+            .download
+            .into_iter()
+            .filter(|ep| !failed.contains(&ep.id))
+            .collect();
+        if download.is_empty() {
+            continue;
+        }
+        let dir_name = sanitize_with_options(
+            &podcast.title,
+            Options {
+                truncate: true,
+                windows: true,
+                replacement: "",
+            },
+        );
+        let dir = create_podcast_dir(config, dir_name)?;
+        download_list(download, &dir, config, &threadpool, &tx_to_main);
+    }
+
+    // every download job holds a sender, so this ends once they are all done
+    drop(tx_to_main);
+    for message in rx_to_main {
+        if let Msg::Podcast(result) = &message {
+            if let Err(e) = download::record_result(db, result) {
+                warn!("cannot update failed downloads: {e}");
+            }
+        }
+        if let Msg::Podcast(PCMsg::DLUnchecked(ep)) = &message {
+            warn!("cannot check the download of {} is complete", ep.title);
+        }
+        match message {
+            Msg::Podcast(PCMsg::DLComplete(ep) | PCMsg::DLUnchecked(ep)) => {
+                let Some(path) = ep.file_path else {
+                    continue;
+                };
+                match db.insert_file(ep.id, &path) {
+                    Ok(()) => changed += 1,
+                    Err(e) => error!("cannot save file of {}: {e}", ep.title),
+                }
+                if let Err(e) = chapters::save_from_file(db, ep.id, &path) {
+                    warn!("cannot read chapters of {}: {e}", ep.title);
+                }
+            }
+            Msg::Podcast(
+                PCMsg::DLResponseError(ep)
+                | PCMsg::DLFileCreateError(ep)
+                | PCMsg::DLFileWriteError(ep),
+            ) => {
+                warn!("cannot download {}", ep.title);
+                failed.insert(ep.id);
+            }
+            _ => {}
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{plan, Policy};
+    use crate::podcast::Episode;
+    use chrono::{Duration, Utc};
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn episode(id: i64, downloaded: bool, played_days_ago: Option<i64>) -> Episode {
+        Episode {
+            id,
+            path: downloaded.then(|| PathBuf::from(format!("{id}.mp3"))),
+            played: played_days_ago.is_some(),
+            played_at: played_days_ago.map(|days| Utc::now() - Duration::days(days)),
+            ..Episode::default()
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let policy: Policy = "3 7 10".parse().unwrap();
+        assert_eq!(
+            policy,
+            Policy {
+                auto_download: 3,
+                delete_played_after: 7,
+                keep_downloaded: 10,
+            }
+        );
+        assert_eq!(policy.to_string(), "3 7 10");
+        assert!("0 0 0".parse::<Policy>().unwrap().is_off());
+        assert!("3 7".parse::<Policy>().is_err());
+        assert!("3 7 -1".parse::<Policy>().is_err());
+    }
+
+    #[test]
+    fn test_plan() {
+        // newest first
+        let episodes = vec![
+            episode(6, false, None),
+            episode(5, false, Some(1)),
+            episode(4, true, None),
+            episode(3, true, Some(10)),
+            episode(2, true, Some(1)),
+            episode(1, true, None),
+        ];
+        let now = Utc::now();
+
+        let result = plan(&episodes, &Policy::default(), now);
+        assert!(result.download.is_empty());
+        assert!(result.delete.is_empty());
+
+        let policy = Policy {
+            auto_download: 3,
+            delete_played_after: 7,
+            keep_downloaded: 0,
+        };
+        let result = plan(&episodes, &policy, now);
+        let download: Vec<i64> = result.download.iter().map(|ep| ep.id).collect();
+        let delete: Vec<i64> = result.delete.iter().map(|(id, _)| *id).collect();
+        assert_eq!(download, vec![6]);
+        assert_eq!(delete, vec![3]);
+
+        let policy = Policy {
+            auto_download: 3,
+            delete_played_after: 7,
+            keep_downloaded: 2,
+        };
+        let result = plan(&episodes, &policy, now);
+        let download: Vec<i64> = result.download.iter().map(|ep| ep.id).collect();
+        let delete: Vec<i64> = result.delete.iter().map(|(id, _)| *id).collect();
+        assert_eq!(download, vec![6]);
+        assert_eq!(delete, vec![3, 2, 1]);
+
+        let policy = Policy {
+            auto_download: 3,
+            delete_played_after: 0,
+            keep_downloaded: 0,
+        };
+        let result = plan(&episodes, &policy, now);
+        assert!(result.delete.is_empty());
+    }
+
+    #[test]
+    fn test_plan_skips_played() {
+        // the newest were played elsewhere without downloading them
+        let episodes = vec![
+            episode(4, false, Some(1)),
+            episode(3, false, Some(1)),
+            episode(2, false, None),
+            episode(1, false, None),
+        ];
+        let policy = Policy {
+            auto_download: 2,
+            delete_played_after: 0,
+            keep_downloaded: 0,
+        };
+        let result = plan(&episodes, &policy, Utc::now());
+        let download: Vec<i64> = result.download.iter().map(|ep| ep.id).collect();
+        assert_eq!(download, vec![2, 1]);
+    }
+}
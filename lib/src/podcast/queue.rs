@@ -0,0 +1,146 @@
+// The podcast queue ("Up Next"), a listening order across feeds kept in the database.
+
+use super::db::Database;
+use super::Episode;
+use anyhow::{bail, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Which end of the feeds the queue starts from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Order {
+    #[default]
+    Oldest,
+    Newest,
+}
+
+/// What to queue: the unplayed episodes of the selected feed, or of all feeds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Generator {
+    pub order: Order,
+    pub all_feeds: bool,
+}
+
+/// Written as the order and then `all` for every feed, like `newest all`
+impl fmt::Display for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.order {
+            Order::Oldest => write!(f, "oldest")?,
+            Order::Newest => write!(f, "newest")?,
+        }
+        if self.all_feeds {
+            write!(f, " all")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Generator {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut generator = Self::default();
+        for word in s.split_whitespace() {
+            match word.to_lowercase().as_str() {
+                "oldest" => generator.order = Order::Oldest,
+                "newest" => generator.order = Order::Newest,
+                "all" => generator.all_feeds = true,
+                _ => bail!("expected oldest or newest, and all to queue every feed"),
+            }
+        }
+        Ok(generator)
+    }
+}
+
+/// Ids of the queued episodes and the unplayed ones of `episodes`, sorted by
+/// publication date. Episodes without a date count as the oldest.
+fn merge(queued: &[Episode], episodes: &[Episode], order: Order) -> Vec<i64> {
+    let mut merged: Vec<&Episode> = queued.iter().collect();
+    for ep in episodes {
+        if !ep.played && !merged.iter().any(|queued| queued.id == ep.id) {
+            merged.push(ep);
+        }
+    }
+    match order {
+        Order::Oldest => merged.sort_by(|a, b| a.pubdate.cmp(&b.pubdate)),
+        Order::Newest => merged.sort_by(|a, b| b.pubdate.cmp(&a.pubdate)),
+    }
+    merged.iter().map(|ep| ep.id).collect()
+}
+
+/// Adds the unplayed ones of `episodes` to the queue, which is sorted again in the
+/// given order. Returns how many were added.
+pub fn add_unplayed(db: &Database, episodes: &[Episode], order: Order) -> Result<usize> {
+    let queued = db.get_queue()?;
+    let ids = merge(&queued, episodes, order);
+    db.set_queue(&ids)?;
+    Ok(ids.len() - queued.len())
+}
+
+/// The episode queued after the one with `url`, if that one is queued
+pub fn next_after(db: &Database, url: &str) -> Result<Option<Episode>> {
+    let queue = db.get_queue()?;
+    Ok(queue
+        .iter()
+        .position(|ep| ep.url == url)
+        .and_then(|index| queue.get(index + 1))
+        .cloned())
+}
+
+/// The episode with `url` played to its end, so it is marked played and taken off
+/// the queue. Returns whether it is a known episode.
+pub fn finish(db: &Database, url: &str) -> Result<bool> {
+    let Some(ep) = db.get_episode_by_url(url)? else {
+        return Ok(false);
+    };
+    db.set_played_status(ep.id, true)?;
+    db.remove_from_queue(ep.id)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge, Generator, Order};
+    use crate::podcast::Episode;
+    use chrono::{TimeZone, Utc};
+    use pretty_assertions::assert_eq;
+
+    fn episode(id: i64, day: Option<u32>, played: bool) -> Episode {
+        Episode {
+            id,
+            pubdate: day.and_then(|d| Utc.with_ymd_and_hms(2023, 1, d, 0, 0, 0).single()),
+            played,
+            ..Episode::default()
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("".parse::<Generator>().unwrap(), Generator::default());
+        let generator: Generator = "Newest all".parse().unwrap();
+        assert_eq!(
+            generator,
+            Generator {
+                order: Order::Newest,
+                all_feeds: true,
+            }
+        );
+        assert_eq!(generator.to_string(), "newest all");
+        assert_eq!(Generator::default().to_string(), "oldest");
+        assert!("latest".parse::<Generator>().is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let queued = vec![episode(1, Some(5), false), episode(2, Some(2), true)];
+        let episodes = vec![
+            episode(3, Some(9), false),
+            episode(4, Some(1), true),
+            episode(1, Some(5), false),
+            episode(5, None, false),
+        ];
+        assert_eq!(merge(&queued, &episodes, Order::Oldest), vec![5, 2, 1, 3]);
+        assert_eq!(merge(&queued, &episodes, Order::Newest), vec![3, 1, 2, 5]);
+        assert_eq!(merge(&[], &[], Order::Oldest), Vec::<i64>::new());
+    }
+}
@@ -0,0 +1,199 @@
+// Refreshing feeds on a timer, so they stay current while no client is open.
+
+use super::db::Database;
+use super::{check_feed, Podcast, PodcastFeed, Threadpool};
+use super::{gpodder, policy};
+use crate::config::Settings;
+use crate::types::{Msg, PCMsg};
+use ahash::{AHashMap, AHashSet};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+/// How often the server looks for feeds that are due
+pub const CHECK_EVERY: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Feeds whose refresh interval has passed since they were last checked. A failed attempt
+/// counts as a check, so a broken feed is not retried every minute.
+fn due_feeds(
+    podcasts: &[Podcast],
+    failed: &AHashMap<String, DateTime<Utc>>,
+    config: &Settings,
+    now: DateTime<Utc>,
+) -> Vec<PodcastFeed> {
+    podcasts
+        .iter()
+        .filter(|pod| {
+            let mins = config
+                .podcast_refresh_feeds
+                .iter()
+                .find(|f| f.url == pod.url)
+                .map_or(config.podcast_refresh_mins, |f| f.mins);
+            let last = failed
+                .get(&pod.url)
+                .map_or(pod.last_checked, |t| (*t).max(pod.last_checked));
+            mins > 0 && now - last >= Duration::minutes(i64::try_from(mins).unwrap_or(i64::MAX))
+        })
+        .map(|pod| PodcastFeed::new(Some(pod.id), &pod.url, Some(pod.title.clone())))
+        .collect()
+}
+
+pub struct Scheduler {
+    db_path: PathBuf,
+    failed: AHashMap<String, DateTime<Utc>>,
+    failed_downloads: AHashSet<i64>,
+    last_sync: Option<std::time::Instant>,
+}
+
+impl Scheduler {
+    pub fn new(db_path: &Path) -> Self {
+        Self {
+            db_path: db_path.to_path_buf(),
+            failed: AHashMap::new(),
+            failed_downloads: AHashSet::new(),
+            last_sync: None,
+        }
+    }
+
+    /// Refresh the feeds that are due and return the number of new episodes
+    pub fn run_due(&mut self, config: &Settings) -> Result<usize> {
+        let db = Database::connect(&self.db_path)?;
+        let now = Utc::now();
+        let feeds = due_feeds(&db.get_podcasts()?, &self.failed, config, now);
+        if feeds.is_empty() {
+            return Ok(0);
+        }
+
+        let threadpool = Threadpool::new(config.podcast_simultanious_download);
+        let (tx_to_main, rx_to_main) = mpsc::channel();
+        for feed in &feeds {
+            check_feed(
+                feed.clone(),
+                config.podcast_max_retries,
+                &threadpool,
+                tx_to_main.clone(),
+            );
+        }
+        // every fetch job holds a sender, so the loop ends even if one dies without answering
+        drop(tx_to_main);
+
+        let mut added = 0;
+        let mut remaining = feeds.len();
+        for message in rx_to_main.iter() {
+            match message {
+                Msg::Podcast(PCMsg::SyncData((id, pod))) => {
+                    remaining -= 1;
+                    self.failed.remove(&pod.url);
+                    match db.update_podcast(id, &pod) {
+                        Ok(result) => added += result.added.len(),
+                        Err(e) => error!("error saving feed {}: {e}", pod.url),
+                    }
+                }
+                Msg::Podcast(PCMsg::Error(url, _)) => {
+                    remaining -= 1;
+                    warn!("cannot refresh feed {url}");
+                    self.failed.insert(url, now);
+                }
+                _ => {}
+            }
+            if remaining == 0 {
+                break;
+            }
+        }
+        info!("refreshed {} feeds, {added} new episodes", feeds.len());
+        Ok(added)
+    }
+
+    /// Download and delete episode files as the feed policies ask, and return how many
+    /// files changed. Downloads that failed once are not tried again by this scheduler.
+    pub fn enforce_policies(&mut self, config: &Settings) -> Result<usize> {
+        let db = Database::connect(&self.db_path)?;
+        policy::enforce(&db, config, &mut self.failed_downloads)
+    }
+
+    /// Sync with the gpodder server when it is configured and `podcast_sync.mins` have
+    /// passed, and return how many feeds and episodes changed. A failed sync waits for
+    /// the next interval too.
+    pub fn sync_due(&mut self, config: &Settings) -> Result<usize> {
+        let settings = &config.podcast_sync;
+        if settings.url.is_empty() || settings.mins == 0 {
+            return Ok(0);
+        }
+        let interval = std::time::Duration::from_secs(settings.mins * 60);
+        if self
+            .last_sync
+            .map_or(false, |last| last.elapsed() < interval)
+        {
+            return Ok(0);
+        }
+        self.last_sync = Some(std::time::Instant::now());
+        let mut db = Database::connect(&self.db_path)?;
+        gpodder::sync(&mut db, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::due_feeds;
+    use crate::config::{PodcastRefreshFeed, Settings};
+    use crate::podcast::Podcast;
+    use ahash::AHashMap;
+    use chrono::{Duration, Utc};
+    use pretty_assertions::assert_eq;
+
+    fn podcast(id: i64, url: &str, mins_ago: i64) -> Podcast {
+        Podcast {
+            id,
+            title: url.to_string(),
+            sort_title: url.to_string(),
+            url: url.to_string(),
+            description: None,
+            author: None,
+            explicit: None,
+            last_checked: Utc::now() - Duration::minutes(mins_ago),
+            episodes: vec![],
+            image_url: None,
+        }
+    }
+
+    #[test]
+    fn test_due_feeds() {
+        let mut config = Settings {
+            podcast_refresh_mins: 60,
+            podcast_refresh_feeds: vec![
+                PodcastRefreshFeed {
+                    url: "daily".to_string(),
+                    mins: 10,
+                },
+                PodcastRefreshFeed {
+                    url: "never".to_string(),
+                    mins: 0,
+                },
+            ],
+            ..Settings::default()
+        };
+        let podcasts = vec![
+            podcast(1, "fresh", 30),
+            podcast(2, "stale", 90),
+            podcast(3, "daily", 30),
+            podcast(4, "never", 9000),
+            podcast(5, "broken", 90),
+        ];
+        let mut failed = AHashMap::new();
+        failed.insert("broken".to_string(), Utc::now() - Duration::minutes(5));
+
+        let due: Vec<i64> = due_feeds(&podcasts, &failed, &config, Utc::now())
+            .iter()
+            .filter_map(|f| f.id)
+            .collect();
+        assert_eq!(due, vec![2, 3]);
+
+        config.podcast_refresh_mins = 0;
+        let due: Vec<i64> = due_feeds(&podcasts, &failed, &config, Utc::now())
+            .iter()
+            .filter_map(|f| f.id)
+            .collect();
+        assert_eq!(due, vec![3]);
+    }
+}
@@ -0,0 +1,292 @@
+// Podcasting 2.0 transcripts, shown in sync like lyrics.
+
+use super::db::Database;
+use crate::songtag::lrc::Lyric;
+use anyhow::{bail, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::blocking::ClientBuilder;
+use rss::Item;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Types of transcripts with timing, most preferred first
+const TYPES: [&str; 5] = [
+    "text/vtt",
+    "application/x-subrip",
+    "application/srt",
+    "text/srt",
+    "application/json",
+];
+
+/// A line of json transcripts is closed when it gets this long, in milliseconds
+const JSON_LINE_LENGTH: i64 = 5000;
+
+lazy_static! {
+    /// VTT voice tag, like `<v Jane>` or `<v.loud Jane>`
+    static ref RE_VOICE: Regex = Regex::new(r"<v(?:\.[^ >]*)? ([^>]*)>").expect("Regex error");
+    static ref RE_TAGS: Regex = Regex::new(r"<[^<>]*>").expect("Regex error");
+}
+
+/// A line of a stored transcript containing the searched text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub episode_id: i64,
+    /// Milliseconds into the episode
+    pub time_stamp: i64,
+    pub text: String,
+}
+
+/// Address of the transcript linked from a feed item, if it has one with timing
+pub fn url(item: &Item) -> Option<String> {
+    let transcripts = item.extensions().get("podcast")?.get("transcript")?;
+    TYPES.iter().find_map(|kind| {
+        transcripts
+            .iter()
+            .find(|t| {
+                t.attrs()
+                    .get("type")
+                    .map_or(false, |v| v.eq_ignore_ascii_case(kind))
+            })
+            .and_then(|t| t.attrs().get("url").cloned())
+    })
+}
+
+/// Parse a transcript in SRT, VTT or json format
+pub fn parse(text: &str) -> Result<Lyric> {
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    let lines = if text.starts_with('{') {
+        from_json(text)?
+    } else {
+        from_cues(text)
+    };
+    if lines.is_empty() {
+        bail!("no timed lines in transcript");
+    }
+    Ok(Lyric::from_lines(lines))
+}
+
+/// SRT and VTT cues: a timing line like `00:01:02,500 --> 00:01:05,000` and the text
+/// below it up to a blank line. Cue numbers, notes and styles are skipped.
+fn from_cues(text: &str) -> Vec<(i64, String)> {
+    let mut lines = Vec::new();
+    let mut cue: Option<(i64, Vec<&str>)> = None;
+    for line in text.lines().map(str::trim) {
+        if let Some((start, _)) = line.split_once("-->") {
+            lines.extend(cue.take().and_then(cue_line));
+            cue = parse_time(start.trim()).map(|start| (start, Vec::new()));
+        } else if line.is_empty() {
+            lines.extend(cue.take().and_then(cue_line));
+        } else if let Some((_, text)) = &mut cue {
+            text.push(line);
+        }
+    }
+    lines.extend(cue.and_then(cue_line));
+    lines
+}
+
+fn cue_line((start, text): (i64, Vec<&str>)) -> Option<(i64, String)> {
+    let text = text.join(" ");
+    let text = RE_VOICE.replace_all(&text, "$1: ");
+    let text = RE_TAGS.replace_all(&text, "");
+    let text = text.trim();
+    (!text.is_empty()).then(|| (start, text.to_string()))
+}
+
+/// Time of a cue in milliseconds, written `HH:MM:SS,mmm` in SRT and `HH:MM:SS.mmm` or
+/// `MM:SS.mmm` in VTT
+fn parse_time(text: &str) -> Option<i64> {
+    let text = text.replace(',', ".");
+    let (clock, fraction) = text.split_once('.').unwrap_or((&text, "0"));
+    let mut seconds = 0;
+    for part in clock.split(':') {
+        seconds = seconds * 60 + part.parse::<i64>().ok()?;
+    }
+    let millis: i64 = format!("{fraction:0<3}").get(..3)?.parse().ok()?;
+    Some(seconds * 1000 + millis)
+}
+
+/// Json transcripts often have a segment per word, so segments are joined into lines
+/// that end with a sentence, a new speaker or after a few seconds.
+#[allow(clippy::cast_possible_truncation)]
+fn from_json(text: &str) -> Result<Vec<(i64, String)>> {
+    let json: Value = serde_json::from_str(text)?;
+    let Some(segments) = json.get("segments").and_then(Value::as_array) else {
+        bail!("no segments in json transcript");
+    };
+    let mut lines: Vec<(i64, String)> = Vec::new();
+    let mut speaker = None;
+    let mut line_open = false;
+    for segment in segments {
+        let (Some(start), Some(body)) = (
+            segment.get("startTime").and_then(Value::as_f64),
+            segment.get("body").and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+        let body = body.trim();
+        if body.is_empty() {
+            continue;
+        }
+        let start = (start * 1000.0).round() as i64;
+        let segment_speaker = segment.get("speaker").and_then(Value::as_str);
+        let new_speaker = segment_speaker.is_some() && segment_speaker != speaker;
+        speaker = segment_speaker;
+        match lines.last_mut() {
+            Some((line_start, line))
+                if line_open && !new_speaker && start - *line_start < JSON_LINE_LENGTH =>
+            {
+                line.push(' ');
+                line.push_str(body);
+            }
+            _ => match segment_speaker {
+                Some(name) if new_speaker => lines.push((start, format!("{name}: {body}"))),
+                _ => lines.push((start, body.to_string())),
+            },
+        }
+        line_open = !body.ends_with(['.', '?', '!']);
+    }
+    Ok(lines)
+}
+
+/// Download a transcript, checking that it can be shown
+pub fn fetch(url: &str) -> Result<String> {
+    let client = ClientBuilder::new()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+    let text = client.get(url).send()?.error_for_status()?.text()?;
+    parse(&text)?;
+    Ok(text)
+}
+
+/// Lines of the stored transcripts containing `query`, ignoring case
+pub fn search(db: &Database, query: &str) -> Result<Vec<Match>> {
+    let mut matches = Vec::new();
+    for (episode_id, text) in db.get_transcripts_containing(query)? {
+        let Ok(lyric) = parse(&text) else {
+            continue;
+        };
+        matches.extend(
+            find(&lyric, query)
+                .into_iter()
+                .map(|(time_stamp, text)| Match {
+                    episode_id,
+                    time_stamp,
+                    text,
+                }),
+        );
+    }
+    Ok(matches)
+}
+
+fn find(lyric: &Lyric, query: &str) -> Vec<(i64, String)> {
+    let query = query.to_lowercase();
+    lyric
+        .unsynced_captions
+        .iter()
+        .filter(|c| c.text().to_lowercase().contains(&query))
+        .map(|c| (c.time_stamp(), c.text().to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, parse, parse_time};
+    use pretty_assertions::assert_eq;
+
+    fn lines(text: &str) -> Vec<(i64, String)> {
+        parse(text)
+            .unwrap()
+            .unsynced_captions
+            .iter()
+            .map(|c| (c.time_stamp(), c.text().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time("00:01:02,500"), Some(62_500));
+        assert_eq!(parse_time("01:00:00.000"), Some(3_600_000));
+        assert_eq!(parse_time("01:02.5"), Some(62_500));
+        assert_eq!(parse_time("1:02"), Some(62_000));
+        assert_eq!(parse_time("a:02"), None);
+    }
+
+    #[test]
+    fn test_parse_srt() {
+        let srt = "1
+00:00:00,500 --> 00:00:02,000
+Welcome to the show.
+
+2
+00:00:02,500 --> 00:00:05,000
+Today we talk
+about <i>chapters</i>.
+";
+        assert_eq!(
+            lines(srt),
+            vec![
+                (500, "Welcome to the show.".to_string()),
+                (2500, "Today we talk about chapters.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_vtt() {
+        let vtt = "WEBVTT
+
+NOTE made by hand
+
+00:05.000 --> 00:07.000 align:start
+<v Jane>Hello there.
+
+intro
+00:01.000 --> 00:03.000
+<v.loud Bob>First!
+";
+        assert_eq!(
+            lines(vtt),
+            vec![
+                (1000, "Bob: First!".to_string()),
+                (5000, "Jane: Hello there.".to_string()),
+            ]
+        );
+        assert!(parse("WEBVTT\n\nNOTE nothing timed\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let json = r#"{"version": "1.0.0", "segments": [
+            {"speaker": "Jane", "startTime": 0.5, "endTime": 0.9, "body": "Hello"},
+            {"speaker": "Jane", "startTime": 0.9, "endTime": 1.2, "body": "there."},
+            {"speaker": "Jane", "startTime": 1.5, "endTime": 2.0, "body": "Welcome"},
+            {"speaker": "Bob", "startTime": 2.1, "endTime": 2.5, "body": "Thanks"},
+            {"speaker": "Bob", "startTime": 8.0, "endTime": 8.5, "body": "anyway"}
+        ]}"#;
+        assert_eq!(
+            lines(json),
+            vec![
+                (500, "Jane: Hello there.".to_string()),
+                (1500, "Welcome".to_string()),
+                (2100, "Bob: Thanks".to_string()),
+                (8000, "anyway".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        let lyric =
+            parse("00:01.000 --> 00:02.000\nHello World\n\n00:03.000 --> 00:04.000\nbye world\n")
+                .unwrap();
+        assert_eq!(
+            find(&lyric, "WORLD"),
+            vec![
+                (1000, "Hello World".to_string()),
+                (3000, "bye world".to_string())
+            ]
+        );
+        assert!(find(&lyric, "moon").is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+/**
+ * MIT License
+ *
+ * termusic - Copyright (c) 2021 Larry Hao
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::SongTag;
+use crate::config::FingerprintSettings;
+use crate::fingerprint::{Fingerprint, Resolver};
+use anyhow::{bail, Result};
+use reqwest::blocking::{Client, ClientBuilder};
+use serde_json::{from_str, Value};
+use std::time::Duration;
+
+pub const NAME: &str = "acoustid";
+
+/// Looks up fingerprints on an `AcoustID` server and names the matching `MusicBrainz`
+/// recordings.
+pub struct AcoustId {
+    client: Client,
+    url: String,
+    api_key: String,
+}
+
+impl AcoustId {
+    pub fn new(settings: &FingerprintSettings) -> Self {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(settings.timeout_secs))
+            .build()
+            .expect("failed to build reqwest client.");
+
+        Self {
+            client,
+            url: settings.url.clone(),
+            api_key: settings.api_key.clone(),
+        }
+    }
+}
+
+impl Resolver for AcoustId {
+    fn lookup(&self, fingerprint: &Fingerprint) -> Result<Vec<SongTag>> {
+        let duration = fingerprint.duration.to_string();
+        let encoded = fingerprint.encode();
+        let form = vec![
+            ("format", "json"),
+            ("client", self.api_key.as_str()),
+            ("meta", "recordings releasegroups compress"),
+            ("duration", &duration),
+            ("fingerprint", &encoded),
+        ];
+        let result = self.client.post(&self.url).form(&form).send()?.text()?;
+        to_song_tags(&result)
+    }
+}
+
+/// Every recording once, best match first. Recordings without a title are left out.
+fn to_song_tags(json: &str) -> Result<Vec<SongTag>> {
+    let value = from_str::<Value>(json)?;
+    if value["status"] != "ok" {
+        let message = value["error"]["message"]
+            .as_str()
+            .unwrap_or("unknown error");
+        bail!("acoustid lookup failed: {message}");
+    }
+    let mut song_tags: Vec<SongTag> = vec![];
+    let recordings = value["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|result| result["recordings"].as_array())
+        .flatten();
+    for recording in recordings {
+        let Some(title) = recording["title"].as_str() else {
+            continue;
+        };
+        let song_id = recording["id"].as_str().map(ToString::to_string);
+        if song_tags.iter().any(|s| s.song_id == song_id) {
+            continue;
+        }
+        let artist: String = recording["artists"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|artist| {
+                let name = artist["name"].as_str()?;
+                Some(format!(
+                    "{name}{}",
+                    artist["joinphrase"].as_str().unwrap_or("")
+                ))
+            })
+            .collect();
+        let release_group = &recording["releasegroups"][0];
+        song_tags.push(SongTag {
+            artist: Some(artist).filter(|a| !a.is_empty()),
+            title: Some(title.to_string()),
+            album: release_group["title"].as_str().map(ToString::to_string),
+            service_provider: Some(NAME.to_string()),
+            song_id,
+            album_id: release_group["id"].as_str().map(ToString::to_string),
+            ..SongTag::default()
+        });
+    }
+    Ok(song_tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_song_tags;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_to_song_tags() {
+        let json = r#"{"status": "ok", "results": [
+            {"id": "a", "score": 0.97, "recordings": [
+                {"id": "r1", "title": "Under Pressure",
+                 "artists": [{"name": "Queen", "joinphrase": " & "}, {"name": "David Bowie"}],
+                 "releasegroups": [{"id": "g1", "title": "Hot Space"}]},
+                {"id": "r2"}
+            ]},
+            {"id": "b", "score": 0.5, "recordings": [
+                {"id": "r1", "title": "Under Pressure"},
+                {"id": "r3", "title": "Under Pressure (live)"}
+            ]}
+        ]}"#;
+        let song_tags = to_song_tags(json).unwrap();
+        let found: Vec<(Option<&str>, Option<&str>, Option<&str>)> = song_tags
+            .iter()
+            .map(|s| (s.artist(), s.title(), s.album()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    Some("Queen & David Bowie"),
+                    Some("Under Pressure"),
+                    Some("Hot Space")
+                ),
+                (None, Some("Under Pressure (live)"), None),
+            ]
+        );
+        assert_eq!(song_tags[0].service_provider(), Some("acoustid"));
+
+        let error = r#"{"status": "error", "error": {"code": 4, "message": "invalid API key"}}"#;
+        let Err(e) = to_song_tags(error) else {
+            panic!("error status not reported");
+        };
+        assert_eq!(e.to_string(), "acoustid lookup failed: invalid API key");
+    }
+}
@@ -24,6 +24,7 @@
 mod model;
 
 use super::encrypt::Crypto;
+use super::{LyricProvider, SongTag};
 use anyhow::{anyhow, bail, Result};
 use bytes::Buf;
 use lofty::Picture;
@@ -36,14 +37,16 @@ static URL_LYRIC_SEARCH_KUGOU: &str = "http://krcs.kugou.com/search";
 static URL_LYRIC_DOWNLOAD_KUGOU: &str = "http://lyrics.kugou.com/download";
 static URL_SONG_DOWNLOAD_KUGOU: &str = "http://www.kugou.com/yy/index.php?r=play/getdata";
 
+pub const NAME: &str = "kugou";
+
 pub struct Api {
     client: Client,
 }
 
 impl Api {
-    pub fn new() -> Self {
+    pub fn new(timeout: Duration) -> Self {
         let client = ClientBuilder::new()
-            .timeout(Duration::from_secs(10))
+            .timeout(timeout)
             .build()
             .expect("failed to build reqwest client.");
 
@@ -172,3 +175,105 @@ impl Api {
         Ok(picture)
     }
 }
+
+pub struct Provider {
+    timeout: Duration,
+}
+
+impl Provider {
+    pub const fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl LyricProvider for Provider {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn search(&self, keywords: &str, limit: u16) -> Result<Vec<SongTag>> {
+        let results = Api::new(self.timeout).search(keywords, 1, 0, limit)?;
+        Ok(serde_json::from_str(&results)?)
+    }
+
+    fn fetch_lyric(&self, song_tag: &SongTag) -> Result<String> {
+        let lyric_id = song_tag
+            .lyric_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("lyric_id is missing for kugou"))?;
+        Api::new(self.timeout).song_lyric(lyric_id)
+    }
+
+    fn fetch_cover(&self, song_tag: &SongTag) -> Result<Picture> {
+        let pic_id = song_tag
+            .pic_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("pic_id is missing for kugou"))?;
+        let album_id = song_tag
+            .album_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("album_id is missing for kugou"))?;
+        Api::new(self.timeout).pic(pic_id, album_id)
+    }
+
+    fn download_url(&self, song_tag: &SongTag) -> Result<String> {
+        let song_id = song_tag
+            .song_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("song_id is missing for kugou"))?;
+        let album_id = song_tag.album_id.as_deref().unwrap_or("N/A");
+        Api::new(self.timeout).song_url(song_id, album_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::model::{to_lyric, to_lyric_id_accesskey, to_song_info};
+    use super::{LyricProvider, Provider, SongTag};
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_search() {
+        let tags = to_song_info(include_str!("../test-data/kugou_search.json")).unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].title.as_deref(), Some("Yesterday"));
+        assert_eq!(tags[0].artist.as_deref(), Some("The Beatles"));
+        assert_eq!(tags[0].album.as_deref(), Some("Help!"));
+        assert_eq!(
+            tags[0].lyric_id.as_deref(),
+            Some("7D4DB4EE3A2E7A1F6A8E3C0E5B5E8D0B")
+        );
+        assert_eq!(tags[0].album_id.as_deref(), Some("969476"));
+        assert_eq!(tags[0].url().as_deref(), Some("Downloadable"));
+        assert_eq!(tags[0].service_provider(), Some("kugou"));
+        assert_eq!(tags[1].url().as_deref(), Some("Copyright Protected"));
+    }
+
+    #[test]
+    fn test_parse_lyric() {
+        assert_eq!(
+            to_lyric_id_accesskey(include_str!("../test-data/kugou_lyric_search.json")),
+            Some((
+                "C3D2A6A0EFF4A6F4F9D2E7C1D6A81B0C".to_string(),
+                "22422076".to_string()
+            ))
+        );
+        assert_eq!(
+            to_lyric(include_str!("../test-data/kugou_lyric.json")).as_deref(),
+            Some("[00:00.00]Yesterday\r\n[00:02.50]All my troubles seemed so far away\r\n")
+        );
+        // no candidates when kugou has no lyric for the song
+        assert_eq!(
+            to_lyric_id_accesskey(r#"{"status":200,"errcode":200,"candidates":[]}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fetch_lyric_needs_id() {
+        let provider = Provider::new(Duration::from_secs(1));
+        let err = provider.fetch_lyric(&SongTag::default()).unwrap_err();
+        assert_eq!(err.to_string(), "lyric_id is missing for kugou");
+    }
+}
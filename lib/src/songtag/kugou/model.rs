@@ -21,7 +21,8 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use super::super::{ServiceProvider, SongTag};
+use super::super::SongTag;
+use super::NAME;
 use base64::{engine::general_purpose, Engine as _};
 use serde_json::{from_str, json, Value};
 
@@ -137,7 +138,7 @@ fn parse_song_info(v: &Value) -> Option<SongTag> {
         ),
         pic_id: Some(v.get("hash")?.as_str()?.to_owned()),
         lang_ext: Some("kugou".to_string()),
-        service_provider: Some(ServiceProvider::Kugou),
+        service_provider: Some(NAME.to_string()),
         lyric_id: Some(v.get("hash")?.as_str()?.to_owned()),
         url: Some(url),
         album_id: Some(v.get("album_id")?.as_str()?.to_owned()),
@@ -36,7 +36,9 @@
 // [al:Hits Of The 60's - Vol. 2 – Oldies]
 // [00:12.00]Lyrics beginning ...
 // [00:15.30]Some more lyrics ...
-use anyhow::Result;
+// Enhanced LRC (A2) also times every word:
+// [00:12.00]<00:12.00>Lyrics <00:12.60>beginning <00:13.40>...<00:14.10>
+use anyhow::{bail, Result};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::cmp::Ordering;
@@ -46,6 +48,8 @@ use std::time::Duration;
 lazy_static! {
     static ref LYRICS_RE: Regex = Regex::new("^[^\x00-\x08\x0A-\x1F\x7F]*$").unwrap();
     static ref TAG_RE: Regex = Regex::new(r"\[.*:.*\]").unwrap();
+    static ref ID_TAG_RE: Regex = Regex::new(r"^\[([A-Za-z#]+):(.*)\]$").unwrap();
+    static ref WORD_TIME_RE: Regex = Regex::new(r"<(\d+:\d+\.\d+)>").unwrap();
     static ref LINE_STARTS_WITH_RE: Regex =
         Regex::new("^\\[([^\x00-\x08\x0A-\x1F\x7F\\[\\]:]*):([^\x00-\x08\x0A-\x1F\x7F\\[\\]]*)\\]")
             .unwrap();
@@ -55,6 +59,8 @@ lazy_static! {
 pub struct Lyric {
     pub offset: i64, // positive means delay lyric
     pub lang_extension: Option<String>,
+    /// ID tags like `ar`, `ti`, `al` and `length`, in file order. `offset` is kept above.
+    pub tags: Vec<(String, String)>,
     pub unsynced_captions: Vec<UnsyncedCaption>, // USLT captions
 }
 
@@ -62,11 +68,49 @@ pub struct Lyric {
 pub struct UnsyncedCaption {
     time_stamp: i64,
     text: String,
+    /// Per word timing of enhanced lrc, empty for a plain line. The texts add up to `text`.
+    words: Vec<Word>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Word {
+    pub time_stamp: i64,
+    pub text: String,
+}
+
+/// Lyrics being timed line by line in the tag editor. Every line needs a time stamp before
+/// it can be turned into lrc.
+#[derive(Clone, Debug, Default)]
+pub struct LyricSync {
+    /// ID tags written back in front of the lines
+    pub tags: Vec<(String, String)>,
+    /// Playback position in milliseconds each line starts at
+    lines: Vec<(Option<i64>, String)>,
 }
 
 const EOL: &str = "\n";
 
 impl Lyric {
+    /// Lyric of lines timed elsewhere, like the cues of a transcript. Time stamps are in
+    /// milliseconds, lines are not merged as they are for lrc.
+    pub fn from_lines(lines: impl IntoIterator<Item = (i64, String)>) -> Self {
+        let mut unsynced_captions: Vec<UnsyncedCaption> = lines
+            .into_iter()
+            .map(|(time_stamp, text)| UnsyncedCaption {
+                time_stamp: time_stamp.max(0),
+                text,
+                words: vec![],
+            })
+            .collect();
+        unsynced_captions.sort_by_key(|c| c.time_stamp);
+        Self {
+            offset: 0,
+            lang_extension: None,
+            tags: vec![],
+            unsynced_captions,
+        }
+    }
+
     // GetText will fetch lyric by time in seconds
     pub fn get_text(&self, mut time: i64) -> Option<String> {
         if self.unsynced_captions.is_empty() {
@@ -118,6 +162,37 @@ impl Lyric {
         Some(index)
     }
 
+    /// Value of an ID tag, e.g. `tag("ar")` for the artist
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Line being sung at `time` in milliseconds, without the lead of `get_index`. Before
+    /// the first line this is the first line.
+    pub fn line_at(&self, time: i64) -> Option<usize> {
+        let time = time + self.offset;
+        match self
+            .unsynced_captions
+            .iter()
+            .rposition(|c| c.time_stamp <= time)
+        {
+            Some(index) => Some(index),
+            None if self.unsynced_captions.is_empty() => None,
+            None => Some(0),
+        }
+    }
+
+    /// Line being sung at `time` in milliseconds, with the number of chars of it already sung
+    /// and the length of the word being sung. A line without word timing is one long word.
+    pub fn karaoke(&self, time: i64) -> Option<(usize, usize, usize)> {
+        let index = self.line_at(time)?;
+        let (sung, word) = self.unsynced_captions[index].progress(time + self.offset);
+        Some((index, sung, word))
+    }
+
     pub fn adjust_offset(&mut self, time: i64, offset: i64) {
         if let Some(index) = self.get_index(time) {
             // when time stamp is less than 10 seconds or index is before the first line, we adjust
@@ -132,6 +207,9 @@ impl Lyric {
                     Ordering::Greater | Ordering::Equal => adjusted_time_stamp,
                     Ordering::Less => 0,
                 };
+                for w in &mut v.words {
+                    w.time_stamp = (w.time_stamp + offset).max(0);
+                }
             }
         };
         // we sort the captions by time_stamp. This is to fix some lyrics downloaded are not sorted
@@ -141,6 +219,9 @@ impl Lyric {
 
     pub fn as_lrc_text(&self) -> String {
         let mut result: String = String::new();
+        for (key, value) in &self.tags {
+            result += format!("[{key}:{value}]\n").as_str();
+        }
         if self.offset != 0 {
             let string_offset = format!("[offset:{}]\n", self.offset);
             result += string_offset.as_ref();
@@ -161,8 +242,7 @@ impl Lyric {
             }
             if let Some(item) = unsynced_captions.get(i - offset) {
                 if v.time_stamp - item.time_stamp < 2000 {
-                    unsynced_captions[i - offset].text += "  ";
-                    unsynced_captions[i - offset].text += v.text.as_ref();
+                    unsynced_captions[i - offset].append(v);
                     unsynced_captions.remove(i - offset + 1);
                     offset += 1;
                 }
@@ -174,6 +254,60 @@ impl Lyric {
 }
 
 impl UnsyncedCaption {
+    pub fn time_stamp(&self) -> i64 {
+        self.time_stamp
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    /// Chars sung before the current word and the length of it at `time` in milliseconds
+    fn progress(&self, time: i64) -> (usize, usize) {
+        if self.words.is_empty() {
+            return (0, self.text.chars().count());
+        }
+        match self.words.iter().rposition(|w| w.time_stamp <= time) {
+            Some(current) => (
+                self.words[..current]
+                    .iter()
+                    .map(|w| w.text.chars().count())
+                    .sum(),
+                self.words[current].text.chars().count(),
+            ),
+            None => (0, 0),
+        }
+    }
+
+    /// Join the next line onto this one, as `merge_adjacent` does
+    fn append(&mut self, next: &Self) {
+        if !self.words.is_empty() || !next.words.is_empty() {
+            let mut words = self.words_or_whole();
+            if let Some(last) = words.last_mut() {
+                last.text += "  ";
+            }
+            words.append(&mut next.words_or_whole());
+            self.words = words;
+        }
+        self.text += "  ";
+        self.text += next.text.as_ref();
+    }
+
+    fn words_or_whole(&self) -> Vec<Word> {
+        if self.words.is_empty() {
+            vec![Word {
+                time_stamp: self.time_stamp,
+                text: self.text.clone(),
+            }]
+        } else {
+            self.words.clone()
+        }
+    }
+
     fn parse_line(line: &mut String) -> Result<Self, ()> {
         //[00:12.00]Line 1 lyrics
         // !line.starts_with('[') | !line.contains(']')
@@ -185,12 +319,49 @@ impl UnsyncedCaption {
         let text = line
             .drain(line.find(']').ok_or(())? + 1..)
             .collect::<String>();
+        let time_stamp = time_stamp.try_into().unwrap_or(0);
+        let words = Self::parse_words(time_stamp, &text);
         Ok(Self {
-            time_stamp: time_stamp.try_into().unwrap_or(0),
-            text,
+            time_stamp,
+            text: if words.is_empty() {
+                text
+            } else {
+                words.iter().map(|w| w.text.as_str()).collect()
+            },
+            words,
         })
     }
 
+    // <00:12.00>Line <00:12.50>1 <00:13.00>lyrics<00:14.00>
+    // text in front of the first word tag starts with the line
+    fn parse_words(line_time_stamp: i64, text: &str) -> Vec<Word> {
+        let mut words = vec![];
+        let mut time_stamp = line_time_stamp;
+        let mut start = 0;
+        for cap in WORD_TIME_RE.captures_iter(text) {
+            let (Some(tag), Ok(next_time_stamp)) = (cap.get(0), Self::parse_time(&cap[1])) else {
+                continue;
+            };
+            let word = &text[start..tag.start()];
+            if !word.is_empty() {
+                words.push(Word {
+                    time_stamp,
+                    text: word.to_string(),
+                });
+            }
+            time_stamp = next_time_stamp.try_into().unwrap_or(0);
+            start = tag.end();
+        }
+        if start > 0 {
+            // kept even when empty, it marks the end of the last word
+            words.push(Word {
+                time_stamp,
+                text: text[start..].to_string(),
+            });
+        }
+        words
+    }
+
     #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn parse_time(string: &str) -> Result<u64, ()> {
         //mm:ss.xx or mm:ss.xxx
@@ -216,23 +387,120 @@ impl UnsyncedCaption {
     }
 
     fn as_lrc(&self) -> String {
-        let line = format!(
-            "[{}]{}",
-            time_lrc(self.time_stamp.try_into().unwrap_or(0)),
-            self.text
-        );
+        let mut line = format!("[{}]", time_lrc(self.time_stamp.try_into().unwrap_or(0)));
+        if self.words.is_empty() {
+            line += self.text.as_str();
+        }
+        for w in &self.words {
+            line += format!(
+                "<{}>{}",
+                time_lrc(w.time_stamp.try_into().unwrap_or(0)),
+                w.text
+            )
+            .as_str();
+        }
         line + EOL
     }
 }
 
+impl LyricSync {
+    /// Lines of `text`, which can be plain text or lrc. Time stamps of lrc are kept with the
+    /// offset applied, so syncing a lyric again only needs the wrong lines fixed.
+    pub fn new(text: &str) -> Self {
+        let mut sync = Self::default();
+        let mut offset = 0;
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some(cap) = ID_TAG_RE.captures(line) {
+                let value = cap[2].trim();
+                if cap[1].eq_ignore_ascii_case("offset") {
+                    offset = value.replace(' ', "").parse().unwrap_or(0);
+                } else {
+                    sync.tags.push((cap[1].to_lowercase(), value.to_string()));
+                }
+                continue;
+            }
+            match UnsyncedCaption::parse_line(&mut line.to_string()) {
+                Ok(caption) => sync.lines.push((Some(caption.time_stamp), caption.text)),
+                Err(_) => sync.lines.push((None, line.to_string())),
+            }
+        }
+        for (time_stamp, _) in &mut sync.lines {
+            *time_stamp = time_stamp.map(|t| (t - offset).max(0));
+        }
+        sync
+    }
+
+    pub fn lines(&self) -> &[(Option<i64>, String)] {
+        &self.lines
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn stamp(&mut self, index: usize, time: i64) {
+        if let Some(line) = self.lines.get_mut(index) {
+            line.0 = Some(time.max(0));
+        }
+    }
+
+    /// Move the time stamp of a line by `delta` milliseconds. Lines without one stay as is.
+    pub fn nudge(&mut self, index: usize, delta: i64) {
+        if let Some((Some(time_stamp), _)) = self.lines.get_mut(index) {
+            *time_stamp = (*time_stamp + delta).max(0);
+        }
+    }
+
+    pub fn clear(&mut self, index: usize) {
+        if let Some(line) = self.lines.get_mut(index) {
+            line.0 = None;
+        }
+    }
+
+    /// The stamped line that plays at `time` in milliseconds
+    pub fn line_at(&self, time: i64) -> Option<usize> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(_, (t, _))| t.map_or(false, |t| t <= time))
+            .max_by_key(|(_, (t, _))| *t)
+            .map(|(index, _)| index)
+    }
+
+    pub fn as_lrc_text(&self) -> Result<String> {
+        let mut unsynced_captions = vec![];
+        for (index, (time_stamp, text)) in self.lines.iter().enumerate() {
+            let Some(time_stamp) = time_stamp else {
+                bail!("line {} has no time stamp yet", index + 1);
+            };
+            unsynced_captions.push(UnsyncedCaption {
+                time_stamp: *time_stamp,
+                text: text.clone(),
+                words: vec![],
+            });
+        }
+        unsynced_captions.sort_by_key(|c| c.time_stamp);
+        let lyric = Lyric {
+            offset: 0,
+            lang_extension: None,
+            tags: self.tags.clone(),
+            unsynced_captions,
+        };
+        Ok(lyric.as_lrc_text())
+    }
+}
+
 fn time_lrc(time_stamp: u64) -> String {
     let time_duration = Duration::from_millis(time_stamp);
-    let _h = time_duration.as_secs() / 3600;
-    let m = (time_duration.as_secs() / 60) % 60;
+    let m = time_duration.as_secs() / 60;
     let s = time_duration.as_secs() % 60;
-    let ms = time_duration.as_millis() % 60;
+    let cs = time_duration.subsec_millis() / 10;
 
-    let res = format!("{m:02}:{s:02}.{ms:02}");
+    let res = format!("{m:02}:{s:02}.{cs:02}");
     res
 }
 
@@ -245,6 +513,7 @@ impl FromStr for Lyric {
         // lines := strings.Split(s, "\n")
         let mut offset: i64 = 0;
         let lang_extension = Some(String::new());
+        let mut tags = vec![];
         let mut unsynced_captions = vec![];
         for line in s.split('\n') {
             let mut line = line.to_string();
@@ -267,6 +536,9 @@ impl FromStr for Lyric {
                 if let Ok(o) = line.parse() {
                     offset = o;
                 }
+            } else if let Some(cap) = ID_TAG_RE.captures(&line) {
+                tags.push((cap[1].to_lowercase(), cap[2].trim().to_string()));
+                continue;
             }
 
             if !LINE_STARTS_WITH_RE.is_match(line.as_ref()) {
@@ -284,6 +556,7 @@ impl FromStr for Lyric {
         let mut lyric = Self {
             offset,
             lang_extension,
+            tags,
             unsynced_captions,
         };
 
@@ -292,3 +565,95 @@ impl FromStr for Lyric {
         Ok(lyric)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Lyric, LyricSync, Word};
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
+
+    const ENHANCED: &str = "[ti:Let's Twist Again]
+[ar:Chubby Checker]
+[length: 02:21]
+[offset:-200]
+[00:12.00]<00:12.00>Come <00:12.50>on <00:13.00>let's twist<00:14.00>
+[00:15.30]Some more lyrics ...";
+
+    #[test]
+    fn test_parse_tags() {
+        let lyric = Lyric::from_str(ENHANCED).unwrap();
+        assert_eq!(lyric.offset, -200);
+        assert_eq!(lyric.tag("ti"), Some("Let's Twist Again"));
+        assert_eq!(lyric.tag("ar"), Some("Chubby Checker"));
+        assert_eq!(lyric.tag("length"), Some("02:21"));
+        assert_eq!(lyric.unsynced_captions.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_words() {
+        let lyric = Lyric::from_str(ENHANCED).unwrap();
+        let line = &lyric.unsynced_captions[0];
+        assert_eq!(line.text(), "Come on let's twist");
+        assert_eq!(
+            line.words()[1],
+            Word {
+                time_stamp: 12500,
+                text: "on ".to_string()
+            }
+        );
+        assert_eq!(line.words().last().unwrap().text, "");
+        assert!(lyric.unsynced_captions[1].words().is_empty());
+
+        // offset -200 makes these 12.5s and 13.0s into the line
+        assert_eq!(lyric.karaoke(12700), Some((0, 5, 3)));
+        assert_eq!(lyric.karaoke(13200), Some((0, 8, 11)));
+        assert_eq!(lyric.karaoke(15600), Some((1, 0, 20)));
+        assert_eq!(lyric.karaoke(0), Some((0, 0, 0)));
+    }
+
+    #[test]
+    fn test_as_lrc_text() {
+        let lyric = Lyric::from_str(ENHANCED).unwrap();
+        let text = lyric.as_lrc_text();
+        assert_eq!(
+            text,
+            "[ti:Let's Twist Again]
+[ar:Chubby Checker]
+[length:02:21]
+[offset:-200]
+[00:12.00]<00:12.00>Come <00:12.50>on <00:13.00>let's twist<00:14.00>
+[00:15.30]Some more lyrics ...
+"
+        );
+        assert_eq!(Lyric::from_str(&text).unwrap().as_lrc_text(), text);
+    }
+
+    #[test]
+    fn test_lyric_sync() {
+        let mut sync = LyricSync::new("[ar:Chubby Checker]\nCome on\n\nlet's twist\nagain\n");
+        assert_eq!(sync.len(), 3);
+        assert!(sync.as_lrc_text().is_err());
+        sync.stamp(0, 12000);
+        sync.stamp(1, 15300);
+        sync.stamp(2, 17000);
+        sync.nudge(1, -100);
+        sync.clear(2);
+        assert_eq!(sync.line_at(16000), Some(1));
+        assert_eq!(sync.line_at(11000), None);
+        sync.stamp(2, 18010);
+        let text = sync.as_lrc_text().unwrap();
+        assert_eq!(
+            text,
+            "[ar:Chubby Checker]
+[00:12.00]Come on
+[00:15.20]let's twist
+[00:18.01]again
+"
+        );
+
+        // syncing again keeps the stamps, with the offset applied
+        let again = LyricSync::new(&format!("[offset:200]\n{text}"));
+        assert_eq!(again.lines()[0], (Some(11800), "Come on".to_string()));
+        assert_eq!(again.tags, sync.tags);
+    }
+}
@@ -23,6 +23,7 @@
  */
 mod model;
 
+use super::{LyricProvider, SongTag};
 use anyhow::{anyhow, Result};
 use bytes::Buf;
 use lofty::Picture;
@@ -35,14 +36,16 @@ static URL_SEARCH_MIGU: &str = "https://m.music.migu.cn/migu/remoting/scr_search
 static URL_LYRIC_MIGU: &str = "https://music.migu.cn/v3/api/music/audioPlayer/getLyric";
 static URL_PIC_MIGU: &str = "https://music.migu.cn/v3/api/music/audioPlayer/getSongPic";
 
+pub const NAME: &str = "migu";
+
 pub struct Api {
     client: Client,
 }
 
 impl Api {
-    pub fn new() -> Self {
+    pub fn new(timeout: Duration) -> Self {
         let client = ClientBuilder::new()
-            .timeout(Duration::from_secs(10))
+            .timeout(timeout)
             .build()
             .expect("client build error");
 
@@ -118,3 +121,86 @@ impl Api {
         // Ok(bytes)
     }
 }
+
+pub struct Provider {
+    timeout: Duration,
+}
+
+impl Provider {
+    pub const fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+// migu search results already carry a url, so the default download_url is used
+impl LyricProvider for Provider {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn search(&self, keywords: &str, limit: u16) -> Result<Vec<SongTag>> {
+        let results = Api::new(self.timeout).search(keywords, 1, 0, limit)?;
+        Ok(serde_json::from_str(&results)?)
+    }
+
+    fn fetch_lyric(&self, song_tag: &SongTag) -> Result<String> {
+        let lyric_id = song_tag
+            .lyric_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("lyric_id is missing for migu"))?;
+        Api::new(self.timeout).song_lyric(lyric_id)
+    }
+
+    fn fetch_cover(&self, song_tag: &SongTag) -> Result<Picture> {
+        let song_id = song_tag
+            .song_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("song_id is missing for migu"))?;
+        Api::new(self.timeout).pic(song_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::model::{to_lyric, to_song_info};
+    use super::{LyricProvider, Provider, SongTag};
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_search() {
+        // the second entry has no song name and is left out
+        let tags = to_song_info(include_str!("../test-data/migu_search.json")).unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].title.as_deref(), Some("Yesterday"));
+        assert_eq!(tags[0].artist.as_deref(), Some("The Beatles"));
+        assert_eq!(tags[0].album.as_deref(), Some("Help!"));
+        assert_eq!(tags[0].song_id.as_deref(), Some("1001877418"));
+        assert_eq!(tags[0].lyric_id.as_deref(), Some("6005751KSQ1"));
+        assert_eq!(tags[0].album_id.as_deref(), Some("1003215288"));
+        assert_eq!(
+            tags[0].url().as_deref(),
+            Some("https://freetyst.nf.migu.cn/public/product5th/product35/2019/10/3117/yesterday.mp3")
+        );
+        assert_eq!(tags[0].service_provider(), Some("migu"));
+    }
+
+    #[test]
+    fn test_parse_lyric() {
+        assert_eq!(
+            to_lyric(include_str!("../test-data/migu_lyric.json")).as_deref(),
+            Some("[00:00.00]Yesterday\r\n[00:02.50]All my troubles seemed so far away\r\n")
+        );
+        assert_eq!(
+            to_lyric(include_str!("../test-data/migu_nolyric.json")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fetch_lyric_needs_id() {
+        let provider = Provider::new(Duration::from_secs(1));
+        let err = provider.fetch_lyric(&SongTag::default()).unwrap_err();
+        assert_eq!(err.to_string(), "lyric_id is missing for migu");
+    }
+}
@@ -21,7 +21,8 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use super::super::{ServiceProvider, SongTag};
+use super::super::SongTag;
+use super::NAME;
 use serde_json::{json, Value};
 
 pub fn to_lyric(json: &str) -> Option<String> {
@@ -100,7 +101,7 @@ fn parse_song_info(v: &Value) -> Option<SongTag> {
         ),
         pic_id: Some(pic_id),
         lang_ext: Some("migu".to_string()),
-        service_provider: Some(ServiceProvider::Migu),
+        service_provider: Some(NAME.to_string()),
         lyric_id: Some(v.get("copyrightId")?.as_str()?.to_owned()),
         url: Some(url),
         album_id: Some(album_id),
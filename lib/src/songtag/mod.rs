@@ -21,31 +21,37 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+#[cfg(feature = "fingerprint")]
+pub mod acoustid;
 pub mod encrypt;
 mod kugou;
 pub mod lrc;
 mod migu;
 mod netease;
+#[allow(clippy::module_name_repetitions)]
+pub mod provider;
 
-use crate::types::{DLMsg, Msg, SearchLyricState};
+use crate::types::{DLMsg, Msg};
 use crate::utils::get_parent_folder;
-use anyhow::{anyhow, bail, Result};
+use anyhow::{bail, Result};
 use lofty::id3::v2::{Frame, FrameFlags, FrameValue, Id3v2Tag, UnsynchronizedTextFrame};
-use lofty::{Accessor, Picture, TagExt, TextEncoding};
+use lofty::{Accessor, TagExt, TextEncoding};
+pub use provider::{LyricProvider, Registry};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::mpsc::Sender;
 use std::thread::{self, sleep};
 use std::time::Duration;
 use ytd_rs::{Arg, YoutubeDL};
 
-#[derive(Deserialize, Serialize)]
+#[derive(Default, Deserialize, Serialize)]
 pub struct SongTag {
     artist: Option<String>,
     title: Option<String>,
     album: Option<String>,
     lang_ext: Option<String>,
-    service_provider: Option<ServiceProvider>,
+    /// Name of the `LyricProvider` this came from
+    service_provider: Option<String>,
     song_id: Option<String>,
     lyric_id: Option<String>,
     url: Option<String>,
@@ -54,85 +60,6 @@ pub struct SongTag {
     // genre: Option<String>,
 }
 
-#[derive(Deserialize, Serialize)]
-#[allow(clippy::use_self)]
-pub enum ServiceProvider {
-    Netease,
-    Kugou,
-    Migu,
-}
-
-impl std::fmt::Display for ServiceProvider {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let service_provider = match self {
-            Self::Netease => "Netease",
-            Self::Kugou => "Kugou",
-            Self::Migu => "Migu",
-        };
-        write!(f, "{service_provider}")
-    }
-}
-
-// Search function of 3 servers. Run in parallel to get results faster.
-pub fn search(search_str: &str, tx_tageditor: Sender<SearchLyricState>) {
-    let mut results: Vec<SongTag> = Vec::new();
-    let (tx, rx): (Sender<Vec<SongTag>>, Receiver<Vec<SongTag>>) = mpsc::channel();
-
-    let tx1 = tx.clone();
-    let search_str_netease = search_str.to_string();
-    let handle_netease = thread::spawn(move || -> Result<()> {
-        let mut netease_api = netease::Api::new();
-        if let Ok(results) = netease_api.search(&search_str_netease, 1, 0, 30) {
-            let result_new: Vec<SongTag> = serde_json::from_str(&results)?;
-            tx1.send(result_new).ok();
-        }
-        Ok(())
-    });
-
-    let tx2 = tx.clone();
-    let search_str_migu = search_str.to_string();
-    let handle_migu = thread::spawn(move || -> Result<()> {
-        let migu_api = migu::Api::new();
-        if let Ok(results) = migu_api.search(&search_str_migu, 1, 0, 30) {
-            let result_new: Vec<SongTag> = serde_json::from_str(&results)?;
-            tx2.send(result_new).ok();
-        }
-        Ok(())
-    });
-
-    let kugou_api = kugou::Api::new();
-    let search_str_kugou = search_str.to_string();
-    let handle_kugou = thread::spawn(move || -> Result<()> {
-        if let Ok(r) = kugou_api.search(&search_str_kugou, 1, 0, 30) {
-            let result_new: Vec<SongTag> = serde_json::from_str(&r)?;
-            tx.send(result_new).ok();
-        }
-        Ok(())
-    });
-
-    thread::spawn(move || {
-        if handle_netease.join().is_ok() {
-            if let Ok(result_new) = rx.try_recv() {
-                results.extend(result_new);
-            }
-        }
-
-        if handle_migu.join().is_ok() {
-            if let Ok(result_new) = rx.try_recv() {
-                results.extend(result_new);
-            }
-        }
-
-        if handle_kugou.join().is_ok() {
-            if let Ok(result_new) = rx.try_recv() {
-                results.extend(result_new);
-            }
-        }
-
-        tx_tageditor.send(SearchLyricState::Finish(results)).ok();
-    });
-}
-
 impl SongTag {
     pub fn artist(&self) -> Option<&str> {
         self.artist.as_deref()
@@ -167,103 +94,24 @@ impl SongTag {
         // }
     }
 
-    pub const fn service_provider(&self) -> Option<&ServiceProvider> {
-        self.service_provider.as_ref()
-        // match self.service_provider.as_ref() {
-        //     Some(service_provider) => Some(service_provider),
-        //     None => None,
-        // }
+    pub fn service_provider(&self) -> Option<&str> {
+        self.service_provider.as_deref()
     }
 
     pub fn url(&self) -> Option<String> {
         self.url.as_ref().map(std::string::ToString::to_string)
     }
-    // get lyric by lyric_id
-    pub fn fetch_lyric(&self) -> Result<String> {
-        let mut lyric_string = String::new();
-
-        match self.service_provider {
-            Some(ServiceProvider::Kugou) => {
-                let kugou_api = kugou::Api::new();
-                if let Some(lyric_id) = &self.lyric_id {
-                    lyric_string = kugou_api.song_lyric(lyric_id)?;
-                }
-            }
-            Some(ServiceProvider::Netease) => {
-                let mut netease_api = netease::Api::new();
-                if let Some(lyric_id) = &self.lyric_id {
-                    lyric_string = netease_api.song_lyric(lyric_id)?;
-                }
-            }
-            Some(ServiceProvider::Migu) => {
-                let migu_api = migu::Api::new();
-                if let Some(lyric_id) = &self.lyric_id {
-                    lyric_string = migu_api.song_lyric(lyric_id)?;
-                }
-            }
-            None => {}
-        }
-
-        Ok(lyric_string)
-    }
-
-    // get photo by pic_id(kugou/netease) or song_id(migu)
-    pub fn fetch_photo(&self) -> Result<Picture> {
-        // let mut encoded_image_bytes: Vec<u8> = Vec::new();
-
-        match self.service_provider {
-            Some(ServiceProvider::Kugou) => {
-                let kugou_api = kugou::Api::new();
-                if let Some(p) = &self.pic_id {
-                    if let Some(album_id) = &self.album_id {
-                        Ok(kugou_api.pic(p, album_id)?)
-                    } else {
-                        bail!("album_id is missing for kugou")
-                    }
-                } else {
-                    bail!("pic_id is missing for kugou")
-                }
-            }
-            Some(ServiceProvider::Netease) => {
-                let mut netease_api = netease::Api::new();
-                if let Some(p) = &self.pic_id {
-                    Ok(netease_api.pic(p)?)
-                } else {
-                    bail!("pic_id is missing for netease")
-                }
-            }
-            Some(ServiceProvider::Migu) => {
-                let migu_api = migu::Api::new();
-                if let Some(p) = &self.song_id {
-                    Ok(migu_api.pic(p)?)
-                } else {
-                    bail!("song_id is missing for migu")
-                }
-            }
-            None => {
-                bail!("no servie provider given");
-            }
-        }
-
-        // if encoded_image_bytes.is_empty() {
-        //     bail!("failed to fetch image");
-        // }
-
-        // Ok(Picture::new_unchecked(
-        //     PictureType::Other,
-        //     MimeType::Jpeg,
-        //     Some(String::from("Image")),
-        //     encoded_image_bytes,
-        // ))
-    }
-
     #[allow(clippy::too_many_lines)]
-    pub fn download(&self, file: &str, tx_tageditor: &Sender<Msg>) -> Result<()> {
+    pub fn download(
+        &self,
+        file: &str,
+        providers: &Registry,
+        tx_tageditor: &Sender<Msg>,
+    ) -> Result<()> {
         let p_parent = get_parent_folder(file);
-        let song_id = self
-            .song_id
-            .as_ref()
-            .ok_or_else(|| anyhow!("error downloading because no song id is found"))?;
+        if self.song_id.is_none() {
+            bail!("error downloading because no song id is found");
+        }
         let artist = self
             .artist
             .clone()
@@ -274,9 +122,8 @@ impl SongTag {
             .unwrap_or_else(|| "Unknown Title".to_string());
 
         let album = self.album.clone().unwrap_or_else(|| String::from("N/A"));
-        let lyric = self.fetch_lyric();
-        let photo = self.fetch_photo();
-        let album_id = self.album_id.clone().unwrap_or_else(|| String::from("N/A"));
+        let lyric = providers.fetch_lyric(self);
+        let photo = providers.fetch_cover(self);
 
         let filename = format!("{artist}-{title}.%(ext)s");
 
@@ -294,21 +141,7 @@ impl SongTag {
         if mp3_url.starts_with("Copyright") {
             bail!("Copyright protected, please select another item.");
         }
-        let mut url = mp3_url;
-
-        if let Some(s) = &self.service_provider {
-            match s {
-                ServiceProvider::Netease => {
-                    let mut netease_api = netease::Api::new();
-                    url = netease_api.song_url(song_id)?;
-                }
-                ServiceProvider::Migu => {}
-                ServiceProvider::Kugou => {
-                    let kugou_api = kugou::Api::new();
-                    url = kugou_api.song_url(song_id, &album_id)?;
-                }
-            }
-        }
+        let url = providers.download_url(self)?;
 
         if url.is_empty() {
             bail!("url fetch failed, please try another item.");
@@ -6,6 +6,7 @@
 mod model;
 
 use super::encrypt::Crypto;
+use super::{LyricProvider, SongTag};
 use anyhow::{anyhow, bail, Result};
 use lazy_static::lazy_static;
 use lofty::Picture;
@@ -22,6 +23,8 @@ lazy_static! {
 
 static BASE_URL_NETEASE: &str = "https://music.163.com";
 
+pub const NAME: &str = "netease";
+
 const LINUX_USER_AGENT: &str =
     "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/60.0.3112.90 Safari/537.36";
 
@@ -56,9 +59,9 @@ enum CryptoApi {
 
 impl Api {
     #[allow(unused)]
-    pub fn new() -> Self {
+    pub fn new(timeout: Duration) -> Self {
         let client = ClientBuilder::new()
-            .timeout(Duration::from_secs(10))
+            .timeout(timeout)
             .build()
             .expect("build client error");
 
@@ -258,3 +261,94 @@ fn choose_user_agent(ua: &str) -> &str {
     };
     USER_AGENT_LIST[index]
 }
+
+pub struct Provider {
+    timeout: Duration,
+}
+
+impl Provider {
+    pub const fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl LyricProvider for Provider {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn search(&self, keywords: &str, limit: u16) -> Result<Vec<SongTag>> {
+        let results = Api::new(self.timeout).search(keywords, 1, 0, limit)?;
+        Ok(serde_json::from_str(&results)?)
+    }
+
+    fn fetch_lyric(&self, song_tag: &SongTag) -> Result<String> {
+        let lyric_id = song_tag
+            .lyric_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("lyric_id is missing for netease"))?;
+        Api::new(self.timeout).song_lyric(lyric_id)
+    }
+
+    fn fetch_cover(&self, song_tag: &SongTag) -> Result<Picture> {
+        let pic_id = song_tag
+            .pic_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("pic_id is missing for netease"))?;
+        Api::new(self.timeout).pic(pic_id)
+    }
+
+    fn download_url(&self, song_tag: &SongTag) -> Result<String> {
+        let song_id = song_tag
+            .song_id
+            .as_ref()
+            .ok_or_else(|| anyhow!("song_id is missing for netease"))?;
+        Api::new(self.timeout).song_url(song_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::model::{to_lyric, to_song_info, Parse};
+    use super::{LyricProvider, Provider, SongTag};
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_search() {
+        let tags = to_song_info(
+            include_str!("../test-data/netease_search.json"),
+            Parse::Search,
+        )
+        .unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].title.as_deref(), Some("晴天"));
+        assert_eq!(tags[0].artist.as_deref(), Some("周杰伦"));
+        assert_eq!(tags[0].album.as_deref(), Some("叶惠美"));
+        assert_eq!(tags[0].song_id.as_deref(), Some("1372188635"));
+        assert_eq!(tags[0].lyric_id.as_deref(), Some("1372188635"));
+        assert_eq!(tags[0].pic_id.as_deref(), Some("109951163200249252"));
+        assert_eq!(tags[0].url().as_deref(), Some("Copyright protected"));
+        assert_eq!(tags[0].service_provider(), Some("netease"));
+        assert_eq!(tags[1].url().as_deref(), Some("Downloadable"));
+    }
+
+    #[test]
+    fn test_parse_lyric() {
+        assert_eq!(
+            to_lyric(include_str!("../test-data/netease_lyric.json")).as_deref(),
+            Some("[00:00.00] 作词 : 方文山\n[00:27.30]故事的小黄花\n")
+        );
+        assert_eq!(
+            to_lyric(include_str!("../test-data/netease_nolyric.json")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fetch_lyric_needs_id() {
+        let provider = Provider::new(Duration::from_secs(1));
+        let err = provider.fetch_lyric(&SongTag::default()).unwrap_err();
+        assert_eq!(err.to_string(), "lyric_id is missing for netease");
+    }
+}
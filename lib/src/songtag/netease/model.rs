@@ -3,7 +3,8 @@
  * Copyright (C) 2019 gmg137 <gmg137@live.com>
  * Distributed under terms of the GPLv3 license.
  */
-use super::super::{ServiceProvider, SongTag};
+use super::super::SongTag;
+use super::NAME;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
@@ -166,7 +167,7 @@ fn parse_song_info(v: &Value) -> Option<SongTag> {
         lang_ext: Some(String::from("netease")),
         lyric_id: Some(v.get("id")?.as_u64()?.to_string()),
         song_id: Some(v.get("id")?.as_u64()?.to_string()),
-        service_provider: Some(ServiceProvider::Netease),
+        service_provider: Some(NAME.to_string()),
         url: Some(
             if v.get("fee")?.as_u64()? == 0 {
                 "Downloadable"
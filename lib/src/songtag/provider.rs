@@ -0,0 +1,202 @@
+/**
+ * MIT License
+ *
+ * termusic - Copyright (c) 2021 Larry Hao
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::{kugou, migu, netease, SongTag};
+use crate::config::LyricProviderSettings;
+use crate::parallel::{Pending, Sources};
+use crate::types::SearchLyricState;
+use anyhow::{anyhow, Result};
+use lofty::Picture;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Number of results asked from each provider
+const SEARCH_LIMIT: u16 = 30;
+
+/// A source of lyrics and album covers for the tag editor.
+pub trait LyricProvider: Send + Sync {
+    /// Name used in `lyric_providers` of config.toml, also stored in every `SongTag` it returns
+    fn name(&self) -> &'static str;
+
+    fn search(&self, keywords: &str, limit: u16) -> Result<Vec<SongTag>>;
+
+    fn fetch_lyric(&self, song_tag: &SongTag) -> Result<String>;
+
+    fn fetch_cover(&self, song_tag: &SongTag) -> Result<Picture>;
+
+    /// Url that `yt-dlp` can download the song from. Providers without downloads keep the
+    /// default, which only works if search already filled in `SongTag::url`.
+    fn download_url(&self, song_tag: &SongTag) -> Result<String> {
+        song_tag
+            .url()
+            .ok_or_else(|| anyhow!("{} does not support downloading", self.name()))
+    }
+}
+
+/// Create the provider called `name`. New providers only need an entry here.
+fn build(name: &str, timeout: Duration) -> Option<Arc<dyn LyricProvider>> {
+    match name {
+        netease::NAME => Some(Arc::new(netease::Provider::new(timeout))),
+        migu::NAME => Some(Arc::new(migu::Provider::new(timeout))),
+        kugou::NAME => Some(Arc::new(kugou::Provider::new(timeout))),
+        _ => None,
+    }
+}
+
+/// The enabled providers, in the order their search results are listed.
+#[derive(Default)]
+pub struct Registry {
+    providers: Sources<dyn LyricProvider>,
+}
+
+impl Registry {
+    /// Build from config. Disabled and unknown providers are left out.
+    pub fn new(settings: &[LyricProviderSettings]) -> Self {
+        let mut registry = Self::default();
+        for s in settings.iter().filter(|s| s.enabled) {
+            let timeout = Duration::from_secs(s.timeout_secs);
+            if let Some(provider) = build(&s.name, timeout) {
+                registry.push(provider, timeout);
+            }
+        }
+        registry
+    }
+
+    pub fn push(&mut self, provider: Arc<dyn LyricProvider>, timeout: Duration) {
+        self.providers.push(provider.name(), provider, timeout);
+    }
+
+    pub fn get(&self, name: &str) -> Result<&dyn LyricProvider> {
+        self.providers
+            .get(name)
+            .ok_or_else(|| anyhow!("lyric provider {name} is not enabled"))
+    }
+
+    fn provider_of(&self, song_tag: &SongTag) -> Result<&dyn LyricProvider> {
+        let name = song_tag
+            .service_provider()
+            .ok_or_else(|| anyhow!("no service provider given"))?;
+        self.get(name)
+    }
+
+    pub fn fetch_lyric(&self, song_tag: &SongTag) -> Result<String> {
+        self.provider_of(song_tag)?.fetch_lyric(song_tag)
+    }
+
+    pub fn fetch_cover(&self, song_tag: &SongTag) -> Result<Picture> {
+        self.provider_of(song_tag)?.fetch_cover(song_tag)
+    }
+
+    pub fn download_url(&self, song_tag: &SongTag) -> Result<String> {
+        self.provider_of(song_tag)?.download_url(song_tag)
+    }
+
+    /// Search all providers in parallel and send the results to the tag editor when done.
+    pub fn search(&self, keywords: &str, tx_tageditor: Sender<SearchLyricState>) {
+        let pending = self.spawn_searches(keywords);
+        thread::spawn(move || {
+            tx_tageditor
+                .send(SearchLyricState::Finish(collect(pending)))
+                .ok();
+        });
+    }
+
+    fn spawn_searches(&self, keywords: &str) -> Pending<Vec<SongTag>> {
+        let keywords = keywords.to_string();
+        self.providers
+            .spawn(move |provider| provider.search(&keywords, SEARCH_LIMIT))
+    }
+}
+
+/// Results in registry order. A provider that fails or misses its deadline is skipped.
+fn collect(pending: Pending<Vec<SongTag>>) -> Vec<SongTag> {
+    pending
+        .collect()
+        .into_iter()
+        .filter_map(|(_, answer)| answer.ok())
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect, LyricProvider, Registry, SongTag};
+    use crate::config::Settings;
+    use anyhow::{bail, Result};
+    use lofty::Picture;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    struct Fake(&'static str);
+
+    impl LyricProvider for Fake {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+        fn search(&self, keywords: &str, _limit: u16) -> Result<Vec<SongTag>> {
+            Ok(vec![SongTag {
+                title: Some(keywords.to_string()),
+                service_provider: Some(self.0.to_string()),
+                ..SongTag::default()
+            }])
+        }
+        fn fetch_lyric(&self, _song_tag: &SongTag) -> Result<String> {
+            Ok(format!("[00:01.00]{}", self.0))
+        }
+        fn fetch_cover(&self, _song_tag: &SongTag) -> Result<Picture> {
+            bail!("no cover")
+        }
+    }
+
+    #[test]
+    fn test_registry_from_config() {
+        let mut settings = Settings::default().lyric_providers;
+        settings[1].enabled = false;
+        settings[2].name = "nonexistent".to_string();
+        let registry = Registry::new(&settings);
+        assert_eq!(registry.providers.names(), vec!["netease"]);
+        assert!(registry.get("migu").is_err());
+    }
+
+    #[test]
+    fn test_registry_search() {
+        let mut registry = Registry::default();
+        registry.push(Arc::new(Fake("first")), Duration::from_secs(5));
+        registry.push(Arc::new(Fake("second")), Duration::from_secs(5));
+
+        let results = collect(registry.spawn_searches("hello"));
+        let providers: Vec<&str> = results
+            .iter()
+            .filter_map(SongTag::service_provider)
+            .collect();
+        assert_eq!(providers, vec!["first", "second"]);
+        assert_eq!(results[0].title.as_deref(), Some("hello"));
+        assert_eq!(
+            registry.fetch_lyric(&results[1]).unwrap(),
+            "[00:01.00]second"
+        );
+    }
+}
@@ -23,21 +23,23 @@
  */
 // database
 use crate::config::Settings;
+use crate::organize::Move;
 use crate::track::Track;
 use crate::utils::{filetype_supported, get_app_config_path, get_pin_yin};
-use rusqlite::{params, Connection, Error, Result, Row};
+use rusqlite::{params, Connection, Error, Result, Row, Transaction};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, UNIX_EPOCH};
 
-const DB_VERSION: u32 = 2;
+const DB_VERSION: u32 = 6;
 
+#[derive(Debug)]
 pub struct DataBase {
     conn: Arc<Mutex<Connection>>,
     max_depth: usize,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TrackForDB {
     pub id: u64,
     pub artist: String,
@@ -51,24 +53,50 @@ pub struct TrackForDB {
     pub directory: String,
     pub last_modified: String,
     pub last_position: Duration,
+    pub album_artist: String,
+    pub year: Option<u32>,
+    pub track_number: Option<u32>,
+    pub disc_number: Option<u32>,
+    pub composer: String,
+    pub comment: String,
+    pub bitrate: Option<u32>,
+    pub sample_rate: Option<u32>,
+    pub file_size: u64,
+    pub has_picture: bool,
+    pub has_lyrics: bool,
+}
+
+/// An album of one album artist, as listed by the browse view
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlbumForDB {
+    pub album: String,
+    pub year: Option<u32>,
+    pub track_count: u32,
+    pub duration: Duration,
+    /// One file of the album, used to look up its cover
+    pub file: String,
 }
 
 #[derive(PartialEq, Eq)]
 pub enum SearchCriteria {
     Artist,
+    AlbumArtist,
     Album,
     Genre,
     Directory,
     Playlist,
+    Duplicates,
 }
 
 impl From<usize> for SearchCriteria {
     fn from(u_index: usize) -> Self {
         match u_index {
-            1 => Self::Album,
-            2 => Self::Genre,
-            3 => Self::Directory,
-            4 => Self::Playlist,
+            1 => Self::AlbumArtist,
+            2 => Self::Album,
+            3 => Self::Genre,
+            4 => Self::Directory,
+            5 => Self::Playlist,
+            6 => Self::Duplicates,
             _ => Self::Artist,
             // 0 | _ => Self::Artist,
         }
@@ -79,10 +107,12 @@ impl std::fmt::Display for SearchCriteria {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Artist => write!(f, "artist"),
+            Self::AlbumArtist => write!(f, "album_artist"),
             Self::Album => write!(f, "album"),
             Self::Genre => write!(f, "genre"),
             Self::Directory => write!(f, "directory"),
             Self::Playlist => write!(f, "playlist"),
+            Self::Duplicates => write!(f, "duplicates"),
         }
     }
 }
@@ -100,6 +130,7 @@ impl DataBase {
             .expect("get user_version error");
         if DB_VERSION != user_version {
             conn.execute("DROP TABLE tracks", []).ok();
+            conn.execute("DROP TABLE tracks_fts", []).ok();
             conn.pragma_update(None, "user_version", DB_VERSION)
                 .expect("update user_version error");
         }
@@ -117,52 +148,184 @@ impl DataBase {
              ext TEXT,
              directory TEXT,
              last_modified TEXT,
-             last_position INTERGER
+             last_position INTERGER,
+             album_artist TEXT,
+             year INTEGER,
+             track_number INTEGER,
+             disc_number INTEGER,
+             composer TEXT,
+             comment TEXT,
+             bitrate INTEGER,
+             sample_rate INTEGER,
+             file_size INTEGER,
+             has_picture INTEGER,
+             has_lyrics INTEGER
             )",
             [],
         )
         .expect("create table tracks failed");
 
+        // rowid mirrors tracks.id. The trigram tokenizer gives substring matching, and
+        // splitting the query into trigrams (see `fts_query`) makes it typo tolerant.
+        conn.execute(
+            "create virtual table if not exists tracks_fts using fts5(
+             artist,
+             title,
+             album,
+             genre,
+             file,
+             pinyin,
+             tokenize = 'trigram'
+            )",
+            [],
+        )
+        .expect("create table tracks_fts failed");
+
         let max_depth = config.max_depth_cli;
 
         let conn = Arc::new(Mutex::new(conn));
         Self { conn, max_depth }
     }
 
-    fn add_records(conn: &Arc<Mutex<Connection>>, tracks: Vec<Track>) -> Result<()> {
+    fn add_records(conn: &Arc<Mutex<Connection>>, tracks: &[Track]) -> Result<()> {
         let mut conn = conn.lock().expect("conn is not available for add records");
         let tx = conn.transaction()?;
 
         for track in tracks {
+            Self::insert_record(&tx, track)?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Replace the records of `tracks` after their tags were saved, in one transaction. Each
+    /// comes with the file it had before, as saving may rename it. The playback position is
+    /// kept.
+    pub fn update_records(&self, tracks: &[(String, Track)]) -> Result<()> {
+        let mut conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for update records");
+        let tx = conn.transaction()?;
+
+        for (file, track) in tracks {
+            let last_position: Option<u64> = tx
+                .query_row(
+                    "SELECT last_position FROM tracks WHERE file = ?",
+                    params![file],
+                    |row| row.get(0),
+                )
+                .ok();
             tx.execute(
-            "INSERT INTO tracks (artist, title, album, genre,  file, duration, name, ext, directory, last_modified, last_position) 
-            values (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-            params![
-                track.artist().unwrap_or("Unknown Artist").to_string(),
-                track.title().unwrap_or("Unknown Title").to_string(),
-                track.album().unwrap_or("empty").to_string(),
-                track.genre().unwrap_or("no type").to_string(),
-                track.file().unwrap_or("Unknown File").to_string(),
-                track.duration().as_secs(),
-                track.name().unwrap_or_default().to_string(),
-                track.ext().unwrap_or_default().to_string(),
-                track.directory().unwrap_or_default().to_string(),
-                track
-                    .last_modified
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_secs()
-                    .to_string(),
-                0,
+                "DELETE FROM tracks_fts WHERE rowid IN (SELECT id FROM tracks WHERE file = ?)",
+                params![file],
+            )?;
+            tx.execute("DELETE FROM tracks WHERE file = ?", params![file])?;
+            Self::insert_record(&tx, track)?;
+            if let Some(last_position) = last_position {
+                tx.execute(
+                    "UPDATE tracks SET last_position = ? WHERE id = ?",
+                    params![last_position, tx.last_insert_rowid()],
+                )?;
+            }
+        }
 
-            ],
-        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Point the records of moved files to their new path. Everything else, like the
+    /// playback position, stays.
+    pub fn move_records(&self, moves: &[Move]) -> Result<()> {
+        let mut conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for move records");
+        let tx = conn.transaction()?;
+
+        for m in moves {
+            let from = m.from.to_string_lossy();
+            let to = m.to.to_string_lossy();
+            let name =
+                m.to.file_name()
+                    .map_or_else(String::new, |n| n.to_string_lossy().to_string());
+            let directory =
+                m.to.parent()
+                    .map_or_else(String::new, |d| d.to_string_lossy().to_string());
+            tx.execute(
+                "UPDATE tracks_fts SET file = ?1 WHERE rowid IN (SELECT id FROM tracks WHERE file = ?2)",
+                params![to, from],
+            )?;
+            tx.execute(
+                "UPDATE tracks SET file = ?1, name = ?2, directory = ?3 WHERE file = ?4",
+                params![to, name, directory, from],
+            )?;
         }
 
         tx.commit()?;
         Ok(())
     }
 
+    fn insert_record(tx: &Transaction<'_>, track: &Track) -> Result<()> {
+        tx.execute(
+        "INSERT INTO tracks (artist, title, album, genre,  file, duration, name, ext, directory, last_modified, last_position, album_artist, year, track_number, disc_number, composer, comment, bitrate, sample_rate, file_size, has_picture, has_lyrics) 
+        values (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22)",
+        params![
+            track.artist().unwrap_or("Unknown Artist").to_string(),
+            track.title().unwrap_or("Unknown Title").to_string(),
+            track.album().unwrap_or("empty").to_string(),
+            track.genre().unwrap_or("no type").to_string(),
+            track.file().unwrap_or("Unknown File").to_string(),
+            track.duration().as_secs(),
+            track.name().unwrap_or_default().to_string(),
+            track.ext().unwrap_or_default().to_string(),
+            track.directory().unwrap_or_default().to_string(),
+            track
+                .last_modified
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+                .to_string(),
+            0,
+            // fall back to the track artist so untagged albums still group somewhere
+            track
+                .album_artist()
+                .or_else(|| track.artist())
+                .unwrap_or("Unknown Artist")
+                .to_string(),
+            track.year(),
+            track.track_number(),
+            track.disc_number(),
+            track.composer().unwrap_or_default().to_string(),
+            track.comment().unwrap_or_default().to_string(),
+            track.bitrate(),
+            track.sample_rate(),
+            track
+                .file()
+                .and_then(|f| std::fs::metadata(f).ok())
+                .map_or(0, |m| m.len()),
+            track.has_embedded_picture(),
+            track.has_embedded_lyrics(),
+        ],
+    )?;
+        let id = tx.last_insert_rowid();
+        tx.execute(
+            "INSERT INTO tracks_fts (rowid, artist, title, album, genre, file, pinyin)
+            values (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                id,
+                track.artist().unwrap_or_default(),
+                track.title().unwrap_or_default(),
+                track.album().unwrap_or_default(),
+                track.genre().unwrap_or_default(),
+                track.file().unwrap_or_default(),
+                pinyin_for_index(track),
+            ],
+        )?;
+        Ok(())
+    }
+
     fn need_update(conn: &Arc<Mutex<Connection>>, path: &Path) -> Result<bool> {
         let conn = conn.lock().expect("conn is not available for need update.");
         let filename = path
@@ -213,6 +376,10 @@ impl DataBase {
         let tx = conn.transaction()?;
 
         for track in tracks {
+            tx.execute(
+                "DELETE FROM tracks_fts WHERE rowid IN (SELECT id FROM tracks WHERE file = ?)",
+                params![track],
+            )?;
             tx.execute("DELETE FROM tracks WHERE file = ?", params![track])?;
         }
 
@@ -248,7 +415,7 @@ impl DataBase {
                 }
             }
             if !track_vec.is_empty() {
-                Self::add_records(&conn, track_vec)?;
+                Self::add_records(&conn, &track_vec)?;
             }
 
             // delete records where local file are missing
@@ -303,10 +470,95 @@ impl DataBase {
         // eprintln!("cri: {}", cri);
         // eprintln!("vec: {:?}", vec_records);
 
-        vec_records.sort_by_cached_key(|k| get_pin_yin(&k.name));
+        if *cri == SearchCriteria::Album {
+            sort_album_tracks(&mut vec_records);
+        } else {
+            vec_records.sort_by_cached_key(|k| get_pin_yin(&k.name));
+        }
         Ok(vec_records)
     }
 
+    /// Albums of `album_artist` with their year, track count and total duration, oldest first.
+    /// Albums without a year come last.
+    pub fn get_albums_by_artist(&self, album_artist: &str) -> Result<Vec<AlbumForDB>> {
+        let conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for get albums by artist.");
+        let mut stmt = conn.prepare(
+            "SELECT album, MAX(year), COUNT(*), SUM(duration), MIN(file) FROM tracks
+             WHERE album_artist = ?1 GROUP BY album",
+        )?;
+
+        let mut vec: Vec<AlbumForDB> = stmt
+            .query_map([album_artist], |row| {
+                let duration: u64 = row.get(3)?;
+                Ok(AlbumForDB {
+                    album: row.get(0)?,
+                    year: row.get(1)?,
+                    track_count: row.get(2)?,
+                    duration: Duration::from_secs(duration),
+                    file: row.get(4)?,
+                })
+            })?
+            .flatten()
+            .collect();
+
+        vec.sort_by_cached_key(|a| (a.year.is_none(), a.year, get_pin_yin(&a.album)));
+        Ok(vec)
+    }
+
+    /// Tracks of one album of `album_artist`, in disc and track order.
+    pub fn get_album_tracks(&self, album_artist: &str, album: &str) -> Result<Vec<TrackForDB>> {
+        let conn = self
+            .conn
+            .lock()
+            .expect("conn is not available for get album tracks.");
+        let mut stmt =
+            conn.prepare("SELECT * FROM tracks WHERE album_artist = ?1 AND album = ?2")?;
+
+        let mut vec_records: Vec<TrackForDB> = stmt
+            .query_map([album_artist, album], |row| Ok(Self::track_db(row)))?
+            .flatten()
+            .collect();
+
+        sort_album_tracks(&mut vec_records);
+        Ok(vec_records)
+    }
+
+    /// Ranked fuzzy search over artist, title, album, genre, file path and the pinyin of
+    /// artist/title/album. Queries shorter than a trigram fall back to a plain `LIKE`.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<TrackForDB>> {
+        let conn = self.conn.lock().expect("conn is not available for search.");
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+
+        let Some(fts_query) = fts_query(query) else {
+            let like = like_pattern(query);
+            let mut stmt = conn.prepare(
+                "SELECT * FROM tracks WHERE artist LIKE ?1 ESCAPE '\\' OR title LIKE ?1 ESCAPE '\\'
+                 OR album LIKE ?1 ESCAPE '\\' OR genre LIKE ?1 ESCAPE '\\'
+                 OR file LIKE ?1 ESCAPE '\\' LIMIT ?2",
+            )?;
+            let vec = stmt
+                .query_map(params![like, limit], |row| Ok(Self::track_db(row)))?
+                .flatten()
+                .collect();
+            return Ok(vec);
+        };
+
+        // artist and title weigh the most, file path the least
+        let mut stmt = conn.prepare(
+            "SELECT tracks.* FROM tracks_fts JOIN tracks ON tracks.id = tracks_fts.rowid
+             WHERE tracks_fts MATCH ?1
+             ORDER BY bm25(tracks_fts, 10.0, 10.0, 5.0, 2.0, 1.0, 5.0) LIMIT ?2",
+        )?;
+        let vec = stmt
+            .query_map(params![fts_query, limit], |row| Ok(Self::track_db(row)))?
+            .flatten()
+            .collect();
+        Ok(vec)
+    }
+
     fn track_db(row: &Row<'_>) -> TrackForDB {
         let d_u64: u64 = row.get(6).unwrap();
         let last_position_u64: u64 = row.get(11).unwrap();
@@ -324,6 +576,17 @@ impl DataBase {
             directory: row.get(9).unwrap(),
             last_modified: row.get(10).unwrap(),
             last_position: Duration::from_secs(last_position_u64),
+            album_artist: row.get(12).unwrap_or_default(),
+            year: row.get(13).unwrap_or_default(),
+            track_number: row.get(14).unwrap_or_default(),
+            disc_number: row.get(15).unwrap_or_default(),
+            composer: row.get(16).unwrap_or_default(),
+            comment: row.get(17).unwrap_or_default(),
+            bitrate: row.get(18).unwrap_or_default(),
+            sample_rate: row.get(19).unwrap_or_default(),
+            file_size: row.get(20).unwrap_or_default(),
+            has_picture: row.get(21).unwrap_or_default(),
+            has_lyrics: row.get(22).unwrap_or_default(),
         }
     }
 
@@ -387,6 +650,10 @@ impl DataBase {
         // eprintln!("set last position as {}", last_position.as_secs());
     }
 
+    pub fn delete_record_by_path(&self, file: &str) -> Result<()> {
+        Self::delete_records(&self.conn, vec![file.to_string()])
+    }
+
     pub fn get_record_by_path(&mut self, str: &str) -> Result<TrackForDB> {
         let search_str = "SELECT * FROM tracks WHERE file = ?";
         let conn = self
@@ -410,3 +677,90 @@ impl DataBase {
         Err(Error::QueryReturnedNoRows)
     }
 }
+
+fn sort_album_tracks(tracks: &mut [TrackForDB]) {
+    tracks.sort_by_cached_key(|k| {
+        (
+            k.disc_number.unwrap_or(1),
+            k.track_number.unwrap_or(u32::MAX),
+            get_pin_yin(&k.name),
+        )
+    });
+}
+
+/// Pinyin of artist, title and album, only for fields where it differs from the text itself.
+fn pinyin_for_index(track: &Track) -> String {
+    [track.artist(), track.title(), track.album()]
+        .into_iter()
+        .flatten()
+        .filter_map(|field| {
+            let pinyin = get_pin_yin(field);
+            (pinyin != field.to_uppercase()).then_some(pinyin)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Turn user input into an FTS5 query that ORs together every trigram of every word, so a
+/// track still matches when some of them are wrong and bm25 ranks closer matches higher.
+/// Returns `None` when no word is long enough to form a trigram.
+fn fts_query(input: &str) -> Option<String> {
+    let mut trigrams: Vec<String> = Vec::new();
+    for word in input.split_whitespace() {
+        let chars: Vec<char> = word
+            .chars()
+            .filter(|c| !matches!(c, '*' | '?'))
+            .flat_map(char::to_lowercase)
+            .collect();
+        for window in chars.windows(3) {
+            let trigram: String = window.iter().collect();
+            let quoted = format!("\"{}\"", trigram.replace('"', "\"\""));
+            if !trigrams.contains(&quoted) {
+                trigrams.push(quoted);
+            }
+        }
+    }
+    if trigrams.is_empty() {
+        return None;
+    }
+    Some(trigrams.join(" OR "))
+}
+
+/// `LIKE` pattern matching `query` anywhere, with its `%` and `_` taken literally
+fn like_pattern(query: &str) -> String {
+    format!(
+        "%{}%",
+        query
+            .trim()
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fts_query, like_pattern};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(fts_query("ab"), None);
+        assert_eq!(fts_query("*?"), None);
+        assert_eq!(fts_query("Abba"), Some(r#""abb" OR "bba""#.to_string()));
+        assert_eq!(
+            fts_query("lalala la"),
+            Some(r#""lal" OR "ala""#.to_string())
+        );
+        assert_eq!(
+            fts_query(r#"a"bc"#),
+            Some(r#""a""b" OR """bc""#.to_string())
+        );
+    }
+
+    #[test]
+    fn test_like_pattern() {
+        assert_eq!(like_pattern(" ab "), "%ab%");
+        assert_eq!(like_pattern(r"5%_\"), r"%5\%\_\\%");
+    }
+}
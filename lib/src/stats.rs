@@ -0,0 +1,340 @@
+/**
+ * MIT License
+ *
+ * termusic - Copyright (c) 2021 Larry Hao
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// summary of library.db for `termusic stats` and the stats popup
+use crate::cover;
+use crate::sqlite::TrackForDB;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+/// What `DataBase` and `Track` store in place of a tag that is not there
+const PLACEHOLDERS: [&str; 7] = [
+    "",
+    "Unknown Artist",
+    "Unknown Title",
+    "Unsupported?",
+    "empty",
+    "no type",
+    "Unknown",
+];
+
+/// Genres beyond this many are only listed in the JSON output
+const TEXT_GENRE_LIMIT: usize = 15;
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct LibraryStats {
+    pub tracks: usize,
+    pub artists: usize,
+    pub albums: usize,
+    /// Total duration in seconds
+    pub duration: u64,
+    /// Total size in bytes
+    pub size: u64,
+    pub formats: Vec<Count>,
+    pub genres: Vec<Count>,
+    pub bitrates: Vec<Count>,
+    pub sample_rates: Vec<Count>,
+    pub missing: Missing,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Count {
+    pub name: String,
+    pub count: usize,
+}
+
+/// Number of tracks without each piece of metadata
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Missing {
+    pub artist: usize,
+    pub title: usize,
+    pub album: usize,
+    pub genre: usize,
+    pub year: usize,
+    pub track_number: usize,
+    pub cover: usize,
+    pub lyrics: usize,
+}
+
+impl LibraryStats {
+    /// Summarize `records`. A cover counts if it is embedded or an image sits next to the
+    /// file, found the way the player looks for album art.
+    pub fn from_records(records: &[TrackForDB]) -> Self {
+        let mut artists = HashSet::new();
+        let mut albums = HashSet::new();
+        let mut folder_has_image: HashMap<&str, bool> = HashMap::new();
+        let mut stats = Self {
+            tracks: records.len(),
+            ..Self::default()
+        };
+
+        for record in records {
+            stats.duration += record.duration.as_secs();
+            stats.size += record.file_size;
+
+            if is_missing(&record.artist) {
+                stats.missing.artist += 1;
+            } else {
+                artists.insert(record.artist.as_str());
+            }
+            if is_missing(&record.album) {
+                stats.missing.album += 1;
+            } else {
+                albums.insert((record.album_artist.as_str(), record.album.as_str()));
+            }
+            let file_stem = Path::new(&record.name)
+                .file_stem()
+                .map(std::ffi::OsStr::to_string_lossy);
+            if is_missing(&record.title) || file_stem.as_deref() == Some(record.title.as_str()) {
+                stats.missing.title += 1;
+            }
+            if is_missing(&record.genre) {
+                stats.missing.genre += 1;
+            }
+            if record.year.is_none() {
+                stats.missing.year += 1;
+            }
+            if record.track_number.is_none() {
+                stats.missing.track_number += 1;
+            }
+            if !record.has_lyrics {
+                stats.missing.lyrics += 1;
+            }
+            if !record.has_picture
+                && !*folder_has_image
+                    .entry(record.directory.as_str())
+                    .or_insert_with(|| has_image(&record.directory))
+            {
+                stats.missing.cover += 1;
+            }
+        }
+
+        stats.artists = artists.len();
+        stats.albums = albums.len();
+        stats.formats = breakdown(records.iter().map(|r| r.ext.to_lowercase()));
+        stats.genres = breakdown(
+            records
+                .iter()
+                .filter(|r| !is_missing(&r.genre))
+                .map(|r| r.genre.clone()),
+        );
+        stats.bitrates = breakdown(records.iter().map(|r| bitrate_bucket(r.bitrate)));
+        stats.sample_rates = breakdown(records.iter().map(|r| {
+            r.sample_rate
+                .map_or_else(|| "unknown".to_string(), |hz| format!("{hz} Hz"))
+        }));
+        stats
+    }
+
+    /// Label and value pairs, with an empty value for section headings. Used for the text
+    /// report and the stats popup alike.
+    pub fn rows(&self) -> Vec<(String, String)> {
+        let mut rows = vec![
+            ("Tracks".to_string(), self.tracks.to_string()),
+            ("Artists".to_string(), self.artists.to_string()),
+            ("Albums".to_string(), self.albums.to_string()),
+            ("Total duration".to_string(), format_duration(self.duration)),
+            ("Total size".to_string(), format_size(self.size)),
+        ];
+
+        let mut section = |title: &str, counts: &[Count], limit: usize| {
+            rows.push((String::new(), String::new()));
+            rows.push((title.to_string(), String::new()));
+            for c in counts.iter().take(limit) {
+                rows.push((format!("  {}", c.name), c.count.to_string()));
+            }
+            if counts.len() > limit {
+                rows.push((format!("  ({} more)", counts.len() - limit), String::new()));
+            }
+        };
+        section("Formats", &self.formats, usize::MAX);
+        section("Genres", &self.genres, TEXT_GENRE_LIMIT);
+        section("Bitrates", &self.bitrates, usize::MAX);
+        section("Sample rates", &self.sample_rates, usize::MAX);
+
+        let m = &self.missing;
+        rows.push((String::new(), String::new()));
+        rows.push(("Tracks missing".to_string(), String::new()));
+        for (label, count) in [
+            ("artist", m.artist),
+            ("title", m.title),
+            ("album", m.album),
+            ("genre", m.genre),
+            ("year", m.year),
+            ("track number", m.track_number),
+            ("cover art", m.cover),
+            ("lyrics", m.lyrics),
+        ] {
+            rows.push((format!("  {label}"), count.to_string()));
+        }
+        rows
+    }
+}
+
+impl fmt::Display for LibraryStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.rows();
+        let width = rows
+            .iter()
+            .map(|(l, _)| l.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (label, value) in rows {
+            if value.is_empty() {
+                writeln!(f, "{label}")?;
+            } else {
+                writeln!(f, "{label:<width$}  {value:>8}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn is_missing(value: &str) -> bool {
+    PLACEHOLDERS.contains(&value.trim())
+}
+
+fn has_image(directory: &str) -> bool {
+    cover::find_in_dir(Path::new(directory)).is_some()
+}
+
+/// Count occurrences, most common first
+fn breakdown<I: Iterator<Item = String>>(values: I) -> Vec<Count> {
+    let mut map: HashMap<String, usize> = HashMap::new();
+    for v in values {
+        *map.entry(v).or_default() += 1;
+    }
+    let mut counts: Vec<Count> = map
+        .into_iter()
+        .map(|(name, count)| Count { name, count })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    counts
+}
+
+fn bitrate_bucket(bitrate: Option<u32>) -> String {
+    match bitrate {
+        None | Some(0) => "unknown",
+        Some(b) if b < 128 => "< 128 kbps",
+        Some(b) if b < 192 => "128 - 191 kbps",
+        Some(b) if b < 256 => "192 - 255 kbps",
+        Some(b) if b < 320 => "256 - 319 kbps",
+        Some(b) if b < 500 => "320 - 499 kbps",
+        Some(_) => ">= 500 kbps",
+    }
+    .to_string()
+}
+
+fn format_duration(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86400, secs % 86400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else {
+        format!("{hours}h {minutes}m")
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.2} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_duration, format_size, LibraryStats};
+    use crate::sqlite::TrackForDB;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn record(artist: &str, title: &str, album: &str, ext: &str, bitrate: u32) -> TrackForDB {
+        TrackForDB {
+            id: 0,
+            artist: artist.to_string(),
+            title: title.to_string(),
+            album: album.to_string(),
+            genre: "no type".to_string(),
+            file: format!("/nonexistent/{title}.{ext}"),
+            duration: Duration::from_secs(200),
+            name: format!("01 - {title}.{ext}"),
+            ext: ext.to_string(),
+            directory: "/nonexistent".to_string(),
+            last_modified: String::new(),
+            last_position: Duration::from_secs(0),
+            album_artist: artist.to_string(),
+            year: None,
+            track_number: Some(1),
+            disc_number: None,
+            composer: String::new(),
+            comment: String::new(),
+            bitrate: Some(bitrate),
+            sample_rate: Some(44100),
+            file_size: 1024,
+            has_picture: true,
+            has_lyrics: false,
+        }
+    }
+
+    #[test]
+    fn test_from_records() {
+        let mut records = vec![
+            record("Adele", "Hello", "25", "mp3", 320),
+            record("Adele", "Skyfall", "Skyfall", "MP3", 256),
+            record("Unknown Artist", "track01", "empty", "flac", 900),
+        ];
+        records[2].name = "track01.flac".to_string();
+        let stats = LibraryStats::from_records(&records);
+        assert_eq!(stats.tracks, 3);
+        assert_eq!(stats.artists, 1);
+        assert_eq!(stats.albums, 2);
+        assert_eq!(stats.size, 3072);
+        assert_eq!(stats.formats[0].name, "mp3");
+        assert_eq!(stats.formats[0].count, 2);
+        assert_eq!(stats.missing.artist, 1);
+        // "track01" is just the file name
+        assert_eq!(stats.missing.title, 1);
+        assert_eq!(stats.missing.genre, 3);
+        assert_eq!(stats.missing.cover, 0);
+        assert_eq!(stats.missing.lyrics, 3);
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format_duration(90061), "1d 1h 1m");
+        assert_eq!(format_duration(3660), "1h 1m");
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024 / 2), "1.50 GiB");
+    }
+}
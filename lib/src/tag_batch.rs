@@ -0,0 +1,284 @@
+/**
+ * MIT License
+ *
+ * termusic - Copyright (c) 2021 Larry Hao
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+// editing the tags of several tracks at once in the tag editor
+use crate::track::Track;
+use std::sync::mpsc::Sender;
+use std::thread;
+
+/// Shown for a field the tracks disagree on. Left as is, the field is not touched.
+pub const KEEP: &str = "<keep>";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagField {
+    Artist,
+    Title,
+    Album,
+    Genre,
+    AlbumArtist,
+    Year,
+    Track,
+    Disc,
+    Composer,
+    Comment,
+}
+
+impl TagField {
+    pub const ALL: [Self; 10] = [
+        Self::Artist,
+        Self::Title,
+        Self::Album,
+        Self::Genre,
+        Self::AlbumArtist,
+        Self::Year,
+        Self::Track,
+        Self::Disc,
+        Self::Composer,
+        Self::Comment,
+    ];
+
+    pub fn get(self, track: &Track) -> Option<String> {
+        match self {
+            Self::Artist => track.artist().map(str::to_string),
+            Self::Title => track.title().map(str::to_string),
+            Self::Album => track.album().map(str::to_string),
+            Self::Genre => track.genre().map(str::to_string),
+            Self::AlbumArtist => track.album_artist().map(str::to_string),
+            Self::Year => track.year().map(|n| n.to_string()),
+            Self::Track => track.track_number().map(|n| n.to_string()),
+            Self::Disc => track.disc_number().map(|n| n.to_string()),
+            Self::Composer => track.composer().map(str::to_string),
+            Self::Comment => track.comment().map(str::to_string),
+        }
+    }
+
+    /// Numbers that do not parse clear the field, like in the single track editor.
+    pub fn set(self, track: &mut Track, value: &str) {
+        match self {
+            Self::Artist => track.set_artist(value),
+            Self::Title => track.set_title(value),
+            Self::Album => track.set_album(value),
+            Self::Genre => track.set_genre(value),
+            Self::AlbumArtist => track.set_album_artist(value),
+            Self::Year => track.set_year(value.trim().parse().ok()),
+            Self::Track => track.set_track_number(value.trim().parse().ok()),
+            Self::Disc => track.set_disc_number(value.trim().parse().ok()),
+            Self::Composer => track.set_composer(value),
+            Self::Comment => track.set_comment(value),
+        }
+    }
+
+    /// The value all `tracks` have, or `KEEP` if they differ
+    pub fn common_value(self, tracks: &[Track]) -> String {
+        let mut values = tracks.iter().map(|t| self.get(t));
+        let first = values.next().flatten();
+        if values.all(|v| v == first) {
+            first.unwrap_or_default()
+        } else {
+            KEEP.to_string()
+        }
+    }
+}
+
+/// The `values` edited away from what `tracks` have in common. Fields still showing `KEEP`
+/// are left out too.
+pub fn changed_fields(tracks: &[Track], values: &[(TagField, String)]) -> Vec<(TagField, String)> {
+    values
+        .iter()
+        .filter(|(field, value)| value != KEEP && *value != field.common_value(tracks))
+        .cloned()
+        .collect()
+}
+
+pub enum BatchSaveState {
+    /// Number of files done so far
+    Progress(usize),
+    /// File of each written track with the track as read back, then the file and error of
+    /// every one that failed
+    Finish(Vec<(String, Track)>, Vec<(String, String)>),
+}
+
+/// Apply `changes` to all `tracks` and write them in the background. A file that fails does
+/// not stop the others.
+pub fn save_batch(tracks: Vec<Track>, changes: &[(TagField, String)], tx: Sender<BatchSaveState>) {
+    save_each(
+        tracks.into_iter().map(|t| (t, changes.to_vec())).collect(),
+        tx,
+    );
+}
+
+/// Like `save_batch`, with changes of its own for every track. Tracks without changes are
+/// not written.
+pub fn save_each(tracks: Vec<(Track, Vec<(TagField, String)>)>, tx: Sender<BatchSaveState>) {
+    let tracks = tracks
+        .into_iter()
+        .map(|(mut track, changes)| {
+            for (field, value) in &changes {
+                field.set(&mut track, value);
+            }
+            (!changes.is_empty()).then_some(track)
+        })
+        .collect();
+    write_all(tracks, tx);
+}
+
+/// Write `tracks` as they are in the background, like `save_batch`
+pub fn save_tracks(tracks: Vec<Track>, tx: Sender<BatchSaveState>) {
+    write_all(tracks.into_iter().map(Some).collect(), tx);
+}
+
+/// Skipped tracks are `None`, counted as done
+fn write_all(tracks: Vec<Option<Track>>, tx: Sender<BatchSaveState>) {
+    thread::spawn(move || {
+        let mut saved = vec![];
+        let mut errors = vec![];
+        for (done, track) in tracks.into_iter().enumerate() {
+            if let Some(mut track) = track {
+                let file = track.file().unwrap_or_default().to_string();
+                // the files keep their names, which the tags of several could share
+                match track.write_tag() {
+                    // read back, so the database gets the new modification time as well
+                    Ok(()) => {
+                        let read_back = Track::read_from_path(&file, true).unwrap_or(track);
+                        saved.push((file, read_back));
+                    }
+                    Err(e) => errors.push((file, e.to_string())),
+                }
+            }
+            tx.send(BatchSaveState::Progress(done + 1)).ok();
+        }
+        tx.send(BatchSaveState::Finish(saved, errors)).ok();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{changed_fields, save_batch, save_each, BatchSaveState, TagField, KEEP};
+    use crate::track::Track;
+    use pretty_assertions::assert_eq;
+    use std::path::Path;
+    use std::sync::mpsc;
+
+    /// A short silent 16 bit mono wav file without tags
+    fn write_wav(path: &Path) {
+        let data_len: u32 = 800;
+        let mut wav = vec![];
+        wav.extend(b"RIFF");
+        wav.extend((36 + data_len).to_le_bytes());
+        wav.extend(b"WAVEfmt ");
+        wav.extend(16_u32.to_le_bytes());
+        wav.extend(1_u16.to_le_bytes());
+        wav.extend(1_u16.to_le_bytes());
+        wav.extend(8000_u32.to_le_bytes());
+        wav.extend(16000_u32.to_le_bytes());
+        wav.extend(2_u16.to_le_bytes());
+        wav.extend(16_u16.to_le_bytes());
+        wav.extend(b"data");
+        wav.extend(data_len.to_le_bytes());
+        wav.resize(wav.len() + data_len as usize, 0);
+        std::fs::write(path, wav).unwrap();
+    }
+
+    #[test]
+    fn test_common_value() {
+        let mut tracks = vec![Track::new_radio("a"), Track::new_radio("b")];
+        for (i, track) in tracks.iter_mut().enumerate() {
+            TagField::Album.set(track, "Abbey Road");
+            TagField::Track.set(track, &(i + 1).to_string());
+        }
+        assert_eq!(TagField::Album.common_value(&tracks), "Abbey Road");
+        assert_eq!(TagField::Track.common_value(&tracks), KEEP);
+        assert_eq!(TagField::Year.common_value(&tracks), "");
+
+        let changes = changed_fields(
+            &tracks,
+            &[
+                (TagField::Album, "Let It Be".to_string()),
+                (TagField::Track, KEEP.to_string()),
+                (TagField::Year, String::new()),
+            ],
+        );
+        assert_eq!(changes, vec![(TagField::Album, "Let It Be".to_string())]);
+    }
+
+    /// Files written and the errors of a batch save
+    fn finish(rx: &mpsc::Receiver<BatchSaveState>) -> (Vec<String>, Vec<(String, String)>) {
+        loop {
+            if let BatchSaveState::Finish(saved, errors) = rx.recv().unwrap() {
+                return (saved.into_iter().map(|(file, _)| file).collect(), errors);
+            }
+        }
+    }
+
+    #[test]
+    fn test_save_batch_keeps_names() {
+        let dir = std::env::temp_dir().join(format!("termusic-batch-{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        // without titles, both would be renamed to the same name
+        let files = [dir.join("one.wav"), dir.join("two.wav")];
+        let tracks = files
+            .iter()
+            .map(|file| {
+                write_wav(file);
+                Track::read_from_path(file, false).unwrap()
+            })
+            .collect();
+
+        let (tx, rx) = mpsc::channel();
+        save_batch(tracks, &[(TagField::Album, "Abbey Road".to_string())], tx);
+        let (saved, errors) = finish(&rx);
+        assert!(errors.is_empty());
+        assert_eq!(saved.len(), 2);
+        for file in &files {
+            let track = Track::read_from_path(file, false).unwrap();
+            assert_eq!(track.album(), Some("Abbey Road"));
+        }
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_save_each_skips_unchanged() {
+        let dir = std::env::temp_dir().join(format!("termusic-each-{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let files = [dir.join("one.wav"), dir.join("two.wav")];
+        let tracks = files
+            .iter()
+            .zip([vec![(TagField::Title, "Come Together".to_string())], vec![]])
+            .map(|(file, changes)| {
+                write_wav(file);
+                (Track::read_from_path(file, false).unwrap(), changes)
+            })
+            .collect();
+        let untouched = std::fs::read(&files[1]).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        save_each(tracks, tx);
+        let (saved, errors) = finish(&rx);
+        assert!(errors.is_empty());
+        assert_eq!(saved, vec![files[0].to_string_lossy().to_string()]);
+        assert_eq!(std::fs::read(&files[1]).unwrap(), untouched);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
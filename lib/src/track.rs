@@ -1,3 +1,4 @@
+use crate::cover;
 use crate::podcast::Episode;
 /**
  * MIT License
@@ -26,7 +27,9 @@ use crate::songtag::lrc::Lyric;
 use crate::utils::get_parent_folder;
 use anyhow::{bail, Result};
 use id3::frame::Lyrics;
-use lofty::id3::v2::{Frame, FrameFlags, FrameValue, Id3v2Tag, UnsynchronizedTextFrame};
+use lofty::id3::v2::{
+    Frame, FrameFlags, FrameValue, Id3v2Tag, TextInformationFrame, UnsynchronizedTextFrame,
+};
 use lofty::{
     mpeg::MpegFile, Accessor, AudioFile, FileType, ItemKey, ItemValue, Picture, PictureType,
     TagExt, TagItem, TaggedFileExt, TextEncoding,
@@ -36,7 +39,7 @@ use std::ffi::OsStr;
 use std::fs::rename;
 use std::fs::File;
 use std::io::BufReader;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::{Duration, SystemTime};
 
@@ -46,32 +49,43 @@ pub struct Track {
     artist: Option<String>,
     /// Album of the song
     album: Option<String>,
+    /// Artist of the whole album, used to keep compilations together
+    album_artist: Option<String>,
     /// Title of the song
     title: Option<String>,
     /// File path to the song
     file: Option<String>,
     /// Duration of the song
     duration: Duration,
+    /// Audio bitrate in kbps
+    bitrate: Option<u32>,
+    /// Sample rate in Hz
+    sample_rate: Option<u32>,
+    /// Whether the tag has a picture or lyrics, known even when only read for the database
+    has_embedded_picture: bool,
+    has_embedded_lyrics: bool,
     /// Name of the song
     name: Option<String>,
     /// Extension of the song
     ext: Option<String>,
     directory: Option<String>,
     pub last_modified: SystemTime,
-    /// USLT lyrics
+    /// USLT lyrics, followed by those read from `.lrc` files
     lyric_frames: Vec<Lyrics>,
+    /// Path of the `.lrc` file for each entry of `lyric_frames`, `None` if embedded
+    lyric_sidecars: Vec<Option<PathBuf>>,
     lyric_selected_index: usize,
     parsed_lyric: Option<Lyric>,
     picture: Option<Picture>,
     album_photo: Option<String>,
     file_type: Option<FileType>,
-    // Date
-    // Track
+    year: Option<u32>,
+    track_number: Option<u32>,
     genre: Option<String>,
-    // Composer
+    composer: Option<String>,
     // Performer
-    // Disc
-    // Comment
+    disc_number: Option<u32>,
+    comment: Option<String>,
     pub media_type: Option<MediaType>,
     pub podcast_localfile: Option<String>,
 }
@@ -103,21 +117,32 @@ impl Track {
         Self {
             artist: Some("Episode".to_string()),
             album: None,
+            album_artist: None,
             // album: Some(ep.description.clone()),
             title: Some(ep.title.clone()),
             file: Some(ep.url.clone()),
             duration: Duration::from_secs(ep.duration.unwrap_or(0) as u64),
+            bitrate: None,
+            sample_rate: None,
+            has_embedded_picture: false,
+            has_embedded_lyrics: false,
             name: None,
             ext: None,
             directory: None,
             last_modified: SystemTime::now(),
             lyric_frames,
+            lyric_sidecars: Vec::new(),
             lyric_selected_index: 0,
             parsed_lyric: None,
             picture: None,
             album_photo: ep.image_url.clone(),
             file_type: None,
+            year: None,
+            track_number: None,
             genre: None,
+            composer: None,
+            disc_number: None,
+            comment: None,
             media_type: Some(MediaType::Podcast),
             podcast_localfile,
         }
@@ -134,6 +159,8 @@ impl Track {
             // We can at most get the duration and file type at this point
             let properties = tagged_file.properties();
             song.duration = properties.duration();
+            song.bitrate = properties.audio_bitrate();
+            song.sample_rate = properties.sample_rate();
             song.file_type = Some(tagged_file.file_type());
 
             if let Some(tag) = tagged_file.primary_tag_mut() {
@@ -146,7 +173,15 @@ impl Track {
                 song.album = tag.album().map(std::borrow::Cow::into_owned);
                 song.title = tag.title().map(std::borrow::Cow::into_owned);
                 song.genre = tag.genre().map(std::borrow::Cow::into_owned);
+                song.album_artist = tag.get_string(&ItemKey::AlbumArtist).map(String::from);
+                song.composer = tag.get_string(&ItemKey::Composer).map(String::from);
+                song.comment = tag.comment().map(std::borrow::Cow::into_owned);
+                song.year = tag.year();
+                song.track_number = tag.track();
+                song.disc_number = tag.disk();
                 song.media_type = Some(MediaType::Music);
+                song.has_embedded_picture = !tag.pictures().is_empty();
+                song.has_embedded_lyrics = tag.get_string(&ItemKey::Lyrics).is_some();
 
                 if for_db {
                     return Ok(song);
@@ -179,10 +214,7 @@ impl Track {
                         create_lyrics(tag, &mut lyric_frames);
                     }
                 };
-                song.parsed_lyric = lyric_frames
-                    .first()
-                    .map(|lf| Lyric::from_str(&lf.text).ok())
-                    .and_then(|pl| pl);
+                song.lyric_sidecars = vec![None; lyric_frames.len()];
                 song.lyric_frames = lyric_frames;
 
                 // Get the picture (not necessarily the front cover)
@@ -200,18 +232,13 @@ impl Track {
         }
 
         let parent_folder = get_parent_folder(&path.to_string_lossy());
-
-        if let Ok(files) = std::fs::read_dir(parent_folder) {
-            for f in files.flatten() {
-                let path = f.path();
-                if let Some(extension) = path.extension() {
-                    if extension == "jpg" || extension == "png" {
-                        song.album_photo = Some(path.to_string_lossy().to_string());
-                    }
-                }
-            }
+        if !for_db {
+            song.load_lyric_dir(&parent_folder);
         }
 
+        song.album_photo = cover::find_in_dir(Path::new(&parent_folder))
+            .map(|path| path.to_string_lossy().to_string());
+
         Ok(song)
     }
 
@@ -256,14 +283,25 @@ impl Track {
             file,
             directory,
             duration,
+            bitrate: None,
+            sample_rate: None,
+            has_embedded_picture: false,
+            has_embedded_lyrics: false,
             name,
             parsed_lyric,
             lyric_frames,
+            lyric_sidecars: Vec::new(),
             lyric_selected_index: 0,
             picture,
             album_photo,
             last_modified,
+            album_artist: None,
+            year: None,
+            track_number: None,
             genre,
+            composer: None,
+            disc_number: None,
+            comment: None,
             media_type: Some(MediaType::Music),
             podcast_localfile: None,
         }
@@ -274,7 +312,108 @@ impl Track {
             lyric.adjust_offset(time_pos, offset);
             let text = lyric.as_lrc_text();
             self.set_lyric(&text, "Adjusted");
-            self.save_tag()?;
+            self.save_lyric()?;
+        }
+        Ok(())
+    }
+
+    /// Add the `song.lrc` and `song.<lang>.lrc` files found in `dir`. The folder of the song
+    /// is always searched by `read_from_path`, this is for an extra lyrics folder.
+    pub fn load_lyric_dir(&mut self, dir: &str) {
+        let Some(stem) = self.file().map(file_stem) else {
+            return;
+        };
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut sidecars: Vec<(PathBuf, String)> = entries
+            .flatten()
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                let lang = lyric_sidecar_lang(&stem, &name)?.to_string();
+                Some((e.path(), lang))
+            })
+            .filter(|(path, _)| !self.lyric_sidecars.contains(&Some(path.clone())))
+            .collect();
+        sidecars.sort();
+
+        for (path, lang) in sidecars {
+            let Ok(text) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            self.lyric_frames.push(Lyrics {
+                lang: if lang.len() == 3 {
+                    lang
+                } else {
+                    "eng".to_string()
+                },
+                description: path
+                    .file_name()
+                    .map_or_else(String::new, |n| n.to_string_lossy().to_string()),
+                text,
+            });
+            self.lyric_sidecars.push(Some(path));
+        }
+        if self.parsed_lyric.is_none() {
+            self.parsed_lyric = self
+                .lyric_frames
+                .get(self.lyric_selected_index)
+                .and_then(|lf| Lyric::from_str(&lf.text).ok());
+        }
+    }
+
+    /// Put `lyric_str` in `song.lrc`, under `dir` or next to the song if `dir` is empty,
+    /// instead of the tag. Replaces the lyrics already read from that file.
+    pub fn set_lyric_sidecar(&mut self, lyric_str: &str, dir: &str) {
+        let Some(file) = self.file() else {
+            return;
+        };
+        let dir = if dir.is_empty() {
+            get_parent_folder(file)
+        } else {
+            dir.to_string()
+        };
+        let path = Path::new(&dir).join(format!("{}.lrc", file_stem(file)));
+        if let Some(index) = self
+            .lyric_sidecars
+            .iter()
+            .position(|s| s.as_ref() == Some(&path))
+        {
+            self.lyric_frames[index].text = lyric_str.to_string();
+            self.lyric_selected_index = index;
+        } else {
+            self.lyric_frames.push(Lyrics {
+                lang: "eng".to_string(),
+                description: path
+                    .file_name()
+                    .map_or_else(String::new, |n| n.to_string_lossy().to_string()),
+                text: lyric_str.to_string(),
+            });
+            self.lyric_sidecars.push(Some(path));
+            self.lyric_selected_index = self.lyric_frames.len() - 1;
+        }
+    }
+
+    pub fn lyric_selected_is_sidecar(&self) -> bool {
+        matches!(
+            self.lyric_sidecars.get(self.lyric_selected_index),
+            Some(Some(_))
+        )
+    }
+
+    /// Save the selected lyrics. Only the `.lrc` file is written if they came from one.
+    pub fn save_lyric(&mut self) -> Result<()> {
+        if self.lyric_selected_is_sidecar() {
+            return self.save_lyric_sidecars();
+        }
+        self.save_tag()
+    }
+
+    pub fn save_lyric_sidecars(&self) -> Result<()> {
+        for (frame, sidecar) in self.lyric_frames.iter().zip(&self.lyric_sidecars) {
+            if let Some(path) = sidecar {
+                std::fs::write(path, &frame.text)?;
+            }
         }
         Ok(())
     }
@@ -307,8 +446,16 @@ impl Track {
         self.parsed_lyric = pl;
     }
 
-    pub fn lyric_frames_remove_selected(&mut self) {
+    /// Remove the selected lyrics. A `.lrc` file they came from is deleted.
+    pub fn lyric_frames_remove_selected(&mut self) -> Result<()> {
+        if self.lyric_selected_index >= self.lyric_frames.len() {
+            return Ok(());
+        }
         self.lyric_frames.remove(self.lyric_selected_index);
+        if let Some(path) = self.lyric_sidecars.remove(self.lyric_selected_index) {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
     }
 
     pub fn set_lyric_selected_index(&mut self, index: usize) {
@@ -383,6 +530,54 @@ impl Track {
         self.genre = Some(genre.to_string());
     }
 
+    pub fn album_artist(&self) -> Option<&str> {
+        self.album_artist.as_deref()
+    }
+
+    pub fn set_album_artist(&mut self, album_artist: &str) {
+        self.album_artist = filter_empty(album_artist);
+    }
+
+    pub const fn year(&self) -> Option<u32> {
+        self.year
+    }
+
+    pub fn set_year(&mut self, year: Option<u32>) {
+        self.year = year;
+    }
+
+    pub const fn track_number(&self) -> Option<u32> {
+        self.track_number
+    }
+
+    pub fn set_track_number(&mut self, track_number: Option<u32>) {
+        self.track_number = track_number;
+    }
+
+    pub const fn disc_number(&self) -> Option<u32> {
+        self.disc_number
+    }
+
+    pub fn set_disc_number(&mut self, disc_number: Option<u32>) {
+        self.disc_number = disc_number;
+    }
+
+    pub fn composer(&self) -> Option<&str> {
+        self.composer.as_deref()
+    }
+
+    pub fn set_composer(&mut self, composer: &str) {
+        self.composer = filter_empty(composer);
+    }
+
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    pub fn set_comment(&mut self, comment: &str) {
+        self.comment = filter_empty(comment);
+    }
+
     /// Optionally return the title of the song
     /// If `None` it wasn't able to read the tags
     pub fn title(&self) -> Option<&str> {
@@ -409,6 +604,22 @@ impl Track {
         self.duration
     }
 
+    pub const fn bitrate(&self) -> Option<u32> {
+        self.bitrate
+    }
+
+    pub const fn sample_rate(&self) -> Option<u32> {
+        self.sample_rate
+    }
+
+    pub const fn has_embedded_picture(&self) -> bool {
+        self.has_embedded_picture
+    }
+
+    pub const fn has_embedded_lyrics(&self) -> bool {
+        self.has_embedded_lyrics
+    }
+
     pub fn duration_formatted(&self) -> String {
         Self::duration_formatted_short(&self.duration)
     }
@@ -430,14 +641,36 @@ impl Track {
     }
 
     pub fn save_tag(&mut self) -> Result<()> {
+        self.write_tag()?;
+        self.rename_by_tag()?;
+        Ok(())
+    }
+
+    /// Write the tags and lyric files, leaving the file name as it is. Saving several
+    /// tracks at once uses this, as their names after the tags could collide.
+    pub fn write_tag(&mut self) -> Result<()> {
         match self.file_type {
             Some(FileType::Mpeg) => {
                 if let Some(file_path) = self.file() {
                     let mut tag = Id3v2Tag::default();
                     self.update_tag(&mut tag);
+                    for (id, value) in [("TPE2", self.album_artist()), ("TCOM", self.composer())] {
+                        if let Some(value) = value {
+                            if let Ok(frame) = Frame::new(
+                                id,
+                                FrameValue::Text(TextInformationFrame {
+                                    encoding: TextEncoding::UTF8,
+                                    value: value.to_string(),
+                                }),
+                                FrameFlags::default(),
+                            ) {
+                                tag.insert(frame);
+                            }
+                        }
+                    }
 
                     if !self.lyric_frames_is_empty() {
-                        if let Some(lyric_frames) = self.lyric_frames() {
+                        if let Some(lyric_frames) = self.embedded_lyric_frames() {
                             for l in lyric_frames {
                                 if let Ok(l_frame) = Frame::new(
                                     "USLT",
@@ -474,9 +707,15 @@ impl Track {
 
                     let mut tag = lofty::Tag::new(tag_type);
                     self.update_tag(&mut tag);
+                    if let Some(album_artist) = self.album_artist() {
+                        tag.insert_text(ItemKey::AlbumArtist, album_artist.to_string());
+                    }
+                    if let Some(composer) = self.composer() {
+                        tag.insert_text(ItemKey::Composer, composer.to_string());
+                    }
 
                     if !self.lyric_frames_is_empty() {
-                        if let Some(lyric_frames) = self.lyric_frames() {
+                        if let Some(lyric_frames) = self.embedded_lyric_frames() {
                             for l in lyric_frames {
                                 tag.push(TagItem::new(ItemKey::Lyrics, ItemValue::Text(l.text)));
                             }
@@ -492,10 +731,24 @@ impl Track {
             }
         }
 
-        self.rename_by_tag()?;
+        self.save_lyric_sidecars()?;
         Ok(())
     }
 
+    fn embedded_lyric_frames(&self) -> Option<Vec<Lyrics>> {
+        let frames: Vec<Lyrics> = self
+            .lyric_frames
+            .iter()
+            .zip(&self.lyric_sidecars)
+            .filter(|(_, sidecar)| sidecar.is_none())
+            .map(|(frame, _)| frame.clone())
+            .collect();
+        if frames.is_empty() {
+            return None;
+        }
+        Some(frames)
+    }
+
     fn rename_by_tag(&mut self) -> Result<()> {
         if let Some(ext) = self.ext() {
             let new_name = format!(
@@ -510,7 +763,14 @@ impl Track {
                 let p_old: &Path = Path::new(file);
                 if let Some(p_prefix) = p_old.parent() {
                     let p_new = p_prefix.join(new_name_path);
+                    if p_new == p_old {
+                        return Ok(());
+                    }
+                    if p_new.exists() {
+                        bail!("cannot rename to {}, it already exists", p_new.display());
+                    }
                     rename(p_old, &p_new)?;
+                    self.rename_lyric_sidecars(&file_stem(file), &file_stem(&p_new))?;
                     self.file = Some(String::from(p_new.to_string_lossy()));
                 }
             }
@@ -519,28 +779,31 @@ impl Track {
         Ok(())
     }
 
+    /// Keep `.lrc` files named after the song when it is renamed
+    fn rename_lyric_sidecars(&mut self, old_stem: &str, new_stem: &str) -> Result<()> {
+        for path in self.lyric_sidecars.iter_mut().flatten() {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string());
+            let Some(rest) = name.as_deref().and_then(|n| n.strip_prefix(old_stem)) else {
+                continue;
+            };
+            let new_path = path.with_file_name(format!("{new_stem}{rest}"));
+            rename(&*path, &new_path)?;
+            *path = new_path;
+        }
+        Ok(())
+    }
+
     pub fn set_lyric(&mut self, lyric_str: &str, lang_ext: &str) {
-        let mut lyric_frames = self.lyric_frames.clone();
-        match self.lyric_frames.get(self.lyric_selected_index) {
-            Some(lyric_frame) => {
-                lyric_frames.remove(self.lyric_selected_index);
-                lyric_frames.insert(
-                    self.lyric_selected_index,
-                    Lyrics {
-                        text: lyric_str.to_string(),
-                        ..lyric_frame.clone()
-                    },
-                );
-            }
-            None => {
-                lyric_frames.push(Lyrics {
-                    lang: "eng".to_string(),
-                    description: lang_ext.to_string(),
-                    text: lyric_str.to_string(),
-                });
-            }
+        if let Some(lyric_frame) = self.lyric_frames.get_mut(self.lyric_selected_index) {
+            lyric_frame.text = lyric_str.to_string();
+        } else {
+            self.lyric_frames.push(Lyrics {
+                lang: "eng".to_string(),
+                description: lang_ext.to_string(),
+                text: lyric_str.to_string(),
+            });
+            self.lyric_sidecars.push(None);
         }
-        self.lyric_frames = lyric_frames;
     }
 
     pub fn set_photo(&mut self, picture: Picture) {
@@ -560,9 +823,53 @@ impl Track {
 
         tag.set_album(self.album().map_or_else(String::new, str::to_string));
         tag.set_genre(self.genre().map_or_else(String::new, str::to_string));
+
+        if let Some(year) = self.year() {
+            tag.set_year(year);
+        }
+        if let Some(track_number) = self.track_number() {
+            tag.set_track(track_number);
+        }
+        if let Some(disc_number) = self.disc_number() {
+            tag.set_disk(disc_number);
+        }
+        if let Some(comment) = self.comment() {
+            tag.set_comment(comment.to_string());
+        }
     }
 }
 
+pub(crate) fn file_stem<P: AsRef<Path>>(path: P) -> String {
+    path.as_ref()
+        .file_stem()
+        .map_or_else(String::new, |s| s.to_string_lossy().to_string())
+}
+
+/// `Some("")` for `song.lrc`, `Some("en")` for `song.en.lrc`, `None` if `file_name` is not
+/// a lyrics file of the song
+pub(crate) fn lyric_sidecar_lang<'a>(song_stem: &str, file_name: &'a str) -> Option<&'a str> {
+    let (stem, ext) = file_name.rsplit_once('.')?;
+    if !ext.eq_ignore_ascii_case("lrc") {
+        return None;
+    }
+    if stem == song_stem {
+        return Some("");
+    }
+    let lang = stem.strip_prefix(song_stem)?.strip_prefix('.')?;
+    if lang.is_empty() || lang.contains('.') {
+        return None;
+    }
+    Some(lang)
+}
+
+fn filter_empty(s: &str) -> Option<String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+    Some(s.to_string())
+}
+
 fn create_lyrics(tag: &mut lofty::Tag, lyric_frames: &mut Vec<Lyrics>) {
     let lyrics = tag.take(&ItemKey::Lyrics);
     for lyric in lyrics {
@@ -576,3 +883,18 @@ fn create_lyrics(tag: &mut lofty::Tag, lyric_frames: &mut Vec<Lyrics>) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::lyric_sidecar_lang;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_lyric_sidecar_lang() {
+        assert_eq!(lyric_sidecar_lang("01 Song", "01 Song.lrc"), Some(""));
+        assert_eq!(lyric_sidecar_lang("01 Song", "01 Song.en.LRC"), Some("en"));
+        assert_eq!(lyric_sidecar_lang("01 Song", "01 Song.flac"), None);
+        assert_eq!(lyric_sidecar_lang("01 Song", "01 Song 2.lrc"), None);
+        assert_eq!(lyric_sidecar_lang("01 Song", "01 Song.a.b.lrc"), None);
+    }
+}
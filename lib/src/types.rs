@@ -2,12 +2,14 @@ use crate::config::{BindingForEvent, ColorTermusic};
 use crate::invidious::{Instance, YoutubeVideo};
 use crate::podcast::{EpData, PodcastFeed, PodcastNoId};
 use crate::songtag::SongTag;
+use crate::sqlite::TrackForDB;
 use anyhow::{anyhow, Result};
 use image::DynamicImage;
 
 #[derive(Clone, PartialEq, Eq)]
 pub enum Msg {
     // AppClose,
+    Browse(BrowseMsg),
     ConfigEditor(ConfigEditorMsg),
     DataBase(DBMsg),
     DeleteConfirmCloseCancel,
@@ -21,10 +23,13 @@ pub enum Msg {
     LayoutTreeView,
     LayoutDataBase,
     LayoutPodCast,
+    LayoutBrowse,
     Library(LIMsg),
     LyricMessage(LyricMsg),
     LyricCycle,
     LyricAdjustDelay(i64),
+    OrganizePopupCloseCancel,
+    OrganizePopupCloseOk,
     PlayerToggleGapless,
     PlayerTogglePause,
     PlayerVolumeUp,
@@ -33,6 +38,8 @@ pub enum Msg {
     PlayerSpeedDown,
     PlayerSeekForward,
     PlayerSeekBackward,
+    PlayerNextChapter,
+    PlayerPreviousChapter,
     Playlist(PLMsg),
     Podcast(PCMsg),
     QuitPopupCloseCancel,
@@ -44,6 +51,8 @@ pub enum Msg {
     SavePlaylistPopupCloseOk(String),
     SavePlaylistConfirmCloseCancel,
     SavePlaylistConfirmCloseOk(String),
+    StatsPopupShow,
+    StatsPopupClose,
     TagEditor(TEMsg),
     UpdatePhoto,
     YoutubeSearch(YSMsg),
@@ -81,6 +90,9 @@ pub enum DLMsg {
 pub enum LyricMsg {
     LyricTextAreaBlurUp,
     LyricTextAreaBlurDown,
+    LyricToggleScroll,
+    /// Row of the lyric panel to seek playback to
+    LyricSeekLine(usize),
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -167,6 +179,8 @@ pub enum ConfigEditorMsg {
 pub enum KFMsg {
     DatabaseAddAllBlurDown,
     DatabaseAddAllBlurUp,
+    DatabaseStatsBlurDown,
+    DatabaseStatsBlurUp,
     GlobalConfigBlurDown,
     GlobalConfigBlurUp,
     GlobalDownBlurDown,
@@ -221,6 +235,20 @@ pub enum KFMsg {
     LibraryDeleteBlurUp,
     LibraryLoadDirBlurDown,
     LibraryLoadDirBlurUp,
+    LibraryOrganizeBlurDown,
+    LibraryOrganizeBlurUp,
+    PodcastFeedPolicyBlurDown,
+    PodcastFeedPolicyBlurUp,
+    PodcastSearchTranscriptsBlurDown,
+    PodcastSearchTranscriptsBlurUp,
+    PodcastQueueFeedBlurDown,
+    PodcastQueueFeedBlurUp,
+    PodcastQueueShowBlurDown,
+    PodcastQueueShowBlurUp,
+    PodcastFeedFilterBlurDown,
+    PodcastFeedFilterBlurUp,
+    PodcastDownloadFailedBlurDown,
+    PodcastDownloadFailedBlurUp,
     LibraryPasteBlurDown,
     LibraryPasteBlurUp,
     LibrarySearchBlurDown,
@@ -259,6 +287,16 @@ pub enum KFMsg {
     LibraryRemoveRootBlurUp,
     GlobalLayoutPodcastBlurDown,
     GlobalLayoutPodcastBlurUp,
+    GlobalLayoutBrowseBlurDown,
+    GlobalLayoutBrowseBlurUp,
+    GlobalLyricToggleScrollBlurDown,
+    GlobalLyricToggleScrollBlurUp,
+    GlobalLyricSeekLineBlurDown,
+    GlobalLyricSeekLineBlurUp,
+    GlobalPlayerNextChapterBlurDown,
+    GlobalPlayerNextChapterBlurUp,
+    GlobalPlayerPreviousChapterBlurDown,
+    GlobalPlayerPreviousChapterBlurUp,
     GlobalXywhMoveLeftBlurDown,
     GlobalXywhMoveLeftBlurUp,
     GlobalXywhMoveRightBlurDown,
@@ -303,6 +341,7 @@ pub enum LIMsg {
     SwitchRoot,
     AddRoot,
     RemoveRoot,
+    Organize(String),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -311,12 +350,30 @@ pub enum DBMsg {
     AddPlaylist(usize),
     CriteriaBlurDown,
     CriteriaBlurUp,
+    DeleteTrack(usize),
+    DuplicatesFound(Vec<Vec<TrackForDB>>),
     SearchResult(usize),
     SearchResultBlurDown,
     SearchResultBlurUp,
     SearchTrack(usize),
     SearchTracksBlurDown,
     SearchTracksBlurUp,
+    TagEditorRunAll,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BrowseMsg {
+    AddAllToPlaylist,
+    AddPlaylist(usize),
+    AlbumHighlighted(usize),
+    AlbumSelected(usize),
+    AlbumsBlurDown,
+    AlbumsBlurUp,
+    ArtistSelected(usize),
+    ArtistsBlurDown,
+    ArtistsBlurUp,
+    TracksBlurDown,
+    TracksBlurUp,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -328,11 +385,33 @@ pub enum PCMsg {
     PodcastAddPopupShow,
     PodcastAddPopupCloseOk(String),
     PodcastAddPopupCloseCancel,
+    PolicyPopupShow,
+    PolicyPopupCloseOk(String),
+    PolicyPopupCloseCancel,
+    FilterPopupShow,
+    FilterPopupCloseOk(String),
+    FilterPopupCloseCancel,
+    QueuePopupShow,
+    QueuePopupCloseOk(String),
+    QueuePopupCloseCancel,
+    QueueTableShow,
+    QueueTableCloseCancel,
+    QueuePlay(usize),
+    QueueRemove(usize),
+    QueueClear,
+    FailedTableShow,
+    FailedTableCloseCancel,
+    FailedRetry(usize),
+    FailedRemove(usize),
+    FailedClear,
     SyncData((i64, PodcastNoId)),
     NewData(PodcastNoId),
     Error(String, PodcastFeed),
     PodcastSelected(usize),
     DescriptionUpdate,
+    NotesSelect(usize),
+    NotesCopyLink(usize),
+    TranscriptFetched(i64),
     EpisodeAdd(usize),
     EpisodeMarkPlayed(usize),
     EpisodeMarkAllPlayed,
@@ -342,6 +421,8 @@ pub enum PCMsg {
     EpisodeDownload(usize),
     DLStart(EpData),
     DLComplete(EpData),
+    /// Downloaded, but with no size or checksum to tell it is all there
+    DLUnchecked(EpData),
     DLResponseError(EpData),
     DLFileCreateError(EpData),
     DLFileWriteError(EpData),
@@ -380,17 +461,21 @@ pub enum GSMsg {
     PopupShowDatabase,
     PopupShowLibrary,
     PopupShowPlaylist,
+    PopupShowTranscript,
     PopupCloseCancel,
     InputBlur,
     PopupUpdateDatabase(String),
     PopupUpdateLibrary(String),
     PopupUpdatePlaylist(String),
+    PopupUpdateTranscript(String),
     TableBlur,
     PopupCloseDatabaseAddPlaylist,
     PopupCloseLibraryAddPlaylist,
     PopupCloseOkLibraryLocate,
     PopupClosePlaylistPlaySelected,
     PopupCloseOkPlaylistLocate,
+    PopupCloseOkTranscriptLocate,
+    PopupCloseTranscriptAddPlaylist,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -408,14 +493,49 @@ pub enum TEMsg {
     TagEditorRun(String),
     TagEditorClose(Option<String>),
     TECounterDeleteOk,
+    TECover(CoverMsg),
     TEDownload(usize),
     TEEmbed(usize),
+    TEFilenameTags(FilenameTagsMsg),
     TEFocus(TFMsg),
+    TELyricSync(LyricSyncMsg),
+    TELyricSyncStart,
     TERename,
     TESearch,
     TESelectLyricOk(usize),
 }
 
+/// Embedding and extracting covers in the tag editor
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CoverMsg {
+    Show,
+    Embed,
+    Extract,
+    Cancel,
+}
+
+/// Reading tags out of file names in the tag editor
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilenameTagsMsg {
+    Show,
+    Preview,
+    FocusInput,
+    FocusTable,
+    Apply,
+    Cancel,
+}
+
+/// Timing plain text lyrics in the tag editor, by row of the line list
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LyricSyncMsg {
+    Stamp(usize),
+    Nudge(usize, i64),
+    Clear(usize),
+    Preview(usize),
+    Save,
+    Cancel,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TFMsg {
     CounterDeleteBlurDown,
@@ -428,6 +548,18 @@ pub enum TFMsg {
     InputAlbumBlurUp,
     InputGenreBlurDown,
     InputGenreBlurUp,
+    InputAlbumArtistBlurDown,
+    InputAlbumArtistBlurUp,
+    InputYearBlurDown,
+    InputYearBlurUp,
+    InputTrackBlurDown,
+    InputTrackBlurUp,
+    InputDiscBlurDown,
+    InputDiscBlurUp,
+    InputComposerBlurDown,
+    InputComposerBlurUp,
+    InputCommentBlurDown,
+    InputCommentBlurUp,
     SelectLyricBlurDown,
     SelectLyricBlurUp,
     TableLyricOptionsBlurDown,
@@ -438,6 +570,9 @@ pub enum TFMsg {
 
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 pub enum Id {
+    BrowseAlbums,
+    BrowseArtists,
+    BrowseTracks,
     ConfigEditor(IdConfigEditor),
     DBListCriteria,
     DBListSearchResult,
@@ -455,9 +590,15 @@ pub enum Id {
     Library,
     Lyric,
     MessagePopup,
+    OrganizePopup,
     Playlist,
     Podcast,
     PodcastAddPopup,
+    PodcastPolicyPopup,
+    PodcastFilterPopup,
+    PodcastQueuePopup,
+    PodcastQueueTablePopup,
+    PodcastFailedTablePopup,
     PodcastSearchTablePopup,
     FeedDeleteConfirmRadioPopup,
     FeedDeleteConfirmInputPopup,
@@ -466,6 +607,7 @@ pub enum Id {
     SavePlaylistPopup,
     SavePlaylistLabel,
     SavePlaylistConfirm,
+    StatsPopup,
     TagEditor(IdTagEditor),
     YoutubeSearchInputPopup,
     YoutubeSearchTablePopup,
@@ -479,7 +621,17 @@ pub enum IdTagEditor {
     InputTitle,
     InputAlbum,
     InputGenre,
+    InputAlbumArtist,
+    InputYear,
+    InputTrack,
+    InputDisc,
+    InputComposer,
+    InputComment,
+    InputCoverFile,
+    InputFilenamePattern,
     SelectLyric,
+    ListLyricSync,
+    TableFilenamePreview,
     TableLyricOptions,
     TextareaLyric,
 }
@@ -531,6 +683,7 @@ pub enum IdConfigEditor {
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 pub enum IdKey {
     DatabaseAddAll,
+    DatabaseStats,
     GlobalConfig,
     GlobalDown,
     GlobalGotoBottom,
@@ -558,6 +711,7 @@ pub enum IdKey {
     GlobalSavePlaylist,
     LibraryDelete,
     LibraryLoadDir,
+    LibraryOrganize,
     LibraryPaste,
     LibrarySearch,
     LibrarySearchYoutube,
@@ -577,6 +731,11 @@ pub enum IdKey {
     LibraryAddRoot,
     LibraryRemoveRoot,
     GlobalLayoutPodcast,
+    GlobalLayoutBrowse,
+    GlobalLyricToggleScroll,
+    GlobalLyricSeekLine,
+    GlobalPlayerNextChapter,
+    GlobalPlayerPreviousChapter,
     GlobalXywhMoveLeft,
     GlobalXywhMoveRight,
     GlobalXywhMoveUp,
@@ -590,12 +749,20 @@ pub enum IdKey {
     PodcastEpDeleteFile,
     PodcastDeleteFeed,
     PodcastDeleteAllFeeds,
+    PodcastFeedPolicy,
     PodcastSearchAddFeed,
+    PodcastSearchTranscripts,
+    PodcastQueueFeed,
+    PodcastQueueShow,
+    PodcastFeedFilter,
+    PodcastDownloadFailed,
     PodcastRefreshFeed,
     PodcastRefreshAllFeeds,
 }
 pub enum SearchLyricState {
     Finish(Vec<SongTag>),
+    /// Candidates found by the audio fingerprint, listed before the search results
+    Identified(Vec<SongTag>),
 }
 
 #[derive(Clone, PartialEq)]
@@ -11,6 +11,14 @@ use std::{
     ffi::OsStr,
     process::{Child, Command},
 };
+use symphonia::core::audio::{SampleBuffer, SignalSpec};
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::conv::ConvertibleSample;
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::{MediaSourceStream, MediaSourceStreamOptions};
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
 use tuirealm::props::Color;
 use tuirealm::tui::layout::{Constraint, Direction, Layout, Rect};
 use unicode_segmentation::UnicodeSegmentation;
@@ -473,11 +481,102 @@ pub fn spawn_process<A: IntoIterator<Item = S> + Clone, S: AsRef<OsStr>>(
     cmd.spawn().expect("Error spawning server process.")
 }
 
+/// Program and arguments opening `target` on `os`. Links come from feeds, so on Windows they
+/// are not handed to `cmd`, which would run what follows a `&` in them.
+fn open_command<'a>(os: &str, target: &'a str) -> (&'static str, Vec<&'a str>) {
+    match os {
+        "macos" => ("open", vec![target]),
+        "windows" => ("rundll32", vec!["url.dll,FileProtocolHandler", target]),
+        _ => ("xdg-open", vec![target]),
+    }
+}
+
+/// Open a link or file with the program the desktop uses for it
+pub fn open_with_system(target: &str) -> Result<()> {
+    let (prog, args) = open_command(std::env::consts::OS, target);
+    Command::new(prog)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow!("cannot run {prog}: {e}"))?;
+    Ok(())
+}
+
+/// Put text on the clipboard through the terminal, with the OSC 52 escape most terminals
+/// understand, so it also works over ssh
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    use base64::{engine::general_purpose, Engine as _};
+    use std::io::Write;
+    let mut stdout = std::io::stdout();
+    write!(
+        stdout,
+        "\x1b]52;c;{}\x07",
+        general_purpose::STANDARD.encode(text)
+    )?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Decode the default track of an audio file, handing every decoded packet to `each` as
+/// interleaved samples together with their spec. Packets that fail to decode are skipped.
+pub(crate) fn decode_audio<S: ConvertibleSample>(
+    path: &Path,
+    mut each: impl FnMut(&[S], &SignalSpec),
+) -> Result<()> {
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(OsStr::to_str) {
+        hint.with_extension(ext);
+    }
+    let mss = MediaSourceStream::new(
+        Box::new(std::fs::File::open(path)?),
+        MediaSourceStreamOptions::default(),
+    );
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        mss,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let mut format = probed.format;
+    let track = format
+        .default_track()
+        .ok_or_else(|| anyhow!("no default track"))?;
+    let track_id = track.id;
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut sample_buf: Option<SampleBuffer<S>> = None;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let audio_buf = match decoder.decode(&packet) {
+            Ok(audio_buf) => audio_buf,
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let spec = *audio_buf.spec();
+        let buf =
+            sample_buf.get_or_insert_with(|| SampleBuffer::new(audio_buf.capacity() as u64, spec));
+        buf.copy_interleaved_ref(audio_buf);
+        each(buf.samples(), &spec);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 #[allow(clippy::non_ascii_literal)]
 mod tests {
 
-    use crate::utils::get_pin_yin;
+    use crate::utils::{get_pin_yin, open_command};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -499,4 +598,14 @@ mod tests {
         assert_eq!(child.width, 271);
         assert_eq!(child.height, 54);
     }
+
+    #[test]
+    fn test_open_command() {
+        let url = "https://example.com/?a=1&calc|x^<y>";
+        assert_eq!(
+            open_command("windows", url),
+            ("rundll32", vec!["url.dll,FileProtocolHandler", url])
+        );
+        assert_eq!(open_command("linux", url), ("xdg-open", vec![url]));
+    }
 }
@@ -60,6 +60,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
 use termusiclib::podcast::db::Database as DBPod;
+use termusiclib::podcast::{chapters, queue};
 use termusiclib::sqlite::DataBase;
 use termusiclib::track::{MediaType, Track};
 use termusiclib::utils::get_app_config_path;
@@ -78,14 +79,18 @@ pub enum PlayerCmd {
     GetProgress,
     PlaySelected,
     SkipPrevious,
+    NextChapter,
     Pause,
     Play,
+    PreviousChapter,
     ProcessID,
     Quit,
     ReloadConfig,
     ReloadPlaylist,
     SeekBackward,
     SeekForward,
+    /// Position in seconds
+    SeekTo(u32),
     SkipNext,
     SpeedDown,
     SpeedUp,
@@ -111,6 +116,8 @@ pub struct GeneralPlayer {
     pub playlist: Playlist,
     pub config: Settings,
     pub current_track_updated: bool,
+    /// The track was skipped, so its end does not count as played
+    pub skipped: bool,
     pub mpris: mpris::Mpris,
     pub discord: discord::Rpc,
     pub db: DataBase,
@@ -156,6 +163,7 @@ impl GeneralPlayer {
             cmd_rx,
             cmd_tx,
             current_track_updated: false,
+            skipped: false,
         }
     }
     pub fn toggle_gapless(&mut self) -> bool {
@@ -165,6 +173,7 @@ impl GeneralPlayer {
     }
 
     pub fn start_play(&mut self) {
+        self.skipped = false;
         if self.playlist.is_stopped() | self.playlist.is_paused() {
             self.playlist.set_status(Status::Running);
         }
@@ -254,6 +263,7 @@ impl GeneralPlayer {
     pub fn next(&mut self) {
         if self.playlist.current_track().is_some() {
             info!("skip route 1 which is in most cases.");
+            self.skipped = true;
             self.playlist.set_next_track(None);
             self.backend.skip_one();
         } else {
@@ -317,6 +327,95 @@ impl GeneralPlayer {
         self.backend.seek(offset).expect("Error in player seek.");
     }
 
+    /// Jump to the start of the next or previous chapter of the podcast episode playing
+    pub fn seek_chapter(&mut self, forward: bool) {
+        let Some(track) = self.playlist.current_track() else {
+            return;
+        };
+        if track.media_type != Some(MediaType::Podcast) {
+            return;
+        }
+        let Some(url) = track.file() else {
+            return;
+        };
+        let chapters = match self.db_podcast.get_chapters_by_url(url) {
+            Ok(chapters) => chapters,
+            Err(e) => {
+                error!("error loading chapters: {e}");
+                return;
+            }
+        };
+        let Ok((position, _duration)) = self.get_progress() else {
+            return;
+        };
+        let position = Duration::from_secs(u64::try_from(position).unwrap_or_default());
+        let target = if forward {
+            chapters::next_start(&chapters, position)
+        } else {
+            chapters::previous_start(&chapters, position)
+        };
+        if let Some(target) = target {
+            self.backend.seek_to(target);
+        }
+    }
+
+    /// Put the episode queued after the playing one next in the playlist, so the podcast
+    /// queue plays on by itself. Returns whether the playlist changed.
+    pub fn podcast_queue_prepare_next(&mut self) -> bool {
+        let Some(track) = self.playlist.current_track() else {
+            return false;
+        };
+        if track.media_type != Some(MediaType::Podcast) {
+            return false;
+        }
+        let Some(url) = track.file() else {
+            return false;
+        };
+        let next = match queue::next_after(&self.db_podcast, url) {
+            Ok(Some(next)) => next,
+            Ok(None) => return false,
+            Err(e) => {
+                error!("error reading podcast queue: {e}");
+                return false;
+            }
+        };
+        let index = self.playlist.get_current_track_index() + 1;
+        if let Some(track) = self.playlist.tracks().get(index) {
+            if track.file() == Some(next.url.as_str()) {
+                return false;
+            }
+        }
+        self.playlist.insert_episode(index, &next);
+        if let Err(e) = self.playlist.save() {
+            error!("error when saving playlist: {e}");
+        }
+        true
+    }
+
+    /// Called when a track ends. An episode that played to its end is marked played and
+    /// taken off the podcast queue, after the next queued one is put in the playlist.
+    /// Returns whether the playlist or the podcast database changed.
+    pub fn podcast_queue_advance(&mut self) -> bool {
+        if std::mem::take(&mut self.skipped) {
+            return false;
+        }
+        let url = match self.playlist.current_track() {
+            Some(track) if track.media_type == Some(MediaType::Podcast) => match track.file() {
+                Some(url) => url.to_string(),
+                None => return false,
+            },
+            _ => return false,
+        };
+        let playlist_changed = self.podcast_queue_prepare_next();
+        match queue::finish(&self.db_podcast, &url) {
+            Ok(finished) => playlist_changed || finished,
+            Err(e) => {
+                error!("error marking episode played: {e}");
+                playlist_changed
+            }
+        }
+    }
+
     #[allow(clippy::cast_sign_loss)]
     pub fn player_save_last_position(&mut self) {
         match self.config.player_remember_last_played_position {
@@ -13,6 +13,7 @@ use termusiclib::podcast::{db::Database as DBPod, Episode};
 use termusiclib::track::MediaType;
 use termusiclib::{
     config::{Loop, Settings},
+    organize::Move,
     track::Track,
     utils::{filetype_supported, get_app_config_path, get_parent_folder},
 };
@@ -75,7 +76,7 @@ impl Playlist {
     /// # Errors
     /// errors could happen when reading files
     pub fn new(config: &Settings) -> Result<Self> {
-        let (current_track_index, tracks) = Self::load()?;
+        let (current_track_index, tracks) = Self::load(config)?;
         let loop_mode = config.player_loop_mode;
         let current_track = None;
 
@@ -111,7 +112,7 @@ impl Playlist {
 
     /// # Errors
     /// errors could happen when reading file
-    pub fn load() -> Result<(usize, Vec<Track>)> {
+    pub fn load(config: &Settings) -> Result<(usize, Vec<Track>)> {
         let mut path = get_app_config_path()?;
         path.push("playlist.log");
 
@@ -141,7 +142,8 @@ impl Playlist {
             .get_podcasts()
             .expect("failed to get podcasts from db.");
         for line in &lines {
-            if let Ok(s) = Track::read_from_path(line, false) {
+            if let Ok(mut s) = Track::read_from_path(line, false) {
+                s.load_lyric_dir(&config.lyric_sidecar_dir);
                 playlist_items.push(s);
                 continue;
             };
@@ -170,7 +172,7 @@ impl Playlist {
     /// # Errors
     /// Errors could happen when reading files
     pub fn reload_tracks(&mut self) -> Result<()> {
-        let (current_track_index, tracks) = Self::load()?;
+        let (current_track_index, tracks) = Self::load(&self.config)?;
         self.tracks = tracks;
         self.current_track_index = current_track_index;
         Ok(())
@@ -385,6 +387,16 @@ impl Playlist {
         self.tracks.push(track);
     }
 
+    pub fn insert_episode(&mut self, index: usize, ep: &Episode) {
+        let track = Track::from_episode(ep);
+        let index = index.min(self.tracks.len());
+        self.tracks.insert(index, track);
+        // Keep pointing at the same track
+        if index <= self.current_track_index && self.len() > 1 {
+            self.current_track_index += 1;
+        }
+    }
+
     /// # Errors
     /// Error happens when track cannot be read from local file
     pub fn add_playlist(&mut self, vec: Vec<&str>) -> Result<()> {
@@ -395,7 +407,8 @@ impl Playlist {
             } else if !filetype_supported(item) {
                 continue;
             } else if PathBuf::from(item).exists() {
-                let track = Track::read_from_path(item, false)?;
+                let mut track = Track::read_from_path(item, false)?;
+                track.load_lyric_dir(&self.config.lyric_sidecar_dir);
                 self.tracks.push(track);
             }
         }
@@ -465,6 +478,21 @@ impl Playlist {
         }
     }
 
+    /// Follow tracks that were moved to another file
+    pub fn move_items(&mut self, moves: &[Move]) {
+        for track in &mut self.tracks {
+            let Some(m) = moves
+                .iter()
+                .find(|m| track.file().map_or(false, |f| m.from == Path::new(f)))
+            else {
+                continue;
+            };
+            if let Ok(moved) = Track::read_from_path(&m.to, false) {
+                *track = moved;
+            }
+        }
+    }
+
     #[must_use]
     pub fn current_track(&self) -> Option<&Track> {
         if self.current_track.is_some() {
@@ -1,26 +1,33 @@
 use anyhow::Result;
 use parking_lot::Mutex;
 use std::sync::Arc;
+use termusiclib::sqlite::DataBase;
 use termusicplayback::player::music_player_server::MusicPlayer;
 use termusicplayback::player::{
     CycleLoopReply, CycleLoopRequest, EmptyReply, GetProgressRequest, GetProgressResponse,
-    PlaySelectedRequest, ReloadConfigRequest, ReloadPlaylistRequest, SeekBackwardRequest,
-    SeekForwardRequest, SeekReply, SkipNextRequest, SkipNextResponse, SkipPreviousRequest,
-    SpeedDownRequest, SpeedReply, SpeedUpRequest, ToggleGaplessReply, ToggleGaplessRequest,
-    TogglePauseRequest, TogglePauseResponse, VolumeDownRequest, VolumeReply, VolumeUpRequest,
+    NextChapterRequest, PlaySelectedRequest, PreviousChapterRequest, ReloadConfigRequest,
+    ReloadPlaylistRequest, SearchReply, SearchRequest, SearchResult, SeekBackwardRequest,
+    SeekForwardRequest, SeekReply, SeekToRequest, SkipNextRequest, SkipNextResponse,
+    SkipPreviousRequest, SpeedDownRequest, SpeedReply, SpeedUpRequest, ToggleGaplessReply,
+    ToggleGaplessRequest, TogglePauseRequest, TogglePauseResponse, VolumeDownRequest, VolumeReply,
+    VolumeUpRequest,
 };
 use termusicplayback::PlayerCmd;
 use tokio::sync::mpsc::UnboundedSender;
 use tonic::{Request, Response, Status};
 
+/// Number of results returned by `Search` when the client does not set a limit
+const SEARCH_DEFAULT_LIMIT: usize = 100;
+
 #[derive(Debug)]
 pub struct MusicPlayerService {
     cmd_tx: Arc<Mutex<UnboundedSender<PlayerCmd>>>,
     pub progress: Arc<Mutex<GetProgressResponse>>,
+    db: DataBase,
 }
 
 impl MusicPlayerService {
-    pub fn new(cmd_tx: Arc<Mutex<UnboundedSender<PlayerCmd>>>) -> Self {
+    pub fn new(cmd_tx: Arc<Mutex<UnboundedSender<PlayerCmd>>>, db: DataBase) -> Self {
         let progress = GetProgressResponse {
             position: 0,
             duration: 60,
@@ -31,10 +38,18 @@ impl MusicPlayerService {
             gapless: true,
             current_track_updated: false,
             radio_title: String::new(),
+            podcast_new_episodes: 0,
+            podcast_files_changed: 0,
+            podcast_queue_advanced: false,
+            podcast_sync_changes: 0,
         };
         let progress = Arc::new(Mutex::new(progress));
 
-        Self { cmd_tx, progress }
+        Self {
+            cmd_tx,
+            progress,
+            db,
+        }
     }
 }
 
@@ -71,6 +86,10 @@ impl MusicPlayer for MusicPlayerService {
             gapless: true,
             current_track_updated: false,
             radio_title: String::new(),
+            podcast_new_episodes: 0,
+            podcast_files_changed: 0,
+            podcast_queue_advanced: false,
+            podcast_sync_changes: 0,
         };
         let mut r = self.progress.lock();
         reply.position = r.position;
@@ -82,9 +101,17 @@ impl MusicPlayer for MusicPlayerService {
         reply.gapless = r.gapless;
         reply.current_track_updated = r.current_track_updated;
         reply.radio_title = r.radio_title.clone();
+        reply.podcast_new_episodes = r.podcast_new_episodes;
+        reply.podcast_files_changed = r.podcast_files_changed;
+        reply.podcast_queue_advanced = r.podcast_queue_advanced;
+        reply.podcast_sync_changes = r.podcast_sync_changes;
         if r.current_track_updated {
             r.current_track_updated = false;
         }
+        r.podcast_new_episodes = 0;
+        r.podcast_files_changed = 0;
+        r.podcast_queue_advanced = false;
+        r.podcast_sync_changes = 0;
 
         Ok(Response::new(reply))
     }
@@ -116,6 +143,32 @@ impl MusicPlayer for MusicPlayerService {
         Ok(Response::new(reply))
     }
 
+    async fn search(
+        &self,
+        request: Request<SearchRequest>,
+    ) -> Result<Response<SearchReply>, Status> {
+        let request = request.into_inner();
+        let limit = match request.limit {
+            0 => SEARCH_DEFAULT_LIMIT,
+            l => l as usize,
+        };
+        let records = self
+            .db
+            .search(&request.query, limit)
+            .map_err(|e| Status::internal(format!("search error: {e}")))?;
+        let results = records
+            .into_iter()
+            .map(|r| SearchResult {
+                file: r.file,
+                artist: r.artist,
+                title: r.title,
+                album: r.album,
+                duration: u32::try_from(r.duration.as_secs()).unwrap_or(u32::MAX),
+            })
+            .collect();
+        Ok(Response::new(SearchReply { results }))
+    }
+
     async fn seek_backward(
         &self,
         _request: Request<SeekBackwardRequest>,
@@ -152,6 +205,60 @@ impl MusicPlayer for MusicPlayerService {
         Ok(Response::new(reply))
     }
 
+    async fn next_chapter(
+        &self,
+        _request: Request<NextChapterRequest>,
+    ) -> Result<Response<SeekReply>, Status> {
+        self.command(&PlayerCmd::NextChapter);
+        // This is to let the player update volume within loop
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let mut reply = SeekReply {
+            position: 0,
+            duration: 60,
+        };
+        let s = self.progress.lock();
+        reply.position = s.position;
+        reply.duration = s.duration;
+
+        Ok(Response::new(reply))
+    }
+
+    async fn previous_chapter(
+        &self,
+        _request: Request<PreviousChapterRequest>,
+    ) -> Result<Response<SeekReply>, Status> {
+        self.command(&PlayerCmd::PreviousChapter);
+        // This is to let the player update volume within loop
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let mut reply = SeekReply {
+            position: 0,
+            duration: 60,
+        };
+        let s = self.progress.lock();
+        reply.position = s.position;
+        reply.duration = s.duration;
+
+        Ok(Response::new(reply))
+    }
+
+    async fn seek_to(
+        &self,
+        request: Request<SeekToRequest>,
+    ) -> Result<Response<SeekReply>, Status> {
+        self.command(&PlayerCmd::SeekTo(request.into_inner().position));
+        // This is to let the player update volume within loop
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let mut reply = SeekReply {
+            position: 0,
+            duration: 60,
+        };
+        let s = self.progress.lock();
+        reply.position = s.position;
+        reply.duration = s.duration;
+
+        Ok(Response::new(reply))
+    }
+
     async fn skip_next(
         &self,
         request: Request<SkipNextRequest>,
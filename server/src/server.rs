@@ -4,8 +4,12 @@ use music_player_service::MusicPlayerService;
 use parking_lot::Mutex;
 use std::sync::Arc;
 use termusiclib::config::Settings;
+use termusiclib::podcast::schedule::{Scheduler, CHECK_EVERY};
+use termusiclib::sqlite::DataBase;
 use termusiclib::track::MediaType;
+use termusiclib::utils::get_app_config_path;
 use termusicplayback::player::music_player_server::MusicPlayerServer;
+use termusicplayback::player::GetProgressResponse;
 use termusicplayback::{GeneralPlayer, PlayerCmd, PlayerTrait, Status};
 use tonic::transport::Server;
 
@@ -21,10 +25,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cmd_tx = Arc::new(Mutex::new(cmd_tx));
     let cmd_rx = Arc::new(Mutex::new(cmd_rx));
 
-    let music_player_service: MusicPlayerService = MusicPlayerService::new(cmd_tx.clone());
     let mut config = Settings::default();
     config.load()?;
+    let music_player_service: MusicPlayerService =
+        MusicPlayerService::new(cmd_tx.clone(), DataBase::new(&config));
     let progress_tick = music_player_service.progress.clone();
+    let progress_podcast = music_player_service.progress.clone();
+    std::thread::spawn(move || refresh_podcasts(&progress_podcast));
 
     let cmd_tx_ctrlc = cmd_tx.clone();
 
@@ -47,6 +54,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     match cmd {
                         PlayerCmd::AboutToFinish => {
                             info!("about to finish signal received");
+                            if player.podcast_queue_prepare_next() {
+                                progress_tick.lock().podcast_queue_advanced = true;
+                            }
                             if !player.playlist.is_empty()
                                 && !player.playlist.has_next_track()
                                 && player.config.player_gapless
@@ -75,6 +85,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         }
                         PlayerCmd::Eos => {
                             info!("Eos received");
+                            if player.podcast_queue_advance() {
+                                progress_tick.lock().podcast_queue_advanced = true;
+                            }
                             if player.playlist.is_empty() {
                                 player.stop();
                                 continue;
@@ -124,6 +137,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 p_tick.position = position as u32;
                             }
                         }
+                        PlayerCmd::SeekTo(position) => {
+                            player.seek_to(std::time::Duration::from_secs(u64::from(position)));
+                            let mut p_tick = progress_tick.lock();
+                            if let Ok((position, _duration)) = player.get_progress() {
+                                p_tick.position = position as u32;
+                            }
+                        }
+                        PlayerCmd::NextChapter | PlayerCmd::PreviousChapter => {
+                            player.seek_chapter(matches!(cmd, PlayerCmd::NextChapter));
+                            let mut p_tick = progress_tick.lock();
+                            if let Ok((position, _duration)) = player.get_progress() {
+                                p_tick.position = position as u32;
+                            }
+                        }
                         PlayerCmd::SkipNext => {
                             info!("skip to next track.");
                             player.player_save_last_position();
@@ -254,3 +281,45 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Keep podcast feeds current, apply the feed policies, sync with a gpodder server, and
+/// tell clients about changes through the progress. Config is read again every round, so changed intervals apply
+/// without a restart.
+fn refresh_podcasts(progress: &Mutex<GetProgressResponse>) {
+    let db_path = match get_app_config_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("podcast refresh disabled: {e}");
+            return;
+        }
+    };
+    let mut scheduler = Scheduler::new(&db_path);
+    loop {
+        let mut config = Settings::default();
+        match config.load() {
+            Ok(()) => {
+                match scheduler.run_due(&config) {
+                    Ok(added) => {
+                        progress.lock().podcast_new_episodes += u32::try_from(added).unwrap_or(0);
+                    }
+                    Err(e) => error!("error refreshing podcasts: {e}"),
+                }
+                match scheduler.enforce_policies(&config) {
+                    Ok(changed) => {
+                        progress.lock().podcast_files_changed +=
+                            u32::try_from(changed).unwrap_or(0);
+                    }
+                    Err(e) => error!("error applying podcast policies: {e}"),
+                }
+                match scheduler.sync_due(&config) {
+                    Ok(changed) => {
+                        progress.lock().podcast_sync_changes += u32::try_from(changed).unwrap_or(0);
+                    }
+                    Err(e) => error!("error syncing podcasts: {e}"),
+                }
+            }
+            Err(e) => error!("error loading config for podcast refresh: {e}"),
+        }
+        std::thread::sleep(CHECK_EVERY);
+    }
+}
@@ -57,4 +57,23 @@ pub enum Action {
         #[arg(value_name = "FILE")]
         file: String,
     },
+    /// Print a summary of the music library database.
+    Stats {
+        /// Print JSON instead of plain text.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Move tracks into folders and file names built from their tags. Only shows the moves
+    /// unless --apply is given.
+    Organize {
+        /// Folder or file to organize, default is the music directory.
+        #[arg(value_name = "PATH")]
+        path: Option<String>,
+        /// Pattern to use instead of `library_organize_pattern` from the config.
+        #[arg(short, long)]
+        pattern: Option<String>,
+        /// Move the files.
+        #[arg(long)]
+        apply: bool,
+    },
 }
@@ -30,13 +30,15 @@
 mod cli;
 mod ui;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Parser;
 use config::Settings;
 use std::path::Path;
 use std::process;
 use sysinfo::{PidExt, ProcessExt, System, SystemExt};
-use termusiclib::{config, podcast, utils};
+use termusiclib::sqlite::DataBase;
+use termusiclib::stats::LibraryStats;
+use termusiclib::{config, organize, podcast, utils};
 use ui::UI;
 #[macro_use]
 extern crate log;
@@ -86,6 +88,24 @@ async fn main() -> Result<()> {
 
             process::exit(0);
         }
+        Some(cli::Action::Stats { json }) => {
+            if let Err(e) = stats(&config, json) {
+                eprintln!("Error when reading stats: {e}");
+                process::exit(1);
+            }
+            process::exit(0);
+        }
+        Some(cli::Action::Organize {
+            path,
+            pattern,
+            apply,
+        }) => {
+            if let Err(e) = organize(&config, path, pattern.as_deref(), apply) {
+                eprintln!("Error when organizing: {e}");
+                process::exit(1);
+            }
+            process::exit(0);
+        }
         None => {}
     }
 
@@ -119,6 +139,71 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+fn organize(
+    config: &Settings,
+    path: Option<String>,
+    pattern: Option<&str>,
+    apply: bool,
+) -> Result<()> {
+    let path = path
+        .and_then(|p| get_path(&p))
+        .or_else(|| config.music_dir_from_cli.clone())
+        .or_else(|| config.music_dir.first().cloned())
+        .unwrap_or_default();
+    let path = Path::new(&path);
+    let pattern = organize::Pattern::parse(pattern.unwrap_or(&config.library_organize_pattern))?;
+    let mut db = DataBase::new(config);
+    let records: Vec<_> = db
+        .get_all_records()?
+        .into_iter()
+        .filter(|r| Path::new(&r.file).starts_with(path))
+        .collect();
+    let plan = organize::plan(&records, &organize::root_of(path, config), &pattern);
+
+    for m in &plan.moves {
+        println!("{} -> {}", m.from.display(), m.to.display());
+    }
+    for m in &plan.conflicts {
+        println!(
+            "skipped, target taken: {} -> {}",
+            m.from.display(),
+            m.to.display()
+        );
+    }
+    if !apply {
+        println!(
+            "{} tracks to move. Run again with --apply to move them.",
+            plan.moves.len()
+        );
+        return Ok(());
+    }
+
+    let report = organize::apply(&plan.moves, &config.lyric_sidecar_dir);
+    db.move_records(&report.moved)?;
+    for (file, e) in &report.failed {
+        eprintln!("Error when moving {file}: {e}");
+    }
+    for (file, e) in &report.left_behind {
+        eprintln!("Left behind {file}: {e}");
+    }
+    println!("{} tracks moved.", report.moved.len());
+    if !report.failed.is_empty() {
+        bail!("{} tracks could not be moved", report.failed.len());
+    }
+    Ok(())
+}
+
+fn stats(config: &Settings, json: bool) -> Result<()> {
+    let mut db = DataBase::new(config);
+    let stats = LibraryStats::from_records(&db.get_all_records()?);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print!("{stats}");
+    }
+    Ok(())
+}
+
 fn get_path(dir: &str) -> Option<String> {
     let music_dir: Option<String>;
     let mut path = Path::new(&dir).to_path_buf();
@@ -0,0 +1,395 @@
+use crate::ui::Model;
+use termusiclib::config::{Keys, Settings};
+use termusiclib::sqlite::SearchCriteria;
+use termusiclib::track::Track;
+use termusiclib::types::{BrowseMsg, Id, Msg};
+use tui_realm_stdlib::List;
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::props::{Alignment, BorderType, TableBuilder, TextSpan};
+use tuirealm::props::{Borders, Color};
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers, NoUserEvent},
+    Component, Event, MockComponent, State, StateValue,
+};
+
+fn browse_list(config: &Settings, title: &str) -> List {
+    List::default()
+        .borders(
+            Borders::default().modifiers(BorderType::Rounded).color(
+                config
+                    .style_color_symbol
+                    .library_border()
+                    .unwrap_or(Color::Blue),
+            ),
+        )
+        .background(
+            config
+                .style_color_symbol
+                .library_background()
+                .unwrap_or(Color::Reset),
+        )
+        .foreground(
+            config
+                .style_color_symbol
+                .library_foreground()
+                .unwrap_or(Color::Yellow),
+        )
+        .title(title, Alignment::Left)
+        .scroll(true)
+        .highlighted_color(
+            config
+                .style_color_symbol
+                .library_highlight()
+                .unwrap_or(Color::LightBlue),
+        )
+        .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+        .rewind(false)
+        .step(4)
+        .rows(
+            TableBuilder::default()
+                .add_col(TextSpan::from("Empty"))
+                .build(),
+        )
+}
+
+/// Movement keys shared by all browse lists
+fn browse_move(component: &mut List, keys: &Keys, ev: &Event<NoUserEvent>) -> Option<CmdResult> {
+    let cmd = match ev {
+        Event::Keyboard(KeyEvent {
+            code: Key::Down,
+            modifiers: KeyModifiers::NONE,
+        }) => Cmd::Move(Direction::Down),
+        Event::Keyboard(KeyEvent {
+            code: Key::Up,
+            modifiers: KeyModifiers::NONE,
+        }) => Cmd::Move(Direction::Up),
+        Event::Keyboard(key) if *key == keys.global_down.key_event() => Cmd::Move(Direction::Down),
+        Event::Keyboard(key) if *key == keys.global_up.key_event() => Cmd::Move(Direction::Up),
+        Event::Keyboard(KeyEvent {
+            code: Key::PageDown,
+            modifiers: KeyModifiers::NONE,
+        }) => Cmd::Scroll(Direction::Down),
+        Event::Keyboard(KeyEvent {
+            code: Key::PageUp,
+            modifiers: KeyModifiers::NONE,
+        }) => Cmd::Scroll(Direction::Up),
+        Event::Keyboard(key) if *key == keys.global_goto_top.key_event() => {
+            Cmd::GoTo(Position::Begin)
+        }
+        Event::Keyboard(key) if *key == keys.global_goto_bottom.key_event() => {
+            Cmd::GoTo(Position::End)
+        }
+        Event::Keyboard(KeyEvent {
+            code: Key::Home,
+            modifiers: KeyModifiers::NONE,
+        }) => Cmd::GoTo(Position::Begin),
+        Event::Keyboard(KeyEvent {
+            code: Key::End,
+            modifiers: KeyModifiers::NONE,
+        }) => Cmd::GoTo(Position::End),
+        _ => return None,
+    };
+    Some(component.perform(cmd))
+}
+
+#[derive(MockComponent)]
+pub struct BrowseArtists {
+    component: List,
+    keys: Keys,
+}
+
+impl BrowseArtists {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: browse_list(config, " Artists "),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for BrowseArtists {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        if browse_move(&mut self.component, &self.keys, &ev).is_some() {
+            return Some(Msg::None);
+        }
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter,
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Browse(BrowseMsg::ArtistSelected(index)));
+                }
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_right.key_event() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Browse(BrowseMsg::ArtistSelected(index)));
+                }
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Tab,
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::Browse(BrowseMsg::ArtistsBlurDown)),
+            Event::Keyboard(KeyEvent {
+                code: Key::BackTab,
+                modifiers: KeyModifiers::SHIFT,
+            }) => return Some(Msg::Browse(BrowseMsg::ArtistsBlurUp)),
+            Event::Keyboard(keyevent) if keyevent == self.keys.database_stats.key_event() => {
+                return Some(Msg::StatsPopupShow)
+            }
+            _ => {}
+        }
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct BrowseAlbums {
+    component: List,
+    keys: Keys,
+}
+
+impl BrowseAlbums {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: browse_list(config, " Albums "),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for BrowseAlbums {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        if browse_move(&mut self.component, &self.keys, &ev).is_some() {
+            // the cover follows the highlighted album
+            if let State::One(StateValue::Usize(index)) = self.state() {
+                return Some(Msg::Browse(BrowseMsg::AlbumHighlighted(index)));
+            }
+            return Some(Msg::None);
+        }
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter,
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Browse(BrowseMsg::AlbumSelected(index)));
+                }
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_right.key_event() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Browse(BrowseMsg::AlbumSelected(index)));
+                }
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_left.key_event() => {
+                return Some(Msg::Browse(BrowseMsg::AlbumsBlurUp))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Tab,
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::Browse(BrowseMsg::AlbumsBlurDown)),
+            Event::Keyboard(KeyEvent {
+                code: Key::BackTab,
+                modifiers: KeyModifiers::SHIFT,
+            }) => return Some(Msg::Browse(BrowseMsg::AlbumsBlurUp)),
+            Event::Keyboard(keyevent) if keyevent == self.keys.database_stats.key_event() => {
+                return Some(Msg::StatsPopupShow)
+            }
+            _ => {}
+        }
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct BrowseTracks {
+    component: List,
+    keys: Keys,
+}
+
+impl BrowseTracks {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: browse_list(config, " Tracks "),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for BrowseTracks {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        if browse_move(&mut self.component, &self.keys, &ev).is_some() {
+            return Some(Msg::None);
+        }
+        match ev {
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_right.key_event() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Browse(BrowseMsg::AddPlaylist(index)));
+                }
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.database_add_all.key_event() => {
+                return Some(Msg::Browse(BrowseMsg::AddAllToPlaylist))
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_left.key_event() => {
+                return Some(Msg::Browse(BrowseMsg::TracksBlurUp))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Tab,
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::Browse(BrowseMsg::TracksBlurDown)),
+            Event::Keyboard(KeyEvent {
+                code: Key::BackTab,
+                modifiers: KeyModifiers::SHIFT,
+            }) => return Some(Msg::Browse(BrowseMsg::TracksBlurUp)),
+            Event::Keyboard(keyevent) if keyevent == self.keys.database_stats.key_event() => {
+                return Some(Msg::StatsPopupShow)
+            }
+            _ => {}
+        }
+        Some(Msg::None)
+    }
+}
+
+impl Model {
+    pub fn browse_sync_artists(&mut self) {
+        match self.db.get_criterias(&SearchCriteria::AlbumArtist) {
+            Ok(artists) => self.browse_artists = artists,
+            Err(e) => self.mount_error_popup(format!("browse artists error: {e}")),
+        }
+
+        let mut table: TableBuilder = TableBuilder::default();
+        for (idx, artist) in self.browse_artists.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            let name = if artist.is_empty() {
+                "Unknown Artist"
+            } else {
+                artist.as_str()
+            };
+            table.add_col(TextSpan::from(name));
+        }
+        if self.browse_artists.is_empty() {
+            table.add_col(TextSpan::from("empty library"));
+        }
+        self.app
+            .attr(
+                &Id::BrowseArtists,
+                tuirealm::Attribute::Content,
+                tuirealm::AttrValue::Table(table.build()),
+            )
+            .ok();
+    }
+
+    fn browse_sync_albums(&mut self) {
+        let mut table: TableBuilder = TableBuilder::default();
+        for (idx, album) in self.browse_albums.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            let year = album
+                .year
+                .map_or_else(|| "----".to_string(), |y| y.to_string());
+            let name = if album.album.is_empty() {
+                "Unknown Album"
+            } else {
+                album.album.as_str()
+            };
+            table
+                .add_col(TextSpan::from(format!("{year} ")))
+                .add_col(TextSpan::new(name).bold())
+                .add_col(TextSpan::from(format!(
+                    " ({} tracks, {})",
+                    album.track_count,
+                    Track::duration_formatted_short(&album.duration)
+                )));
+        }
+        if self.browse_albums.is_empty() {
+            table.add_col(TextSpan::from("no albums"));
+        }
+        self.app
+            .attr(
+                &Id::BrowseAlbums,
+                tuirealm::Attribute::Content,
+                tuirealm::AttrValue::Table(table.build()),
+            )
+            .ok();
+    }
+
+    fn browse_sync_tracks(&mut self) {
+        let mut table: TableBuilder = TableBuilder::default();
+        for (idx, record) in self.browse_tracks.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            let number = record
+                .track_number
+                .map_or_else(String::new, |n| format!("{n:>2}"));
+            let title = if record.title.is_empty() {
+                &record.name
+            } else {
+                &record.title
+            };
+            table
+                .add_col(TextSpan::from(format!("{number:>2} ")))
+                .add_col(TextSpan::from(title))
+                .add_col(TextSpan::from(format!(
+                    " [{}]",
+                    Track::duration_formatted_short(&record.duration)
+                )));
+        }
+        if self.browse_tracks.is_empty() {
+            table.add_col(TextSpan::from("no tracks"));
+        }
+        self.app
+            .attr(
+                &Id::BrowseTracks,
+                tuirealm::Attribute::Content,
+                tuirealm::AttrValue::Table(table.build()),
+            )
+            .ok();
+    }
+
+    pub fn browse_select_artist(&mut self, index: usize) {
+        let Some(artist) = self.browse_artists.get(index).cloned() else {
+            return;
+        };
+        match self.db.get_albums_by_artist(&artist) {
+            Ok(albums) => self.browse_albums = albums,
+            Err(e) => self.mount_error_popup(format!("browse albums error: {e}")),
+        }
+        self.browse_album_artist = artist;
+        self.browse_tracks.clear();
+        self.browse_sync_albums();
+        self.browse_sync_tracks();
+        self.app.active(&Id::BrowseAlbums).ok();
+        self.browse_highlight_album(0);
+    }
+
+    pub fn browse_select_album(&mut self, index: usize) {
+        let Some(album) = self.browse_albums.get(index) else {
+            return;
+        };
+        match self
+            .db
+            .get_album_tracks(&self.browse_album_artist, &album.album)
+        {
+            Ok(tracks) => self.browse_tracks = tracks,
+            Err(e) => self.mount_error_popup(format!("browse tracks error: {e}")),
+        }
+        self.browse_sync_tracks();
+        self.app.active(&Id::BrowseTracks).ok();
+    }
+
+    /// Show the cover of the album under the cursor instead of the playing track's.
+    pub fn browse_highlight_album(&mut self, index: usize) {
+        let file = self.browse_albums.get(index).map(|a| a.file.clone());
+        if file == self.browse_cover {
+            return;
+        }
+        self.browse_cover = file;
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(format!("update photo error: {e}"));
+        }
+    }
+}
@@ -909,7 +909,7 @@ mod test {
             "vanilla".to_string(),
             "chocolate".to_string(),
         ];
-        states.set_choices(&choices);
+        states.set_choices(choices);
         assert_eq!(states.selected, 0);
         assert_eq!(states.choices.len(), 4);
         // Move
@@ -938,7 +938,7 @@ mod test {
         assert_eq!(states.selected, 2);
         // Update
         let choices: &[String] = &["lemon".to_string(), "strawberry".to_string()];
-        states.set_choices(&choices);
+        states.set_choices(choices);
         assert_eq!(states.selected, 1); // Move to first index available
         assert_eq!(states.choices.len(), 2);
         let choices = vec![];
@@ -1117,6 +1117,7 @@ impl KEModifierSelect {
         let keys = &config.keys;
         match id {
             IdKey::DatabaseAddAll => keys.database_add_all.mod_key(),
+            IdKey::DatabaseStats => keys.database_stats.mod_key(),
             IdKey::GlobalConfig => keys.global_config_open.mod_key(),
             IdKey::GlobalDown => keys.global_down.mod_key(),
             IdKey::GlobalGotoBottom => keys.global_goto_bottom.mod_key(),
@@ -1144,6 +1145,7 @@ impl KEModifierSelect {
             IdKey::GlobalSavePlaylist => keys.global_save_playlist.mod_key(),
             IdKey::LibraryDelete => keys.library_delete.mod_key(),
             IdKey::LibraryLoadDir => keys.library_load_dir.mod_key(),
+            IdKey::LibraryOrganize => keys.library_organize.mod_key(),
             IdKey::LibraryPaste => keys.library_paste.mod_key(),
             IdKey::LibrarySearch => keys.library_search.mod_key(),
             IdKey::LibrarySearchYoutube => keys.library_search_youtube.mod_key(),
@@ -1163,6 +1165,11 @@ impl KEModifierSelect {
             IdKey::LibraryAddRoot => keys.library_add_root.mod_key(),
             IdKey::LibraryRemoveRoot => keys.library_remove_root.mod_key(),
             IdKey::GlobalLayoutPodcast => keys.global_layout_podcast.mod_key(),
+            IdKey::GlobalLayoutBrowse => keys.global_layout_browse.mod_key(),
+            IdKey::GlobalLyricToggleScroll => keys.global_lyric_toggle_scroll.mod_key(),
+            IdKey::GlobalLyricSeekLine => keys.global_lyric_seek_line.mod_key(),
+            IdKey::GlobalPlayerNextChapter => keys.global_player_next_chapter.mod_key(),
+            IdKey::GlobalPlayerPreviousChapter => keys.global_player_previous_chapter.mod_key(),
             IdKey::GlobalXywhMoveLeft => keys.global_xywh_move_left.mod_key(),
             IdKey::GlobalXywhMoveRight => keys.global_xywh_move_right.mod_key(),
             IdKey::GlobalXywhMoveUp => keys.global_xywh_move_up.mod_key(),
@@ -1176,6 +1183,12 @@ impl KEModifierSelect {
             IdKey::PodcastEpDeleteFile => keys.podcast_episode_delete_file.mod_key(),
             IdKey::PodcastDeleteFeed => keys.podcast_delete_feed.mod_key(),
             IdKey::PodcastDeleteAllFeeds => keys.podcast_delete_all_feeds.mod_key(),
+            IdKey::PodcastFeedPolicy => keys.podcast_feed_policy.mod_key(),
+            IdKey::PodcastSearchTranscripts => keys.podcast_search_transcripts.mod_key(),
+            IdKey::PodcastQueueFeed => keys.podcast_queue_feed.mod_key(),
+            IdKey::PodcastQueueShow => keys.podcast_queue_show.mod_key(),
+            IdKey::PodcastFeedFilter => keys.podcast_feed_filter.mod_key(),
+            IdKey::PodcastDownloadFailed => keys.podcast_download_failed.mod_key(),
             IdKey::PodcastSearchAddFeed => keys.podcast_search_add_feed.mod_key(),
             IdKey::PodcastRefreshFeed => keys.podcast_refresh_feed.mod_key(),
             IdKey::PodcastRefreshAllFeeds => keys.podcast_refresh_all_feeds.mod_key(),
@@ -2580,6 +2593,145 @@ impl Component<Msg, NoUserEvent> for ConfigGlobalLayoutPodcast {
     }
 }
 
+#[derive(MockComponent)]
+pub struct ConfigGlobalLayoutBrowse {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalLayoutBrowse {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Layout Browse ",
+                IdKey::GlobalLayoutBrowse,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalLayoutBrowseBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalLayoutBrowseBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalLayoutBrowse {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalLyricToggleScroll {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalLyricToggleScroll {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Lyric Scroll ",
+                IdKey::GlobalLyricToggleScroll,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalLyricToggleScrollBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalLyricToggleScrollBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalLyricToggleScroll {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalLyricSeekLine {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalLyricSeekLine {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Lyric Seek Line ",
+                IdKey::GlobalLyricSeekLine,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalLyricSeekLineBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::GlobalLyricSeekLineBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalLyricSeekLine {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalPlayerNextChapter {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalPlayerNextChapter {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Next Chapter ",
+                IdKey::GlobalPlayerNextChapter,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerNextChapterBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerNextChapterBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalPlayerNextChapter {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigGlobalPlayerPreviousChapter {
+    component: KEModifierSelect,
+}
+
+impl ConfigGlobalPlayerPreviousChapter {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Previous Chapter ",
+                IdKey::GlobalPlayerPreviousChapter,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerPreviousChapterBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::GlobalPlayerPreviousChapterBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigGlobalPlayerPreviousChapter {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
 #[derive(MockComponent)]
 pub struct ConfigGlobalXywhMoveLeft {
     component: KEModifierSelect,
@@ -2939,6 +3091,214 @@ impl Component<Msg, NoUserEvent> for ConfigPodcastSearchAddFeed {
     }
 }
 
+#[derive(MockComponent)]
+pub struct ConfigDatabaseStats {
+    component: KEModifierSelect,
+}
+
+impl ConfigDatabaseStats {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Library statistics ",
+                IdKey::DatabaseStats,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::DatabaseStatsBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::DatabaseStatsBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigDatabaseStats {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigLibraryOrganize {
+    component: KEModifierSelect,
+}
+
+impl ConfigLibraryOrganize {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Organize files ",
+                IdKey::LibraryOrganize,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryOrganizeBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::LibraryOrganizeBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigLibraryOrganize {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPodcastFeedPolicy {
+    component: KEModifierSelect,
+}
+
+impl ConfigPodcastFeedPolicy {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Feed policy ",
+                IdKey::PodcastFeedPolicy,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PodcastFeedPolicyBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PodcastFeedPolicyBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPodcastFeedPolicy {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPodcastSearchTranscripts {
+    component: KEModifierSelect,
+}
+
+impl ConfigPodcastSearchTranscripts {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Episode search transcripts ",
+                IdKey::PodcastSearchTranscripts,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::PodcastSearchTranscriptsBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::PodcastSearchTranscriptsBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPodcastSearchTranscripts {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPodcastQueueFeed {
+    component: KEModifierSelect,
+}
+
+impl ConfigPodcastQueueFeed {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Queue unplayed of feed ",
+                IdKey::PodcastQueueFeed,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PodcastQueueFeedBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PodcastQueueFeedBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPodcastQueueFeed {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPodcastQueueShow {
+    component: KEModifierSelect,
+}
+
+impl ConfigPodcastQueueShow {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Show podcast queue ",
+                IdKey::PodcastQueueShow,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PodcastQueueShowBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PodcastQueueShowBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPodcastQueueShow {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPodcastFeedFilter {
+    component: KEModifierSelect,
+}
+
+impl ConfigPodcastFeedFilter {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Feed view options ",
+                IdKey::PodcastFeedFilter,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PodcastFeedFilterBlurDown)),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(KFMsg::PodcastFeedFilterBlurUp)),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPodcastFeedFilter {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ConfigPodcastDownloadFailed {
+    component: KEModifierSelect,
+}
+
+impl ConfigPodcastDownloadFailed {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: KEModifierSelect::new(
+                " Failed downloads ",
+                IdKey::PodcastDownloadFailed,
+                config,
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::PodcastDownloadFailedBlurDown,
+                )),
+                Msg::ConfigEditor(ConfigEditorMsg::KeyFocus(
+                    KFMsg::PodcastDownloadFailedBlurUp,
+                )),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ConfigPodcastDownloadFailed {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
 #[derive(MockComponent)]
 pub struct ConfigPodcastRefreshFeed {
     component: KEModifierSelect,
@@ -306,7 +306,7 @@ impl Model {
     fn update_key_focus(&mut self, msg: &KFMsg) {
         match msg {
             // Focus of key global page
-            KFMsg::GlobalXywhHideBlurDown | KFMsg::GlobalLeftBlurUp => {
+            KFMsg::GlobalPlayerPreviousChapterBlurDown | KFMsg::GlobalLeftBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalQuit)))
                     .ok();
@@ -529,16 +529,51 @@ impl Model {
                     )))
                     .ok();
             }
-            KFMsg::GlobalXywhZoomOutBlurDown | KFMsg::GlobalQuitBlurUp => {
+            KFMsg::GlobalXywhZoomOutBlurDown | KFMsg::GlobalLayoutBrowseBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::GlobalXywhHide,
                     )))
                     .ok();
             }
+            KFMsg::GlobalXywhHideBlurDown | KFMsg::GlobalLyricToggleScrollBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalLayoutBrowse,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalLayoutBrowseBlurDown | KFMsg::GlobalLyricSeekLineBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalLyricToggleScroll,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalLyricToggleScrollBlurDown | KFMsg::GlobalPlayerNextChapterBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalLyricSeekLine,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalLyricSeekLineBlurDown | KFMsg::GlobalPlayerPreviousChapterBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerNextChapter,
+                    )))
+                    .ok();
+            }
+            KFMsg::GlobalPlayerNextChapterBlurDown | KFMsg::GlobalQuitBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::GlobalPlayerPreviousChapter,
+                    )))
+                    .ok();
+            }
 
             // Focus of key 2 page
-            KFMsg::PodcastSearchAddFeedBlurDown | KFMsg::LibraryDeleteBlurUp => {
+            KFMsg::PodcastDownloadFailedBlurDown | KFMsg::LibraryDeleteBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::LibraryTagEditor,
@@ -753,20 +788,76 @@ impl Model {
                     )))
                     .ok();
             }
-            KFMsg::PodcastRefreshAllFeedsBlurDown | KFMsg::LibraryTagEditorBlurUp => {
+            KFMsg::PodcastRefreshAllFeedsBlurDown | KFMsg::DatabaseStatsBlurUp => {
                 self.app
                     .active(&Id::ConfigEditor(IdConfigEditor::Key(
                         IdKey::PodcastSearchAddFeed,
                     )))
                     .ok();
             }
+            KFMsg::PodcastSearchAddFeedBlurDown | KFMsg::LibraryOrganizeBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::DatabaseStats)))
+                    .ok();
+            }
+            KFMsg::DatabaseStatsBlurDown | KFMsg::PodcastFeedPolicyBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::LibraryOrganize,
+                    )))
+                    .ok();
+            }
+            KFMsg::LibraryOrganizeBlurDown | KFMsg::PodcastSearchTranscriptsBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PodcastFeedPolicy,
+                    )))
+                    .ok();
+            }
+            KFMsg::PodcastFeedPolicyBlurDown | KFMsg::PodcastQueueFeedBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PodcastSearchTranscripts,
+                    )))
+                    .ok();
+            }
+            KFMsg::PodcastSearchTranscriptsBlurDown | KFMsg::PodcastQueueShowBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PodcastQueueFeed,
+                    )))
+                    .ok();
+            }
+            KFMsg::PodcastQueueFeedBlurDown | KFMsg::PodcastFeedFilterBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PodcastQueueShow,
+                    )))
+                    .ok();
+            }
+            KFMsg::PodcastQueueShowBlurDown | KFMsg::PodcastDownloadFailedBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PodcastFeedFilter,
+                    )))
+                    .ok();
+            }
+            KFMsg::PodcastFeedFilterBlurDown | KFMsg::LibraryTagEditorBlurUp => {
+                self.app
+                    .active(&Id::ConfigEditor(IdConfigEditor::Key(
+                        IdKey::PodcastDownloadFailed,
+                    )))
+                    .ok();
+            }
         }
     }
 
+    #[allow(clippy::too_many_lines)]
     fn update_key(&mut self, id: &IdKey, binding: &BindingForEvent) {
         self.config_changed = true;
         match id {
             IdKey::DatabaseAddAll => self.ke_key_config.database_add_all = *binding,
+            IdKey::DatabaseStats => self.ke_key_config.database_stats = *binding,
             IdKey::GlobalConfig => self.ke_key_config.global_config_open = *binding,
             IdKey::GlobalDown => self.ke_key_config.global_down = *binding,
             IdKey::GlobalGotoBottom => self.ke_key_config.global_goto_bottom = *binding,
@@ -806,6 +897,7 @@ impl Model {
             IdKey::GlobalSavePlaylist => self.ke_key_config.global_save_playlist = *binding,
             IdKey::LibraryDelete => self.ke_key_config.library_delete = *binding,
             IdKey::LibraryLoadDir => self.ke_key_config.library_load_dir = *binding,
+            IdKey::LibraryOrganize => self.ke_key_config.library_organize = *binding,
             IdKey::LibraryPaste => self.ke_key_config.library_paste = *binding,
             IdKey::LibrarySearch => self.ke_key_config.library_search = *binding,
             IdKey::LibrarySearchYoutube => self.ke_key_config.library_search_youtube = *binding,
@@ -825,6 +917,17 @@ impl Model {
             IdKey::LibraryAddRoot => self.ke_key_config.library_add_root = *binding,
             IdKey::LibraryRemoveRoot => self.ke_key_config.library_remove_root = *binding,
             IdKey::GlobalLayoutPodcast => self.ke_key_config.global_layout_podcast = *binding,
+            IdKey::GlobalLayoutBrowse => self.ke_key_config.global_layout_browse = *binding,
+            IdKey::GlobalLyricToggleScroll => {
+                self.ke_key_config.global_lyric_toggle_scroll = *binding;
+            }
+            IdKey::GlobalLyricSeekLine => self.ke_key_config.global_lyric_seek_line = *binding,
+            IdKey::GlobalPlayerNextChapter => {
+                self.ke_key_config.global_player_next_chapter = *binding;
+            }
+            IdKey::GlobalPlayerPreviousChapter => {
+                self.ke_key_config.global_player_previous_chapter = *binding;
+            }
             IdKey::GlobalXywhMoveLeft => self.ke_key_config.global_xywh_move_left = *binding,
             IdKey::GlobalXywhMoveRight => self.ke_key_config.global_xywh_move_right = *binding,
             IdKey::GlobalXywhMoveUp => self.ke_key_config.global_xywh_move_up = *binding,
@@ -837,6 +940,16 @@ impl Model {
             IdKey::PodcastEpDownload => self.ke_key_config.podcast_episode_download = *binding,
             IdKey::PodcastEpDeleteFile => self.ke_key_config.podcast_episode_delete_file = *binding,
             IdKey::PodcastDeleteFeed => self.ke_key_config.podcast_delete_feed = *binding,
+            IdKey::PodcastFeedPolicy => self.ke_key_config.podcast_feed_policy = *binding,
+            IdKey::PodcastSearchTranscripts => {
+                self.ke_key_config.podcast_search_transcripts = *binding;
+            }
+            IdKey::PodcastQueueFeed => self.ke_key_config.podcast_queue_feed = *binding,
+            IdKey::PodcastQueueShow => self.ke_key_config.podcast_queue_show = *binding,
+            IdKey::PodcastFeedFilter => self.ke_key_config.podcast_feed_filter = *binding,
+            IdKey::PodcastDownloadFailed => {
+                self.ke_key_config.podcast_download_failed = *binding;
+            }
             IdKey::PodcastDeleteAllFeeds => self.ke_key_config.podcast_delete_all_feeds = *binding,
             IdKey::PodcastSearchAddFeed => self.ke_key_config.podcast_search_add_feed = *binding,
             IdKey::PodcastRefreshFeed => self.ke_key_config.podcast_refresh_feed = *binding,
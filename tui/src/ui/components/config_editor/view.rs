@@ -1,9 +1,11 @@
 use crate::ui::components::{
-    AlbumPhotoAlign, CEHeader, CEThemeSelectTable, ConfigDatabaseAddAll, ConfigGlobalConfig,
-    ConfigGlobalDown, ConfigGlobalGotoBottom, ConfigGlobalGotoTop, ConfigGlobalHelp,
-    ConfigGlobalLayoutDatabase, ConfigGlobalLayoutPodcast, ConfigGlobalLayoutTreeview,
-    ConfigGlobalLeft, ConfigGlobalLyricAdjustBackward, ConfigGlobalLyricAdjustForward,
-    ConfigGlobalLyricCycle, ConfigGlobalPlayerNext, ConfigGlobalPlayerPrevious,
+    AlbumPhotoAlign, CEHeader, CEThemeSelectTable, ConfigDatabaseAddAll, ConfigDatabaseStats,
+    ConfigGlobalConfig, ConfigGlobalDown, ConfigGlobalGotoBottom, ConfigGlobalGotoTop,
+    ConfigGlobalHelp, ConfigGlobalLayoutBrowse, ConfigGlobalLayoutDatabase,
+    ConfigGlobalLayoutPodcast, ConfigGlobalLayoutTreeview, ConfigGlobalLeft,
+    ConfigGlobalLyricAdjustBackward, ConfigGlobalLyricAdjustForward, ConfigGlobalLyricCycle,
+    ConfigGlobalLyricSeekLine, ConfigGlobalLyricToggleScroll, ConfigGlobalPlayerNext,
+    ConfigGlobalPlayerNextChapter, ConfigGlobalPlayerPrevious, ConfigGlobalPlayerPreviousChapter,
     ConfigGlobalPlayerSeekBackward, ConfigGlobalPlayerSeekForward, ConfigGlobalPlayerSpeedDown,
     ConfigGlobalPlayerSpeedUp, ConfigGlobalPlayerToggleGapless, ConfigGlobalPlayerTogglePause,
     ConfigGlobalQuit, ConfigGlobalRight, ConfigGlobalSavePlaylist, ConfigGlobalUp,
@@ -11,7 +13,7 @@ use crate::ui::components::{
     ConfigGlobalXywhMoveLeft, ConfigGlobalXywhMoveRight, ConfigGlobalXywhMoveUp,
     ConfigGlobalXywhZoomIn, ConfigGlobalXywhZoomOut, ConfigLibraryAddRoot, ConfigLibraryBackground,
     ConfigLibraryBorder, ConfigLibraryDelete, ConfigLibraryForeground, ConfigLibraryHighlight,
-    ConfigLibraryHighlightSymbol, ConfigLibraryLoadDir, ConfigLibraryPaste,
+    ConfigLibraryHighlightSymbol, ConfigLibraryLoadDir, ConfigLibraryOrganize, ConfigLibraryPaste,
     ConfigLibraryRemoveRoot, ConfigLibrarySearch, ConfigLibrarySearchYoutube,
     ConfigLibrarySwitchRoot, ConfigLibraryTagEditor, ConfigLibraryTitle, ConfigLibraryYank,
     ConfigLyricBackground, ConfigLyricBorder, ConfigLyricForeground, ConfigLyricTitle,
@@ -20,9 +22,11 @@ use crate::ui::components::{
     ConfigPlaylistLqueue, ConfigPlaylistModeCycle, ConfigPlaylistPlaySelected,
     ConfigPlaylistSearch, ConfigPlaylistShuffle, ConfigPlaylistSwapDown, ConfigPlaylistSwapUp,
     ConfigPlaylistTitle, ConfigPlaylistTqueue, ConfigPodcastDeleteAllFeeds,
-    ConfigPodcastDeleteFeed, ConfigPodcastEpDeleteFile, ConfigPodcastEpDownload,
-    ConfigPodcastMarkAllPlayed, ConfigPodcastMarkPlayed, ConfigPodcastRefreshAllFeeds,
-    ConfigPodcastRefreshFeed, ConfigPodcastSearchAddFeed, ConfigProgressBackground,
+    ConfigPodcastDeleteFeed, ConfigPodcastDownloadFailed, ConfigPodcastEpDeleteFile,
+    ConfigPodcastEpDownload, ConfigPodcastFeedFilter, ConfigPodcastFeedPolicy,
+    ConfigPodcastMarkAllPlayed, ConfigPodcastMarkPlayed, ConfigPodcastQueueFeed,
+    ConfigPodcastQueueShow, ConfigPodcastRefreshAllFeeds, ConfigPodcastRefreshFeed,
+    ConfigPodcastSearchAddFeed, ConfigPodcastSearchTranscripts, ConfigProgressBackground,
     ConfigProgressBorder, ConfigProgressForeground, ConfigProgressTitle, ConfigSavePopup,
     ConfigSeekStep, ExitConfirmation, Footer, GlobalListener, KillDaemon, MusicDir, PlayerPort,
     PlayerUseDiscord, PlayerUseMpris, PlaylistDisplaySymbol, PlaylistRandomAlbum,
@@ -779,6 +783,36 @@ impl Model {
             Ok(State::One(_)) => 3,
             _ => 8,
         };
+        let select_global_layout_browse = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalLayoutBrowse),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_lyric_toggle_scroll = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalLyricToggleScroll),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_lyric_seek_line = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalLyricSeekLine),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_next_chapter = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerNextChapter),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_previous_chapter = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::GlobalPlayerPreviousChapter),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
         assert!(self
             .terminal
@@ -881,9 +915,11 @@ impl Model {
                             Constraint::Length(select_global_xywh_zoom_in),
                             Constraint::Length(select_global_xywh_zoom_out),
                             Constraint::Length(select_global_xywh_hide),
-                            // Constraint::Length(select_global_xywh_hide),
-                            // Constraint::Length(select_global_xywh_hide),
-                            // Constraint::Length(select_global_xywh_hide),
+                            Constraint::Length(select_global_layout_browse),
+                            Constraint::Length(select_global_lyric_toggle_scroll),
+                            Constraint::Length(select_global_lyric_seek_line),
+                            Constraint::Length(select_global_player_next_chapter),
+                            Constraint::Length(select_global_player_previous_chapter),
                             Constraint::Min(0),
                         ]
                         .as_ref(),
@@ -1064,6 +1100,31 @@ impl Model {
                     f,
                     chunks_middle_column4[5],
                 );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalLayoutBrowse)),
+                    f,
+                    chunks_middle_column4[6],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalLyricToggleScroll)),
+                    f,
+                    chunks_middle_column4[7],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalLyricSeekLine)),
+                    f,
+                    chunks_middle_column4[8],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerNextChapter)),
+                    f,
+                    chunks_middle_column4[9],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerPreviousChapter)),
+                    f,
+                    chunks_middle_column4[10],
+                );
                 Self::view_config_editor_commons(f, &mut self.app);
             })
             .is_ok());
@@ -1261,6 +1322,63 @@ impl Model {
             _ => 8,
         };
 
+        let database_stats_len = match self
+            .app
+            .state(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::DatabaseStats)))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let library_organize_len = match self.app.state(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::LibraryOrganize,
+        ))) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let podcast_feed_policy_len = match self.app.state(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PodcastFeedPolicy,
+        ))) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let podcast_search_transcripts_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PodcastSearchTranscripts),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let podcast_queue_feed_len = match self.app.state(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PodcastQueueFeed,
+        ))) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let podcast_queue_show_len = match self.app.state(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PodcastQueueShow,
+        ))) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let podcast_feed_filter_len = match self.app.state(&Id::ConfigEditor(IdConfigEditor::Key(
+            IdKey::PodcastFeedFilter,
+        ))) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
+        let podcast_download_failed_len = match self.app.state(&Id::ConfigEditor(
+            IdConfigEditor::Key(IdKey::PodcastDownloadFailed),
+        )) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+
         let podcast_refresh_feed_len = match self.app.state(&Id::ConfigEditor(IdConfigEditor::Key(
             IdKey::PodcastRefreshFeed,
         ))) {
@@ -1373,6 +1491,14 @@ impl Model {
                             Constraint::Length(podcast_refresh_feed_len),
                             Constraint::Length(podcast_refresh_all_feeds_len),
                             Constraint::Length(podcast_search_add_feed_len),
+                            Constraint::Length(database_stats_len),
+                            Constraint::Length(library_organize_len),
+                            Constraint::Length(podcast_feed_policy_len),
+                            Constraint::Length(podcast_search_transcripts_len),
+                            Constraint::Length(podcast_queue_feed_len),
+                            Constraint::Length(podcast_queue_show_len),
+                            Constraint::Length(podcast_feed_filter_len),
+                            Constraint::Length(podcast_download_failed_len),
                             // Constraint::Length(podcast_mark_played_len),
                             // Constraint::Length(podcast_mark_all_played_len),
                             // Constraint::Length(podcast_ep_download_len),
@@ -1544,6 +1670,46 @@ impl Model {
                     f,
                     chunks_middle_column4[3],
                 );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::DatabaseStats)),
+                    f,
+                    chunks_middle_column4[4],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::LibraryOrganize)),
+                    f,
+                    chunks_middle_column4[5],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastFeedPolicy)),
+                    f,
+                    chunks_middle_column4[6],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastSearchTranscripts)),
+                    f,
+                    chunks_middle_column4[7],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastQueueFeed)),
+                    f,
+                    chunks_middle_column4[8],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastQueueShow)),
+                    f,
+                    chunks_middle_column4[9],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastFeedFilter)),
+                    f,
+                    chunks_middle_column4[10],
+                );
+                self.app.view(
+                    &Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastDownloadFailed)),
+                    f,
+                    chunks_middle_column4[11],
+                );
                 Self::view_config_editor_commons(f, &mut self.app);
             })
             .is_ok());
@@ -2367,6 +2533,46 @@ impl Model {
                 vec![],
             )
             .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalLayoutBrowse)),
+                Box::new(ConfigGlobalLayoutBrowse::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalLyricToggleScroll)),
+                Box::new(ConfigGlobalLyricToggleScroll::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalLyricSeekLine)),
+                Box::new(ConfigGlobalLyricSeekLine::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerNextChapter)),
+                Box::new(ConfigGlobalPlayerNextChapter::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::GlobalPlayerPreviousChapter)),
+                Box::new(ConfigGlobalPlayerPreviousChapter::new(config)),
+                vec![],
+            )
+            .is_ok());
         assert!(self
             .app
             .remount(
@@ -2439,6 +2645,70 @@ impl Model {
                 vec![],
             )
             .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::DatabaseStats)),
+                Box::new(ConfigDatabaseStats::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::LibraryOrganize)),
+                Box::new(ConfigLibraryOrganize::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastFeedPolicy)),
+                Box::new(ConfigPodcastFeedPolicy::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastSearchTranscripts)),
+                Box::new(ConfigPodcastSearchTranscripts::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastQueueFeed)),
+                Box::new(ConfigPodcastQueueFeed::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastQueueShow)),
+                Box::new(ConfigPodcastQueueShow::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastFeedFilter)),
+                Box::new(ConfigPodcastFeedFilter::new(config)),
+                vec![],
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::ConfigEditor(IdConfigEditor::Key(IdKey::PodcastDownloadFailed)),
+                Box::new(ConfigPodcastDownloadFailed::new(config)),
+                vec![],
+            )
+            .is_ok());
         self.theme_select_sync();
     }
 
@@ -2884,6 +3154,31 @@ impl Model {
                 IdKey::GlobalXywhHide,
             )))
             .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalLayoutBrowse,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalLyricToggleScroll,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalLyricSeekLine,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalPlayerNextChapter,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::GlobalPlayerPreviousChapter,
+            )))
+            .ok();
 
         self.app
             .umount(&Id::ConfigEditor(IdConfigEditor::Key(
@@ -2930,6 +3225,44 @@ impl Model {
                 IdKey::PodcastSearchAddFeed,
             )))
             .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(IdKey::DatabaseStats)))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::LibraryOrganize,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::PodcastFeedPolicy,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::PodcastSearchTranscripts,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::PodcastQueueFeed,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::PodcastQueueShow,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::PodcastFeedFilter,
+            )))
+            .ok();
+        self.app
+            .umount(&Id::ConfigEditor(IdConfigEditor::Key(
+                IdKey::PodcastDownloadFailed,
+            )))
+            .ok();
         assert!(self
             .app
             .remount(
@@ -1,10 +1,14 @@
 use crate::ui::Model;
+use anyhow::{anyhow, bail, Result};
 use std::path::Path;
+use std::time::Duration;
 use termusiclib::config::{Keys, Settings};
-use termusiclib::sqlite::SearchCriteria;
+use termusiclib::duplicates::{group_tracks, refine_by_audio_hash};
+use termusiclib::sqlite::{SearchCriteria, TrackForDB};
 use termusiclib::types::{DBMsg, Id, Msg};
 use termusiclib::utils::{is_playlist, playlist_get_vec};
 use tui_realm_stdlib::List;
+use tui_realm_treeview::TREE_INITIAL_NODE;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::props::{Alignment, BorderType, TableBuilder, TextSpan};
 use tuirealm::props::{Borders, Color};
@@ -13,6 +17,9 @@ use tuirealm::{
     AttrValue, Attribute, Component, Event, MockComponent, State, StateValue,
 };
 
+/// Maximum number of ranked results shown in the database search popup
+const DB_SEARCH_LIMIT: usize = 200;
+
 #[derive(MockComponent)]
 pub struct DBListCriteria {
     component: List,
@@ -61,6 +68,8 @@ impl DBListCriteria {
                     TableBuilder::default()
                         .add_col(TextSpan::from("Artist"))
                         .add_row()
+                        .add_col(TextSpan::from("Album Artist"))
+                        .add_row()
                         .add_col(TextSpan::from("Album"))
                         .add_row()
                         .add_col(TextSpan::from("Genre"))
@@ -68,6 +77,8 @@ impl DBListCriteria {
                         .add_col(TextSpan::from("Directory"))
                         .add_row()
                         .add_col(TextSpan::from("Playlists"))
+                        .add_row()
+                        .add_col(TextSpan::from("Duplicates"))
                         .build(),
                 ),
             on_key_tab,
@@ -154,6 +165,9 @@ impl Component<Msg, NoUserEvent> for DBListCriteria {
             Event::Keyboard(keyevent) if keyevent == self.keys.library_search.key_event() => {
                 return Some(Msg::GeneralSearch(crate::ui::GSMsg::PopupShowDatabase))
             }
+            Event::Keyboard(keyevent) if keyevent == self.keys.database_stats.key_event() => {
+                return Some(Msg::StatsPopupShow)
+            }
             _ => CmdResult::None,
         };
         Some(Msg::None)
@@ -305,6 +319,9 @@ impl Component<Msg, NoUserEvent> for DBListSearchResult {
             Event::Keyboard(keyevent) if keyevent == self.keys.library_search.key_event() => {
                 return Some(Msg::GeneralSearch(crate::ui::GSMsg::PopupShowDatabase))
             }
+            Event::Keyboard(keyevent) if keyevent == self.keys.database_stats.key_event() => {
+                return Some(Msg::StatsPopupShow)
+            }
 
             _ => CmdResult::None,
         };
@@ -438,10 +455,24 @@ impl Component<Msg, NoUserEvent> for DBListSearchTracks {
             Event::Keyboard(keyevent) if keyevent == self.keys.database_add_all.key_event() => {
                 return Some(Msg::DataBase(DBMsg::AddAllToPlaylist))
             }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.library_tag_editor_open.key_event() =>
+            {
+                return Some(Msg::DataBase(DBMsg::TagEditorRunAll))
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.library_delete.key_event() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::DataBase(DBMsg::DeleteTrack(index)));
+                }
+                CmdResult::None
+            }
 
             Event::Keyboard(keyevent) if keyevent == self.keys.library_search.key_event() => {
                 return Some(Msg::GeneralSearch(crate::ui::GSMsg::PopupShowDatabase))
             }
+            Event::Keyboard(keyevent) if keyevent == self.keys.database_stats.key_event() => {
+                return Some(Msg::StatsPopupShow)
+            }
 
             _ => CmdResult::None,
         };
@@ -458,10 +489,19 @@ impl Model {
                 table.add_row();
             }
 
+            let display_name = if self.db_criteria == SearchCriteria::Duplicates {
+                let bitrate = record
+                    .bitrate
+                    .map_or_else(|| "?".to_string(), |b| b.to_string());
+                format!("[{bitrate:>4}kbps {:<4}] {}", record.ext, record.file)
+            } else {
+                record.name.to_string()
+            };
+
             table
                 .add_col(TextSpan::from(format!("{}", idx + 1)))
                 .add_col(TextSpan::from(" "))
-                .add_col(TextSpan::from(record.name.to_string()));
+                .add_col(TextSpan::from(display_name));
         }
         if self.db_search_results.is_empty() {
             table.add_col(TextSpan::from("empty results"));
@@ -540,6 +580,10 @@ impl Model {
             SearchCriteria::Playlist => {
                 self.db_search_results = self.database_get_playlist();
             }
+            SearchCriteria::Duplicates => {
+                self.db_search_results = vec![];
+                self.database_find_duplicates();
+            }
             _ => {
                 if let Ok(results) = self.db.get_criterias(&self.db_criteria) {
                     self.db_search_results = results;
@@ -582,6 +626,11 @@ impl Model {
                     }
                 }
             }
+            SearchCriteria::Duplicates => {
+                if let Some(group) = self.db_duplicate_groups.get(index) {
+                    self.db_search_tracks = group.clone();
+                }
+            }
             _ => {
                 if let Ok(vec) = self
                     .db
@@ -596,6 +645,96 @@ impl Model {
         self.app.active(&Id::DBListSearchTracks).ok();
     }
 
+    fn database_find_duplicates(&mut self) {
+        let records = match self.db.get_all_records() {
+            Ok(records) => records,
+            Err(e) => {
+                self.mount_error_popup(format!("find duplicates error: {e}"));
+                return;
+            }
+        };
+        let tolerance = Duration::from_secs(self.config.library_duplicate_duration_tolerance);
+        let compare_audio = self.config.library_duplicate_compare_audio;
+        let tx = self.tx_to_main.clone();
+        self.show_message_timeout_label_help("Searching for duplicates...", None, None, None);
+        std::thread::spawn(move || {
+            let mut groups = group_tracks(records, tolerance);
+            if compare_audio {
+                groups = refine_by_audio_hash(groups);
+            }
+            tx.send(Msg::DataBase(DBMsg::DuplicatesFound(groups))).ok();
+        });
+    }
+
+    pub fn database_show_duplicates(&mut self, groups: Vec<Vec<TrackForDB>>) {
+        if self.db_criteria != SearchCriteria::Duplicates {
+            return;
+        }
+        self.show_message_timeout_label_help(
+            format!("{} groups of duplicates found", groups.len()),
+            None,
+            None,
+            None,
+        );
+        self.db_search_results = groups
+            .iter()
+            .filter_map(|g| g.first())
+            .zip(groups.iter().map(Vec::len))
+            .map(|(r, len)| format!("{} - {} ({len})", r.artist, r.title))
+            .collect();
+        self.db_duplicate_groups = groups;
+        self.database_sync_results();
+    }
+
+    /// Select the track in the library tree and reuse its delete confirmation, so the
+    /// file goes through `library_delete_song` like any other deletion.
+    pub fn database_before_delete(&mut self, index: usize) -> Result<()> {
+        let file = self
+            .db_search_tracks
+            .get(index)
+            .ok_or_else(|| anyhow!("no track at index {index}"))?
+            .file
+            .clone();
+        self.app.attr(
+            &Id::Library,
+            Attribute::Custom(TREE_INITIAL_NODE),
+            AttrValue::String(file.clone()),
+        )?;
+        match self.app.state(&Id::Library) {
+            Ok(State::One(StateValue::String(node_id))) if node_id == file => {
+                self.mount_confirm_radio();
+                Ok(())
+            }
+            _ => bail!("{file} is not in the library tree"),
+        }
+    }
+
+    pub fn database_after_delete(&mut self) {
+        let (kept, deleted): (Vec<_>, Vec<_>) = self
+            .db_search_tracks
+            .drain(..)
+            .partition(|t| Path::new(&t.file).exists());
+        self.db_search_tracks = kept;
+        for track in deleted {
+            if let Err(e) = self.db.delete_record_by_path(&track.file) {
+                self.mount_error_popup(format!("delete db record error: {e}"));
+            }
+        }
+
+        if self.db_criteria == SearchCriteria::Duplicates {
+            for group in &mut self.db_duplicate_groups {
+                group.retain(|t| Path::new(&t.file).exists());
+            }
+            let groups: Vec<_> = self
+                .db_duplicate_groups
+                .drain(..)
+                .filter(|g| g.len() > 1)
+                .collect();
+            self.database_show_duplicates(groups);
+        }
+        self.database_sync_tracks();
+    }
+
     #[allow(unused)]
     pub fn database_reload(&mut self) {
         assert!(self
@@ -644,35 +783,20 @@ impl Model {
 
     pub fn database_update_search(&mut self, input: &str) {
         let mut table: TableBuilder = TableBuilder::default();
-        let mut idx = 0;
-        let search = format!("*{}*", input.to_lowercase());
-        let mut db_tracks = vec![];
-        if let Ok(tracks) = self.db.get_all_records() {
-            db_tracks = tracks.clone();
-            for record in tracks {
-                if wildmatch::WildMatch::new(&search).matches(&record.artist.to_lowercase())
-                    | wildmatch::WildMatch::new(&search).matches(&record.title.to_lowercase())
-                {
-                    if idx > 0 {
-                        table.add_row();
-                    }
-
-                    let duration =
-                        termusiclib::track::Track::duration_formatted_short(&record.duration);
-                    let duration_string = format!("[{duration:^6.6}]");
-
-                    table
-                        .add_col(TextSpan::new(duration_string.as_str()))
-                        .add_col(
-                            TextSpan::new(record.artist)
-                                .fg(tuirealm::tui::style::Color::LightYellow),
-                        )
-                        .add_col(TextSpan::new(record.title).bold())
-                        .add_col(TextSpan::new(record.file));
-                    // .add_col(TextSpan::new(record.album().unwrap_or("Unknown Album")));
-                    idx += 1;
-                }
+        let db_tracks = self.db.search(input, DB_SEARCH_LIMIT).unwrap_or_default();
+        for (idx, record) in db_tracks.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
             }
+
+            let duration = termusiclib::track::Track::duration_formatted_short(&record.duration);
+            let duration_string = format!("[{duration:^6.6}]");
+
+            table
+                .add_col(TextSpan::new(duration_string.as_str()))
+                .add_col(TextSpan::new(&record.artist).fg(tuirealm::tui::style::Color::LightYellow))
+                .add_col(TextSpan::new(&record.title).bold())
+                .add_col(TextSpan::new(&record.file));
         }
 
         if db_tracks.is_empty() {
@@ -26,6 +26,8 @@ use super::{GSMsg, Id, Msg};
 use crate::config::{Keys, Settings};
 use crate::ui::Model;
 use anyhow::{anyhow, Result};
+use termusiclib::track::Track;
+use termusicplayback::PlayerCmd;
 use tui_realm_stdlib::{Input, Table};
 use tui_realm_treeview::TREE_INITIAL_NODE;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
@@ -66,7 +68,14 @@ impl GSInputPopup {
                         .modifiers(BorderType::Rounded),
                 )
                 .input_type(InputType::Text)
-                .title("Search for: (support * and ?)", Alignment::Left),
+                .title(
+                    match source {
+                        Source::Database => "Search for: (fuzzy match)",
+                        Source::Library | Source::Playlist => "Search for: (support * and ?)",
+                        Source::Transcript => "Search transcripts for:",
+                    },
+                    Alignment::Left,
+                ),
             source,
         }
     }
@@ -120,6 +129,9 @@ impl Component<Msg, NoUserEvent> for GSInputPopup {
                 Source::Database => {
                     Some(Msg::GeneralSearch(GSMsg::PopupUpdateDatabase(input_string)))
                 }
+                Source::Transcript => Some(Msg::GeneralSearch(GSMsg::PopupUpdateTranscript(
+                    input_string,
+                ))),
             },
             CmdResult::Submit(_) => Some(Msg::GeneralSearch(GSMsg::InputBlur)),
 
@@ -138,6 +150,7 @@ pub enum Source {
     Library,
     Playlist,
     Database,
+    Transcript,
 }
 impl GSTablePopup {
     #[allow(clippy::too_many_lines)]
@@ -151,6 +164,10 @@ impl GSTablePopup {
             config.keys.global_right
         );
         let title_database = format!("Results:( {}: load to playlist)", config.keys.global_right);
+        let title_transcript = format!(
+            "Results:(Enter: locate/{}: load to playlist)",
+            config.keys.global_right
+        );
         match source {
             Source::Library => Self {
                 component: Table::default()
@@ -297,6 +314,54 @@ impl GSTablePopup {
                 source,
                 keys: config.keys.clone(),
             },
+            Source::Transcript => Self {
+                component: Table::default()
+                    .borders(
+                        Borders::default()
+                            .color(
+                                config
+                                    .style_color_symbol
+                                    .library_border()
+                                    .unwrap_or(Color::Magenta),
+                            )
+                            .modifiers(BorderType::Rounded),
+                    )
+                    .background(
+                        config
+                            .style_color_symbol
+                            .library_background()
+                            .unwrap_or(Color::Reset),
+                    )
+                    .foreground(
+                        config
+                            .style_color_symbol
+                            .library_foreground()
+                            .unwrap_or(Color::Magenta),
+                    )
+                    .title(title_transcript, Alignment::Left)
+                    .scroll(true)
+                    .highlighted_color(
+                        config
+                            .style_color_symbol
+                            .library_highlight()
+                            .unwrap_or(Color::LightBlue),
+                    )
+                    .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                    .rewind(false)
+                    .step(4)
+                    .row_height(1)
+                    .headers(&["Time", "Episode", "Line"])
+                    .column_spacing(3)
+                    .widths(&[12, 30, 58])
+                    .table(
+                        TableBuilder::default()
+                            .add_col(TextSpan::from("Empty result."))
+                            .add_col(TextSpan::from("Type to search."))
+                            .build(),
+                    ),
+                source,
+                keys: config.keys.clone(),
+            },
         }
     }
 }
@@ -356,6 +421,9 @@ impl Component<Msg, NoUserEvent> for GSTablePopup {
                     Source::Database => {
                         return Some(Msg::GeneralSearch(GSMsg::PopupCloseDatabaseAddPlaylist))
                     }
+                    Source::Transcript => {
+                        return Some(Msg::GeneralSearch(GSMsg::PopupCloseTranscriptAddPlaylist))
+                    }
                 }
             }
             Event::Keyboard(KeyEvent {
@@ -368,6 +436,9 @@ impl Component<Msg, NoUserEvent> for GSTablePopup {
                     return Some(Msg::GeneralSearch(GSMsg::PopupCloseOkPlaylistLocate))
                 }
                 Source::Database => return Some(Msg::GeneralSearch(GSMsg::PopupCloseCancel)),
+                Source::Transcript => {
+                    return Some(Msg::GeneralSearch(GSMsg::PopupCloseOkTranscriptLocate))
+                }
             },
             _ => CmdResult::None,
         };
@@ -479,6 +550,56 @@ impl Model {
         self.playlist_play_selected(index);
     }
 
+    /// Episode id and time stamp kept in the hidden columns of a transcript result
+    fn general_search_transcript_selected(&self) -> Option<(i64, i64)> {
+        let Ok(State::One(StateValue::Usize(index))) = self.app.state(&Id::GeneralSearchTable)
+        else {
+            return None;
+        };
+        let Ok(Some(AttrValue::Table(table))) =
+            self.app.query(&Id::GeneralSearchTable, Attribute::Content)
+        else {
+            return None;
+        };
+        let line = table.get(index)?;
+        let episode_id = line.get(3)?.content.parse().ok()?;
+        let time_stamp = line.get(4)?.content.parse().ok()?;
+        Some((episode_id, time_stamp))
+    }
+
+    /// Select the episode of a transcript result, and seek to the line when the episode
+    /// is playing
+    pub fn general_search_after_transcript_locate(&mut self) {
+        let Some((episode_id, time_stamp)) = self.general_search_transcript_selected() else {
+            return;
+        };
+        let Some(url) = self.podcast_locate_episode(episode_id) else {
+            return;
+        };
+        let playing = self
+            .current_song
+            .as_ref()
+            .and_then(Track::file)
+            .map_or(false, |file| file == url);
+        if playing {
+            self.command(&PlayerCmd::SeekTo(
+                u32::try_from(time_stamp / 1000).unwrap_or_default(),
+            ));
+        }
+    }
+
+    pub fn general_search_after_transcript_add_playlist(&mut self) -> Result<()> {
+        let Some((episode_id, _)) = self.general_search_transcript_selected() else {
+            return Ok(());
+        };
+        if self.podcast_locate_episode(episode_id).is_some() {
+            if let Ok(State::One(StateValue::Usize(index))) = self.app.state(&Id::Episode) {
+                self.playlist_add_episode(index)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn general_search_after_database_add_playlist(&mut self) -> Result<()> {
         if let Ok(State::One(StateValue::Usize(index))) = self.app.state(&Id::GeneralSearchTable) {
             if let Ok(Some(AttrValue::Table(table))) =
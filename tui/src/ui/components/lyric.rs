@@ -1,12 +1,15 @@
 use crate::ui::{model::TermusicLayout, Model};
 use termusiclib::config::Settings;
-use termusiclib::podcast::Episode;
+use termusiclib::podcast::db::Database as DBPod;
+use termusiclib::podcast::{chapters, Episode};
 use termusiclib::track::MediaType;
-use termusiclib::types::{Id, LyricMsg, Msg};
+use termusiclib::types::{Id, LyricMsg, Msg, PCMsg};
+use termusiclib::utils::{copy_to_clipboard, get_app_config_path, open_with_system};
+use termusicplayback::PlayerCmd;
 
 use anyhow::{anyhow, Result};
-use lazy_static::lazy_static;
-use regex::Regex;
+use std::time::Duration;
+use termusiclib::podcast::notes::Notes;
 use tui_realm_stdlib::Textarea;
 // use tui_realm_textarea::TextArea;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
@@ -14,24 +17,51 @@ use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
 use tuirealm::props::{
     Alignment, AttrValue, Attribute, BorderType, Borders, Color, PropPayload, PropValue, TextSpan,
 };
-use tuirealm::{Component, Event, MockComponent, State, StateValue};
+use tuirealm::tui::layout::Rect;
+use tuirealm::tui::style::{Modifier, Style};
+use tuirealm::tui::text::{Span, Spans};
+use tuirealm::tui::widgets::{Paragraph, Wrap};
+use tuirealm::{Component, Event, Frame, MockComponent, State, StateValue};
 
-lazy_static! {
-    /// Regex for finding <br/> tags -- also captures any surrounding
-    /// line breaks
-    static ref RE_BR_TAGS: Regex = Regex::new(r"((\r\n)|\r|\n)*<br */?>((\r\n)|\r|\n)*").expect("Regex error");
+/// Lines shown before and after the one being sung, unless `lyric_scroll` shows them all
+const LYRIC_CONTEXT_LINES: usize = 2;
 
-    /// Regex for finding HTML tags
-    static ref RE_HTML_TAGS: Regex = Regex::new(r"<[^<>]*>").expect("Regex error");
+/// Lines skipped by page up and down
+const LYRIC_PAGE_STEP: usize = 4;
 
-    /// Regex for finding more than two line breaks
-    static ref RE_MULT_LINE_BREAKS: Regex = Regex::new(r"((\r\n)|\r|\n){3,}").expect("Regex error");
-}
+/// Karaoke state of `Lyric`: the row being sung, chars of it already sung and the length
+/// of the current word
+const KARAOKE: Attribute = Attribute::Custom("karaoke");
 
-#[derive(MockComponent)]
 pub struct Lyric {
     component: Textarea,
     keys: crate::config::Keys,
+    /// Set after the text rows, new text clears it again. Without it the rows are a plain
+    /// textarea, as used for podcast details and radio titles.
+    karaoke: Option<(usize, usize, usize)>,
+    /// Row picked with up and down to seek to, the view follows it instead of the current row
+    selected: Option<usize>,
+    /// Row the textarea has the cursor on, which it does not tell itself
+    row: usize,
+}
+
+/// Episode notes as the lyric panel shows them, to act on the row picked there
+#[derive(Default)]
+pub struct NotesView {
+    url: String,
+    /// For each row, where it seeks to and the link it opens
+    rows: Vec<(Option<Duration>, Option<String>)>,
+}
+
+/// What the lyric panel last showed in karaoke mode
+#[derive(PartialEq, Eq)]
+pub struct KaraokeView {
+    /// Index of the first row in `unsynced_captions`
+    first: usize,
+    rows: Vec<String>,
+    current: usize,
+    sung: usize,
+    word: usize,
 }
 
 impl Lyric {
@@ -62,13 +92,203 @@ impl Lyric {
                 )
                 .title(" Lyrics ", Alignment::Left)
                 // .wrap(true)
-                .step(4)
+                .step(LYRIC_PAGE_STEP)
                 .highlighted_str(&config.style_color_symbol.playlist_highlight_symbol)
                 .text_rows(&[TextSpan::new(format!(
                     "{}.",
                     termusicplayback::Status::Stopped
                 ))]),
             keys: config.keys.clone(),
+            karaoke: None,
+            selected: None,
+            row: 0,
+        }
+    }
+
+    fn rows(&self) -> Vec<String> {
+        match self.component.query(Attribute::Text) {
+            Some(AttrValue::Payload(PropPayload::Vec(spans))) => spans
+                .into_iter()
+                .map(|s| s.unwrap_text_span().content)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn view_karaoke(&self, render: &mut Frame<'_>, area: Rect, karaoke: (usize, usize, usize)) {
+        let (current, sung, word) = karaoke;
+        let rows = self.rows();
+        let foreground = self
+            .component
+            .query(Attribute::Foreground)
+            .map_or(Color::Reset, AttrValue::unwrap_color);
+        let background = self
+            .component
+            .query(Attribute::Background)
+            .map_or(Color::Reset, AttrValue::unwrap_color);
+        let borders = self
+            .component
+            .query(Attribute::Borders)
+            .map_or_else(Borders::default, AttrValue::unwrap_borders);
+        let title = self
+            .component
+            .query(Attribute::Title)
+            .map(AttrValue::unwrap_title);
+        let focus = self
+            .component
+            .query(Attribute::Focus)
+            .map_or(false, AttrValue::unwrap_flag);
+        let block = tui_realm_stdlib::utils::get_block(borders, title, focus, None);
+
+        // keep the line being sung, or the one picked, in the middle. Heights of wrapped rows
+        // are estimated, which is close enough for lyrics.
+        let inner = block.inner(area);
+        let width = usize::from(inner.width).max(1);
+        let focus_row = self.selected.unwrap_or(current);
+        let above: usize = rows
+            .iter()
+            .take(focus_row)
+            .map(|r| unicode_width::UnicodeWidthStr::width(r.as_str()) / width + 1)
+            .sum();
+        let middle = usize::from(inner.height) / 2;
+        let mut lines = vec![Spans::default(); middle.saturating_sub(above)];
+        for (i, row) in rows.iter().enumerate() {
+            let mut spans = if i == current {
+                karaoke_spans(row, sung, word)
+            } else {
+                Spans::from(Span::styled(
+                    row.clone(),
+                    Style::default().add_modifier(Modifier::DIM),
+                ))
+            };
+            if self.selected == Some(i) {
+                for span in &mut spans.0 {
+                    span.style = span.style.add_modifier(Modifier::REVERSED);
+                }
+            }
+            lines.push(spans);
+        }
+
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .scroll((
+                u16::try_from(above.saturating_sub(middle)).unwrap_or(u16::MAX),
+                0,
+            ))
+            .style(Style::default().fg(foreground).bg(background));
+        render.render_widget(paragraph, area);
+    }
+
+    /// Moves the textarea, following the row it is on
+    fn perform_text(&mut self, cmd: Cmd) -> CmdResult {
+        let last = self.rows().len().saturating_sub(1);
+        self.row = match cmd {
+            Cmd::Move(Direction::Down) => self.row + 1,
+            Cmd::Move(Direction::Up) => self.row.saturating_sub(1),
+            Cmd::Scroll(Direction::Down) => self.row + LYRIC_PAGE_STEP,
+            Cmd::Scroll(Direction::Up) => self.row.saturating_sub(LYRIC_PAGE_STEP),
+            Cmd::GoTo(Position::Begin) => 0,
+            Cmd::GoTo(Position::End) => last,
+            _ => self.row,
+        }
+        .min(last);
+        self.component.perform(cmd)
+    }
+
+    fn perform_karaoke(&mut self, current: usize, cmd: Cmd) -> CmdResult {
+        let last = self.rows().len().saturating_sub(1);
+        let selected = self.selected.unwrap_or(current);
+        self.selected = Some(match cmd {
+            Cmd::Move(Direction::Down) => selected + 1,
+            Cmd::Move(Direction::Up) => selected.saturating_sub(1),
+            Cmd::Scroll(Direction::Down) => selected + LYRIC_PAGE_STEP,
+            Cmd::Scroll(Direction::Up) => selected.saturating_sub(LYRIC_PAGE_STEP),
+            Cmd::GoTo(Position::Begin) => 0,
+            Cmd::GoTo(Position::End) => last,
+            _ => return CmdResult::None,
+        })
+        .map(|s| s.min(last));
+        CmdResult::Changed(self.state())
+    }
+}
+
+/// Sung part in bold and the word being sung underlined. Lines without word timing are
+/// one word, those are only made bold.
+fn karaoke_spans(row: &str, sung: usize, word: usize) -> Spans<'static> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut chars = row.chars();
+    let sung_text: String = chars.by_ref().take(sung).collect();
+    let word_text: String = chars.by_ref().take(word).collect();
+    let rest: String = chars.collect();
+    let word_style = if sung == 0 && rest.is_empty() {
+        bold
+    } else {
+        bold.add_modifier(Modifier::UNDERLINED)
+    };
+    Spans::from(vec![
+        Span::styled(sung_text, bold),
+        Span::styled(word_text, word_style),
+        Span::raw(rest),
+    ])
+}
+
+impl MockComponent for Lyric {
+    fn view(&mut self, render: &mut Frame<'_>, area: Rect) {
+        match self.karaoke {
+            Some(karaoke) => self.view_karaoke(render, area, karaoke),
+            None => self.component.view(render, area),
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            attr if attr == KARAOKE => {
+                self.karaoke = match value {
+                    AttrValue::Payload(PropPayload::Tup3((
+                        PropValue::Usize(current),
+                        PropValue::Usize(sung),
+                        PropValue::Usize(word),
+                    ))) => Some((current, sung, word)),
+                    _ => None,
+                };
+            }
+            Attribute::Text => {
+                // another lyric or no lyric at all, the picked row means nothing there
+                let mut other = false;
+                if let AttrValue::Payload(PropPayload::Vec(rows)) = &value {
+                    other = rows.len() != self.rows().len();
+                }
+                self.karaoke = None;
+                self.component.attr(attr, value);
+                if other {
+                    self.selected = None;
+                    self.row = 0;
+                    self.component.perform(Cmd::GoTo(Position::Begin));
+                }
+            }
+            attr => self.component.attr(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        match self.karaoke {
+            Some((current, _, _)) => {
+                State::One(StateValue::Usize(self.selected.unwrap_or(current)))
+            }
+            None => State::One(StateValue::Usize(self.row)),
+        }
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        match self.karaoke {
+            Some((current, _, _)) => self.perform_karaoke(current, cmd),
+            None => self.perform_text(cmd),
         }
     }
 }
@@ -122,6 +342,23 @@ impl Component<Msg, NoUserEvent> for Lyric {
             Event::Keyboard(key) if key == self.keys.global_goto_bottom.key_event() => {
                 self.perform(Cmd::GoTo(Position::End))
             }
+
+            Event::Keyboard(key) if key == self.keys.global_lyric_seek_line.key_event() => {
+                if let State::One(StateValue::Usize(row)) = self.state() {
+                    if self.karaoke.is_some() {
+                        self.selected = None;
+                        return Some(Msg::LyricMessage(LyricMsg::LyricSeekLine(row)));
+                    }
+                    return Some(Msg::Podcast(PCMsg::NotesSelect(row)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(key) if key == self.keys.library_yank.key_event() => {
+                if let (None, State::One(StateValue::Usize(row))) = (self.karaoke, self.state()) {
+                    return Some(Msg::Podcast(PCMsg::NotesCopyLink(row)));
+                }
+                CmdResult::None
+            }
             _ => CmdResult::None,
         };
         Some(Msg::None)
@@ -139,6 +376,17 @@ impl Model {
         self.lyric_set_lyric(&lyric_line);
     }
 
+    /// Playback position in milliseconds. The player reports whole seconds, the rest is
+    /// estimated from the time since the last second started.
+    pub fn lyric_position(&self) -> i64 {
+        let mut position = self.time_pos * 1000;
+        if !self.playlist.is_paused() {
+            position += i64::try_from(self.time_pos_updated.elapsed().as_millis().min(999))
+                .unwrap_or_default();
+        }
+        position
+    }
+
     pub fn lyric_update_for_podcast_by_current_track(&mut self) {
         let mut need_update = false;
         let mut pod_title = String::new();
@@ -190,59 +438,83 @@ impl Model {
     }
 
     pub fn lyric_update_for_episode_after(&mut self, po_title: &str, ep: &Episode) {
-        // convert <br/> tags to a single line break
-        let br_to_lb = RE_BR_TAGS.replace_all(&ep.description, "\n");
-
-        // strip all HTML tags
-        let stripped_tags = RE_HTML_TAGS.replace_all(&br_to_lb, "");
-
-        // convert HTML entities (e.g., &amp;)
-        let decoded = match escaper::decode_html(&stripped_tags) {
-            Err(_) => stripped_tags.to_string(),
-            Ok(s) => s,
-        };
-
-        // remove anything more than two line breaks (i.e., one blank line)
-        let no_line_breaks = RE_MULT_LINE_BREAKS.replace_all(&decoded, "\n\n");
+        let notes = Notes::parse(&ep.description);
 
         let (term_width, _) = viuer::terminal_size();
         let term_width = usize::from(term_width);
         let lyric_width = term_width * 3 / 5;
-        let lines_vec: Vec<_> = no_line_breaks.split('\n').collect();
-        let mut short_string_vec: Vec<_> = Vec::new();
-        for l in lines_vec {
-            let unicode_width = unicode_width::UnicodeWidthStr::width(l);
-            if unicode_width > lyric_width {
-                let mut string_tmp = textwrap::wrap(l, lyric_width);
-                short_string_vec.append(&mut string_tmp);
-            } else {
-                short_string_vec.push(std::borrow::Cow::Borrowed(l));
-            }
-        }
-
-        let mut lines_textspan: Vec<_> = short_string_vec
-            .into_iter()
-            .map(|l| PropValue::TextSpan(TextSpan::from(l)))
-            .collect();
 
+        // what each row leads to, a point of the episode or a link
+        let mut rows: Vec<(Option<Duration>, Option<String>)> = Vec::new();
         let mut final_vec: Vec<_> = Vec::new();
-        final_vec.push(PropValue::TextSpan(TextSpan::from(po_title).bold()));
-        final_vec.push(PropValue::TextSpan(TextSpan::from(&ep.title).bold()));
-        final_vec.push(PropValue::TextSpan(TextSpan::from("   ")));
+        let mut push = |span: TextSpan, seek: Option<Duration>, link: Option<String>| {
+            final_vec.push(PropValue::TextSpan(span));
+            rows.push((seek, link));
+        };
+        push(TextSpan::from(po_title).bold(), None, None);
+        push(TextSpan::from(&ep.title).bold(), None, None);
+        push(TextSpan::from("   "), None, None);
 
         if let Some(date) = ep.pubdate {
-            final_vec.push(PropValue::TextSpan(
+            push(
                 TextSpan::from(format!("Published: {}", date.format("%B %-d, %Y"))).italic(),
-            ));
+                None,
+                None,
+            );
         }
 
-        final_vec.push(PropValue::TextSpan(
+        push(
             TextSpan::from(format!("Duration: {}", ep.format_duration())).italic(),
-        ));
+            None,
+            None,
+        );
+
+        let chapters = self.lyric_chapters_for_episode(ep);
+        if !chapters.is_empty() {
+            push(TextSpan::from("   "), None, None);
+            push(TextSpan::from("Chapters:").bold(), None, None);
+            for (line, start) in chapters {
+                push(line, Some(start), None);
+            }
+        }
 
-        final_vec.push(PropValue::TextSpan(TextSpan::from("   ")));
-        final_vec.push(PropValue::TextSpan(TextSpan::from("Description:").bold()));
-        final_vec.append(&mut lines_textspan);
+        push(TextSpan::from("   "), None, None);
+        push(TextSpan::from("Description:").bold(), None, None);
+        for line in &notes.lines {
+            let link = line
+                .link
+                .and_then(|number| notes.links.get(number - 1))
+                .cloned();
+            if line.text.is_empty() {
+                push(TextSpan::from(""), None, None);
+            }
+            for row in textwrap::wrap(&line.text, lyric_width.max(1)) {
+                let row = if line.bold {
+                    TextSpan::from(row).bold()
+                } else {
+                    TextSpan::from(row)
+                };
+                push(row, line.seek, link.clone());
+            }
+        }
+
+        if !notes.links.is_empty() {
+            push(TextSpan::from("   "), None, None);
+            push(TextSpan::from("Links:").bold(), None, None);
+            for (index, link) in notes.links.iter().enumerate() {
+                push(
+                    TextSpan::from(format!("[{}] {link}", index + 1)),
+                    None,
+                    Some(link.clone()),
+                );
+            }
+        }
+
+        self.lyric_karaoke = None;
+        self.lyric_notes = NotesView {
+            url: ep.url.clone(),
+            rows,
+        };
 
         self.app
             .attr(
@@ -253,8 +525,145 @@ impl Model {
             .ok();
     }
 
+    /// Acts on a row of the episode notes: a timestamp jumps there in the playing
+    /// episode, and a link is opened
+    pub fn lyric_notes_select(&mut self, row: usize) {
+        if self.layout != TermusicLayout::Podcast || self.lyric_shows_transcript() {
+            return;
+        }
+        let Some((seek, link)) = self.lyric_notes.rows.get(row).cloned() else {
+            return;
+        };
+        if let Some(seek) = seek {
+            let playing = self
+                .playlist
+                .current_track()
+                .and_then(termusiclib::track::Track::file)
+                .map_or(false, |file| file == self.lyric_notes.url);
+            if playing && !self.playlist.is_stopped() {
+                self.command(&PlayerCmd::SeekTo(
+                    u32::try_from(seek.as_secs()).unwrap_or_default(),
+                ));
+            } else {
+                self.show_message_timeout_label_help(
+                    "Play the episode to jump to its timestamps",
+                    None,
+                    None,
+                    None,
+                );
+            }
+            return;
+        }
+        if let Some(link) = link {
+            match open_with_system(&link) {
+                Ok(()) => {
+                    self.show_message_timeout_label_help(
+                        format!("Opening {link}"),
+                        None,
+                        None,
+                        None,
+                    );
+                }
+                Err(e) => self.mount_error_popup(format!("Error opening link: {e}")),
+            }
+        }
+    }
+
+    pub fn lyric_notes_copy_link(&mut self, row: usize) {
+        if self.layout != TermusicLayout::Podcast || self.lyric_shows_transcript() {
+            return;
+        }
+        let Some((_, Some(link))) = self.lyric_notes.rows.get(row).cloned() else {
+            return;
+        };
+        match copy_to_clipboard(&link) {
+            Ok(()) => {
+                self.show_message_timeout_label_help(format!("Copied {link}"), None, None, None);
+            }
+            Err(e) => self.mount_error_popup(format!("Error copying link: {e}")),
+        }
+    }
+
+    /// One line per chapter with its start, the playing one marked. Chapters only linked
+    /// from the feed are fetched in the background, and the panel is refreshed when they
+    /// arrive.
+    fn lyric_chapters_for_episode(&mut self, ep: &Episode) -> Vec<(TextSpan, Duration)> {
+        let chapters = self.db_podcast.get_chapters(ep.id).unwrap_or_default();
+        if chapters.is_empty() {
+            if let Some(url) = &ep.chapters_url {
+                if self.podcast_chapters_requested.insert(ep.id) {
+                    let url = url.clone();
+                    let episode_id = ep.id;
+                    let tx = self.tx_to_main.clone();
+                    self.threadpool.execute(move || {
+                        let saved = chapters::fetch_json(&url).and_then(|chapters| {
+                            let db = DBPod::connect(&get_app_config_path()?)?;
+                            db.set_chapters(episode_id, &chapters)
+                        });
+                        match saved {
+                            Ok(()) => tx.send(Msg::Podcast(PCMsg::DescriptionUpdate)).ok(),
+                            Err(e) => {
+                                warn!("cannot fetch chapters from {url}: {e}");
+                                None
+                            }
+                        };
+                    });
+                }
+            }
+            return Vec::new();
+        }
+
+        let playing = self
+            .playlist
+            .current_track()
+            .and_then(termusiclib::track::Track::file)
+            .map_or(false, |file| file == ep.url);
+        let current = if playing {
+            let position = u64::try_from(self.lyric_position()).unwrap_or_default();
+            chapters::current(&chapters, std::time::Duration::from_millis(position))
+        } else {
+            None
+        };
+        chapters
+            .iter()
+            .enumerate()
+            .map(|(index, chapter)| {
+                let line = TextSpan::from(format!("{} {}", chapter.format_start(), chapter.title));
+                if current == Some(index) {
+                    (line.bold(), chapter.start)
+                } else {
+                    (line, chapter.start)
+                }
+            })
+            .collect()
+    }
+
+    /// Whether the podcast layout shows the transcript of the playing episode, which it
+    /// does while that episode is selected
+    pub fn lyric_shows_transcript(&self) -> bool {
+        if self.layout != TermusicLayout::Podcast
+            || self.lyric_transcript_hidden
+            || self.playlist.is_stopped()
+        {
+            return false;
+        }
+        let Some(song) = &self.current_song else {
+            return false;
+        };
+        if song.media_type != Some(MediaType::Podcast) || song.parsed_lyric().is_none() {
+            return false;
+        }
+        let Ok(State::One(StateValue::Usize(episode_index))) = self.app.state(&Id::Episode) else {
+            return false;
+        };
+        self.podcasts
+            .get(self.podcasts_index)
+            .and_then(|pod| pod.episodes.get(episode_index))
+            .map_or(false, |ep| song.file() == Some(ep.url.as_str()))
+    }
+
     pub fn lyric_update(&mut self) {
-        if self.layout == TermusicLayout::Podcast {
+        if self.layout == TermusicLayout::Podcast && !self.lyric_shows_transcript() {
             if let Err(e) = self.lyric_update_for_podcast() {
                 self.mount_error_popup(format!("update episode description error: {e}"));
             }
@@ -269,25 +678,90 @@ impl Model {
                 return;
             }
 
-            let mut line = String::new();
             if song.lyric_frames_is_empty() {
                 self.lyric_set_lyric("No lyrics available.");
                 return;
             }
 
-            if let Some(l) = song.parsed_lyric() {
-                if l.unsynced_captions.is_empty() {
-                    return;
-                }
-                if let Some(l) = l.get_text(self.time_pos) {
-                    line = l;
-                }
-            }
-            if self.lyric_line == line {
+            let Some(l) = song.parsed_lyric() else {
+                return;
+            };
+            let Some((index, sung, word)) = l.karaoke(self.lyric_position()) else {
+                return;
+            };
+            let (first, count) = if self.config.lyric_scroll {
+                (0, l.unsynced_captions.len())
+            } else {
+                let first = index.saturating_sub(LYRIC_CONTEXT_LINES);
+                (first, index - first + LYRIC_CONTEXT_LINES + 1)
+            };
+            let view = KaraokeView {
+                first,
+                rows: l
+                    .unsynced_captions
+                    .iter()
+                    .skip(first)
+                    .take(count)
+                    .map(|c| c.text().to_string())
+                    .collect(),
+                current: index - first,
+                sung,
+                word,
+            };
+            if self.lyric_karaoke.as_ref() == Some(&view) {
                 return;
             }
-            self.lyric_set_lyric(&line);
-            self.lyric_line = line;
+            self.lyric_line = view.rows[view.current].clone();
+            self.lyric_set_karaoke(&view);
+            self.lyric_karaoke = Some(view);
+        }
+    }
+
+    fn lyric_set_karaoke(&mut self, view: &KaraokeView) {
+        self.app
+            .attr(
+                &Id::Lyric,
+                Attribute::Text,
+                AttrValue::Payload(PropPayload::Vec(
+                    view.rows
+                        .iter()
+                        .map(|r| PropValue::TextSpan(TextSpan::from(r)))
+                        .collect(),
+                )),
+            )
+            .ok();
+        self.app
+            .attr(
+                &Id::Lyric,
+                KARAOKE,
+                AttrValue::Payload(PropPayload::Tup3((
+                    PropValue::Usize(view.current),
+                    PropValue::Usize(view.sung),
+                    PropValue::Usize(view.word),
+                ))),
+            )
+            .ok();
+        self.force_redraw();
+    }
+
+    pub fn lyric_toggle_scroll(&mut self) {
+        self.config.lyric_scroll = !self.config.lyric_scroll;
+        self.lyric_update();
+    }
+
+    /// Seek to the start of a row of the lyric panel
+    pub fn lyric_seek_line(&mut self, row: usize) {
+        let (Some(view), Some(song)) = (&self.lyric_karaoke, &self.current_song) else {
+            return;
+        };
+        let Some(l) = song.parsed_lyric() else {
+            return;
+        };
+        if let Some(caption) = l.unsynced_captions.get(view.first + row) {
+            let position = (caption.time_stamp() - l.offset).max(0) / 1000;
+            self.command(&PlayerCmd::SeekTo(
+                u32::try_from(position).unwrap_or_default(),
+            ));
         }
     }
 
@@ -308,6 +782,7 @@ impl Model {
     }
 
     fn lyric_set_lyric(&mut self, text: &str) {
+        self.lyric_karaoke = None;
         self.app
             .attr(
                 &Id::Lyric,
@@ -320,6 +795,17 @@ impl Model {
     }
 
     pub fn lyric_cycle(&mut self) {
+        let transcript = self
+            .current_song
+            .as_ref()
+            .map_or(false, |song| song.media_type == Some(MediaType::Podcast))
+            && self.layout == TermusicLayout::Podcast;
+        if transcript {
+            self.lyric_transcript_hidden = !self.lyric_transcript_hidden;
+            self.lyric_update();
+            self.lyric_update_title();
+            return;
+        }
         if let Some(track) = self.playlist.current_track_as_mut() {
             if let Ok(f) = track.cycle_lyrics() {
                 let lang_ext = f.description.clone();
@@ -351,10 +837,21 @@ impl Model {
         if let Some(track) = &self.current_song {
             match track.media_type {
                 Some(MediaType::Music) => {
-                    let artist = track.artist().unwrap_or("Unknown Artist");
-                    let title = track.title().unwrap_or("Unknown Title");
+                    // untagged files may still have them in the lrc
+                    let lyric = track.parsed_lyric();
+                    let artist = track
+                        .artist()
+                        .or_else(|| lyric.and_then(|l| l.tag("ar")))
+                        .unwrap_or("Unknown Artist");
+                    let title = track
+                        .title()
+                        .or_else(|| lyric.and_then(|l| l.tag("ti")))
+                        .unwrap_or("Unknown Title");
                     lyric_title = format!(" Lyrics of {artist:^.20} - {title:^.20} ");
                 }
+                Some(MediaType::Podcast) if self.lyric_shows_transcript() => {
+                    lyric_title = " Transcript ".to_string();
+                }
                 Some(MediaType::Podcast) => {
                     lyric_title = " Details: ".to_string();
                 }
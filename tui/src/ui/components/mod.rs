@@ -22,6 +22,8 @@
  * SOFTWARE.
  */
 // -- modules
+#[allow(clippy::module_name_repetitions)]
+mod browse;
 mod config_editor;
 mod database;
 mod general_search;
@@ -43,18 +45,20 @@ mod xywh;
 mod youtube_search;
 
 // -- export
+pub use browse::{BrowseAlbums, BrowseArtists, BrowseTracks};
 pub use config_editor::*;
 pub use database::{DBListCriteria, DBListSearchResult, DBListSearchTracks};
 pub use general_search::{GSInputPopup, GSTablePopup, Source};
 pub use labels::{DownloadSpinner, LabelGeneric, LabelSpan};
-pub use lyric::Lyric;
+pub use lyric::{KaraokeView, Lyric, NotesView};
 pub use music_library::MusicLibrary;
 pub use playlist::Playlist;
 pub use podcast::{EpisodeList, FeedsList};
 pub use popups::{
     DeleteConfirmInputPopup, DeleteConfirmRadioPopup, ErrorPopup, FeedDeleteConfirmInputPopup,
-    FeedDeleteConfirmRadioPopup, HelpPopup, MessagePopup, PodcastAddPopup, QuitPopup,
-    SavePlaylistConfirm, SavePlaylistPopup,
+    FeedDeleteConfirmRadioPopup, HelpPopup, MessagePopup, OrganizePopup, PodcastAddPopup,
+    PodcastFailedTablePopup, PodcastFilterPopup, PodcastPolicyPopup, PodcastQueuePopup,
+    PodcastQueueTablePopup, QuitPopup, SavePlaylistConfirm, SavePlaylistPopup, StatsPopup,
 };
 pub use progress::Progress;
 pub use youtube_search::{YSInputPopup, YSTablePopup};
@@ -65,7 +69,8 @@ use crate::config::Keys;
 // #[cfg(any(feature = "mpris", feature = "discord"))]
 // use crate::track::Track;
 use crate::ui::{
-    ConfigEditorMsg, GSMsg, Id, IdConfigEditor, IdTagEditor, Model, Msg, PLMsg, XYWHMsg, YSMsg,
+    ConfigEditorMsg, GSMsg, Id, IdConfigEditor, IdTagEditor, LyricMsg, Model, Msg, PLMsg, XYWHMsg,
+    YSMsg,
 };
 use tui_realm_stdlib::Phantom;
 use tuirealm::event::NoUserEvent;
@@ -143,6 +148,16 @@ impl Component<Msg, NoUserEvent> for GlobalListener {
             {
                 Some(Msg::PlayerSeekBackward)
             }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_player_next_chapter.key_event() =>
+            {
+                Some(Msg::PlayerNextChapter)
+            }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_player_previous_chapter.key_event() =>
+            {
+                Some(Msg::PlayerPreviousChapter)
+            }
             Event::Keyboard(keyevent)
                 if keyevent == self.keys.global_player_speed_up.key_event() =>
             {
@@ -167,6 +182,11 @@ impl Component<Msg, NoUserEvent> for GlobalListener {
             Event::Keyboard(keyevent) if keyevent == self.keys.global_lyric_cycle.key_event() => {
                 Some(Msg::LyricCycle)
             }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_lyric_toggle_scroll.key_event() =>
+            {
+                Some(Msg::LyricMessage(LyricMsg::LyricToggleScroll))
+            }
 
             Event::Keyboard(keyevent)
                 if keyevent == self.keys.global_layout_treeview.key_event() =>
@@ -186,6 +206,10 @@ impl Component<Msg, NoUserEvent> for GlobalListener {
                 Some(Msg::LayoutPodCast)
             }
 
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_layout_browse.key_event() => {
+                Some(Msg::LayoutBrowse)
+            }
+
             Event::Keyboard(keyevent)
                 if keyevent == self.keys.global_player_toggle_gapless.key_event() =>
             {
@@ -292,6 +316,14 @@ impl Model {
                 SubEventClause::Keyboard(keys.global_player_seek_backward.key_event()),
                 Self::no_popup_mounted_clause(),
             ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_player_next_chapter.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_player_previous_chapter.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
             Sub::new(
                 SubEventClause::Keyboard(keys.global_lyric_adjust_forward.key_event()),
                 Self::no_popup_mounted_clause(),
@@ -304,6 +336,10 @@ impl Model {
                 SubEventClause::Keyboard(keys.global_lyric_cycle.key_event()),
                 Self::no_popup_mounted_clause(),
             ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_lyric_toggle_scroll.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
             Sub::new(
                 SubEventClause::Keyboard(keys.global_layout_treeview.key_event()),
                 Self::no_popup_mounted_clause(),
@@ -328,6 +364,10 @@ impl Model {
                 SubEventClause::Keyboard(keys.global_layout_podcast.key_event()),
                 Self::no_popup_mounted_clause(),
             ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_layout_browse.key_event()),
+                Self::no_popup_mounted_clause(),
+            ),
             Sub::new(
                 SubEventClause::Keyboard(keys.global_xywh_move_left.key_event()),
                 SubClause::Always,
@@ -370,7 +410,30 @@ impl Model {
             Box::new(SubClause::IsMounted(Id::FeedDeleteConfirmRadioPopup)),
             Box::new(SubClause::Or(
                 Box::new(SubClause::IsMounted(Id::FeedDeleteConfirmInputPopup)),
-                Box::new(SubClause::IsMounted(Id::PodcastSearchTablePopup)),
+                Box::new(SubClause::Or(
+                    Box::new(SubClause::IsMounted(Id::PodcastSearchTablePopup)),
+                    Box::new(SubClause::Or(
+                        Box::new(SubClause::IsMounted(Id::StatsPopup)),
+                        Box::new(SubClause::Or(
+                            Box::new(SubClause::IsMounted(Id::OrganizePopup)),
+                            Box::new(SubClause::Or(
+                                Box::new(SubClause::IsMounted(Id::PodcastPolicyPopup)),
+                                Box::new(SubClause::Or(
+                                    Box::new(SubClause::IsMounted(Id::PodcastQueuePopup)),
+                                    Box::new(SubClause::Or(
+                                        Box::new(SubClause::IsMounted(Id::PodcastQueueTablePopup)),
+                                        Box::new(SubClause::Or(
+                                            Box::new(SubClause::IsMounted(Id::PodcastFilterPopup)),
+                                            Box::new(SubClause::IsMounted(
+                                                Id::PodcastFailedTablePopup,
+                                            )),
+                                        )),
+                                    )),
+                                )),
+                            )),
+                        )),
+                    )),
+                )),
             )),
         )))
     }
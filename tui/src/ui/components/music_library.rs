@@ -4,6 +4,7 @@ use crate::utils::get_pin_yin;
 use anyhow::{bail, Context, Result};
 use std::fs::{remove_dir_all, remove_file, rename};
 use std::path::{Path, PathBuf};
+use termusiclib::organize::{self, Pattern};
 use tui_realm_treeview::{Node, Tree, TreeView, TREE_CMD_CLOSE, TREE_CMD_OPEN, TREE_INITIAL_NODE};
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
@@ -211,6 +212,10 @@ impl Component<Msg, NoUserEvent> for MusicLibrary {
             Event::Keyboard(keyevent) if keyevent == self.keys.library_remove_root.key_event() => {
                 return Some(Msg::Library(LIMsg::RemoveRoot))
             }
+            Event::Keyboard(keyevent) if keyevent == self.keys.library_organize.key_event() => {
+                let current_node = self.component.tree_state().selected().unwrap();
+                return Some(Msg::Library(LIMsg::Organize(current_node.to_string())));
+            }
             Event::Keyboard(keyevent) if keyevent == self.keys.library_search.key_event() => {
                 return Some(Msg::GeneralSearch(crate::ui::GSMsg::PopupShowLibrary))
             }
@@ -538,4 +543,69 @@ impl Model {
         self.library_switch_root();
         Ok(())
     }
+
+    /// Show where the tracks under `node` would be moved by `library_organize_pattern`
+    pub fn library_organize_preview(&mut self, node: &str) -> Result<()> {
+        let pattern = Pattern::parse(&self.config.library_organize_pattern)?;
+        let path = Path::new(node);
+        let records: Vec<_> = self
+            .db
+            .get_all_records()?
+            .into_iter()
+            .filter(|r| Path::new(&r.file).starts_with(path))
+            .collect();
+        let plan = organize::plan(&records, &organize::root_of(path, &self.config), &pattern);
+        if plan.moves.is_empty() && plan.conflicts.is_empty() {
+            self.update_show_message_timeout("Organize", "All tracks are in place", Some(5));
+            return Ok(());
+        }
+        self.mount_organize_popup(&plan);
+        self.organize_plan = Some(plan);
+        Ok(())
+    }
+
+    pub fn library_organize_apply(&mut self) {
+        self.umount_organize_popup();
+        let Some(plan) = self.organize_plan.take() else {
+            return;
+        };
+        let report = organize::apply(&plan.moves, &self.config.lyric_sidecar_dir);
+        if let Err(e) = self.db.move_records(&report.moved) {
+            self.mount_error_popup(format!("update database error: {e}"));
+        }
+        self.playlist_update_library_move(&report.moved);
+        self.library_reload_with_node_focus(None);
+
+        if report.failed.is_empty() && report.left_behind.is_empty() {
+            self.update_show_message_timeout(
+                "Organize",
+                &format!("{} tracks moved", report.moved.len()),
+                Some(5),
+            );
+        } else if report.failed.is_empty() {
+            let errors: Vec<String> = report
+                .left_behind
+                .iter()
+                .map(|(file, e)| format!("{file}: {e}"))
+                .collect();
+            self.mount_error_popup(format!(
+                "{} tracks moved, {} files left behind: {}",
+                report.moved.len(),
+                errors.len(),
+                errors.join("; ")
+            ));
+        } else {
+            let errors: Vec<String> = report
+                .failed
+                .iter()
+                .map(|(file, e)| format!("{file}: {e}"))
+                .collect();
+            self.mount_error_popup(format!(
+                "{} of {} tracks not moved: {}",
+                errors.len(),
+                plan.moves.len(),
+                errors.join("; ")
+            ));
+        }
+    }
 }
@@ -3,6 +3,7 @@ use crate::ui::Model;
 use anyhow::{anyhow, bail, Result};
 use rand::seq::SliceRandom;
 use std::path::Path;
+use termusiclib::organize::Move;
 use termusiclib::sqlite::SearchCriteria;
 use termusiclib::sqlite::TrackForDB;
 use termusiclib::types::{GSMsg, Id, Msg, PLMsg};
@@ -461,6 +462,14 @@ impl Model {
         self.playlist_sync();
     }
 
+    pub fn playlist_update_library_move(&mut self, moves: &[Move]) {
+        self.playlist.move_items(moves);
+        if let Err(e) = self.player_sync_playlist() {
+            self.mount_error_popup(format!("sync playlist error: {e}"));
+        }
+        self.playlist_sync();
+    }
+
     pub fn playlist_update_title(&mut self) {
         let duration = self.playlist.tracks().iter().map(Track::duration).sum();
         let title = format!(
@@ -1,16 +1,21 @@
 use crate::ui::Model;
 use anyhow::{anyhow, bail, Result};
-use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use sanitize_filename::{sanitize_with_options, Options};
-use serde_json::Value;
-use std::time::Duration;
 use termusiclib::config::{Keys, Settings};
-use termusiclib::podcast::{download_list, EpData, PodcastFeed, PodcastNoId};
+use termusiclib::podcast::db::Database as DBPod;
+use termusiclib::podcast::discovery::Directories;
+use termusiclib::podcast::policy::Policy;
+use termusiclib::podcast::queue::{self, Generator};
+use termusiclib::podcast::{
+    chapters, download, download_list, filter, transcript, EpData, Episode, PodcastFeed,
+    PodcastNoId,
+};
 use termusiclib::track::MediaType;
-use termusiclib::types::{Id, Msg, PCMsg};
+use termusiclib::types::{GSMsg, Id, Msg, PCMsg};
+use termusiclib::utils::get_app_config_path;
 use tui_realm_stdlib::List;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
-use tuirealm::props::{Alignment, BorderType, TableBuilder, TextSpan};
+use tuirealm::props::{Alignment, BorderType, PropPayload, PropValue, TableBuilder, TextSpan};
 use tuirealm::props::{Borders, Color};
 use tuirealm::{
     event::{Key, KeyEvent, KeyModifiers, NoUserEvent},
@@ -186,6 +191,23 @@ impl Component<Msg, NoUserEvent> for FeedsList {
             {
                 return Some(Msg::Podcast(PCMsg::FeedsDeleteShow));
             }
+            Event::Keyboard(keyevent) if keyevent == self.keys.podcast_feed_policy.key_event() => {
+                return Some(Msg::Podcast(PCMsg::PolicyPopupShow));
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.podcast_queue_feed.key_event() => {
+                return Some(Msg::Podcast(PCMsg::QueuePopupShow));
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.podcast_queue_show.key_event() => {
+                return Some(Msg::Podcast(PCMsg::QueueTableShow));
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.podcast_feed_filter.key_event() => {
+                return Some(Msg::Podcast(PCMsg::FilterPopupShow));
+            }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.podcast_download_failed.key_event() =>
+            {
+                return Some(Msg::Podcast(PCMsg::FailedTableShow));
+            }
             _ => CmdResult::None,
         };
         Some(Msg::None)
@@ -363,6 +385,23 @@ impl Component<Msg, NoUserEvent> for EpisodeList {
                 }
                 CmdResult::None
             }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.podcast_search_transcripts.key_event() =>
+            {
+                return Some(Msg::GeneralSearch(GSMsg::PopupShowTranscript));
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.podcast_queue_show.key_event() => {
+                return Some(Msg::Podcast(PCMsg::QueueTableShow));
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.podcast_feed_filter.key_event() => {
+                return Some(Msg::Podcast(PCMsg::FilterPopupShow));
+            }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.podcast_download_failed.key_event() =>
+            {
+                return Some(Msg::Podcast(PCMsg::FailedTableShow));
+            }
             _ => CmdResult::None,
         };
         Some(Msg::None)
@@ -370,68 +409,8 @@ impl Component<Msg, NoUserEvent> for EpisodeList {
 }
 
 impl Model {
-    pub fn podcast_search_itunes(&self, search_str: &str) {
-        let encoded: String = utf8_percent_encode(search_str, NON_ALPHANUMERIC).to_string();
-        let url =
-            format!("https://itunes.apple.com/search?media=podcast&entity=podcast&term={encoded}",);
-        let agent = reqwest::blocking::ClientBuilder::new()
-            .connect_timeout(Duration::from_secs(5))
-            .build()
-            .expect("error build client");
-        // let result = agent.get(&url).call()?;
-
-        let mut max_retries = self.config.podcast_max_retries;
-
-        let tx = self.tx_to_main.clone();
-
-        std::thread::spawn(move || {
-            let request: Result<reqwest::blocking::Response> = loop {
-                let response = agent.get(&url).send();
-                if let Ok(resp) = response {
-                    break Ok(resp);
-                }
-                max_retries -= 1;
-                if max_retries == 0 {
-                    break Err(anyhow!("No response from feed"));
-                }
-            };
-            // below two lines are left for debug purpose
-            // let mut file = std::fs::File::create("data.txt").expect("create failed");
-            // file.write_all(result.into_string()?.as_bytes())
-            //     .expect("write failed");
-            match request {
-                Ok(result) => match result.status() {
-                    reqwest::StatusCode::OK => match result.text() {
-                        Ok(text) => {
-                            if let Some(vec) = parse_itunes_results(&text) {
-                                tx.send(Msg::Podcast(PCMsg::SearchSuccess(vec))).ok();
-                            } else {
-                                tx.send(Msg::Podcast(PCMsg::SearchError(
-                                    "Error parsing result".to_string(),
-                                )))
-                                .ok();
-                            }
-                        }
-                        Err(_) => {
-                            tx.send(Msg::Podcast(PCMsg::SearchError(
-                                "Error in into_string".to_string(),
-                            )))
-                            .ok();
-                        }
-                    },
-                    _ => {
-                        tx.send(Msg::Podcast(PCMsg::SearchError(
-                            "Error result status code".to_string(),
-                        )))
-                        .ok();
-                    }
-                },
-                Err(e) => {
-                    tx.send(Msg::Podcast(PCMsg::SearchError(e.to_string())))
-                        .ok();
-                }
-            }
-        });
+    pub fn podcast_search_directories(&self, query: &str) {
+        Directories::new(&self.config.podcast_directories).search(query, self.tx_to_main.clone());
     }
 
     pub fn podcast_add(&mut self, url: &str) {
@@ -445,6 +424,9 @@ impl Model {
         );
     }
     pub fn podcast_sync_feeds_and_episodes(&mut self) {
+        if let Err(e) = self.podcast_apply_filters() {
+            self.mount_error_popup(format!("Error filtering episodes: {e}"));
+        }
         let mut table: TableBuilder = TableBuilder::default();
 
         for (idx, record) in self.podcasts.iter().enumerate() {
@@ -660,6 +642,204 @@ impl Model {
         }
     }
 
+    /// Show the retention policy of the selected feed for editing.
+    pub fn podcast_policy_show(&mut self) -> Result<()> {
+        let podcast_selected = self
+            .podcasts
+            .get(self.podcasts_index)
+            .ok_or_else(|| anyhow!("get podcast selected failed."))?;
+        let policy = self.db_podcast.get_policy(podcast_selected.id)?;
+        self.mount_podcast_policy_popup(&policy);
+        Ok(())
+    }
+
+    /// Store the policy typed into the popup. The server applies it on its next round.
+    pub fn podcast_policy_save(&mut self, input: &str) -> Result<()> {
+        let policy: Policy = input.parse()?;
+        let podcast_selected = self
+            .podcasts
+            .get(self.podcasts_index)
+            .ok_or_else(|| anyhow!("get podcast selected failed."))?;
+        self.db_podcast.set_policy(podcast_selected.id, &policy)?;
+        Ok(())
+    }
+
+    /// Show the view options of the selected feed, or those for all feeds when it has
+    /// none of its own.
+    pub fn podcast_filter_show(&mut self) -> Result<()> {
+        let podcast_selected = self
+            .podcasts
+            .get(self.podcasts_index)
+            .ok_or_else(|| anyhow!("get podcast selected failed."))?;
+        let filter = self.db_podcast.get_filter(Some(podcast_selected.id))?;
+        let rules = if filter.is_off() {
+            let all = self.db_podcast.get_filter(None)?;
+            if all.is_off() {
+                String::new()
+            } else {
+                format!("{all} all")
+            }
+        } else {
+            filter.to_string()
+        };
+        self.mount_podcast_filter_popup(&rules);
+        Ok(())
+    }
+
+    /// Store the view options typed into the popup, for the selected feed or for all
+    /// feeds, and list the episodes again.
+    pub fn podcast_filter_save(&mut self, input: &str) -> Result<()> {
+        let (filter, all_feeds) = filter::parse_scoped(input)?;
+        if all_feeds {
+            self.db_podcast.set_filter(None, &filter)?;
+        } else {
+            let podcast_selected = self
+                .podcasts
+                .get(self.podcasts_index)
+                .ok_or_else(|| anyhow!("get podcast selected failed."))?;
+            self.db_podcast
+                .set_filter(Some(podcast_selected.id), &filter)?;
+        }
+        self.podcasts = self.db_podcast.get_podcasts()?;
+        self.podcast_sync_feeds_and_episodes();
+        Ok(())
+    }
+
+    /// Hide and sort episodes as the view options say. A feed with options of its own
+    /// ignores those for all feeds.
+    fn podcast_apply_filters(&mut self) -> Result<()> {
+        let all = self.db_podcast.get_filter(None)?;
+        let filters = self.db_podcast.get_filters()?;
+        for pod in &mut self.podcasts {
+            filters
+                .get(&pod.id)
+                .unwrap_or(&all)
+                .apply(&mut pod.episodes);
+        }
+        Ok(())
+    }
+
+    /// Queue the unplayed episodes of the selected feed, or of every feed, as typed
+    /// into the popup.
+    pub fn podcast_queue_add(&mut self, input: &str) -> Result<()> {
+        let generator: Generator = input.parse()?;
+        let episodes: Vec<Episode> = if generator.all_feeds {
+            self.podcasts
+                .iter()
+                .flat_map(|pod| pod.episodes.iter().cloned())
+                .collect()
+        } else {
+            self.podcasts
+                .get(self.podcasts_index)
+                .ok_or_else(|| anyhow!("get podcast selected failed."))?
+                .episodes
+                .clone()
+        };
+        let added = queue::add_unplayed(&self.db_podcast, &episodes, generator.order)?;
+        self.podcast_queue_generator = generator;
+        self.update_show_message_timeout("Podcast", &format!("{added} episodes queued"), Some(5));
+        Ok(())
+    }
+
+    pub fn podcast_queue_show(&mut self) -> Result<()> {
+        self.podcast_queue = self.db_podcast.get_queue()?;
+        self.mount_podcast_queue_table();
+        Ok(())
+    }
+
+    /// Play a queued episode, adding it to the playlist if needed. The server goes on
+    /// with the rest of the queue when it ends.
+    pub fn podcast_queue_play(&mut self, index: usize) -> Result<()> {
+        let ep = self
+            .podcast_queue
+            .get(index)
+            .ok_or_else(|| anyhow!("get queued episode failed."))?
+            .clone();
+        let position = self
+            .playlist
+            .tracks()
+            .iter()
+            .position(|track| track.file() == Some(ep.url.as_str()));
+        let position = if let Some(position) = position {
+            position
+        } else {
+            self.playlist.add_episode(&ep);
+            self.playlist_sync();
+            self.playlist.len() - 1
+        };
+        self.playlist_play_selected(position);
+        Ok(())
+    }
+
+    pub fn podcast_queue_remove(&mut self, index: usize) -> Result<()> {
+        if let Some(ep) = self.podcast_queue.get(index) {
+            self.db_podcast.remove_from_queue(ep.id)?;
+        }
+        self.podcast_queue = self.db_podcast.get_queue()?;
+        self.update_podcast_queue_table();
+        Ok(())
+    }
+
+    pub fn podcast_queue_clear(&mut self) -> Result<()> {
+        self.db_podcast.set_queue(&[])?;
+        self.podcast_queue.clear();
+        self.update_podcast_queue_table();
+        Ok(())
+    }
+
+    /// The server finished a queued episode, so it marked it played and may have put
+    /// the next one in the playlist.
+    pub fn podcast_queue_advanced_by_server(&mut self) {
+        if let Err(e) = self.playlist.reload_tracks() {
+            self.mount_error_popup(format!("error reloading playlist: {e}"));
+        }
+        self.playlist_sync();
+        match self.db_podcast.get_podcasts() {
+            Ok(podcasts) => {
+                self.podcasts = podcasts;
+                self.podcast_sync_feeds_and_episodes();
+            }
+            Err(e) => self.mount_error_popup(format!("error reloading podcasts: {e}")),
+        }
+        if self.app.mounted(&Id::PodcastQueueTablePopup) {
+            if let Ok(queue) = self.db_podcast.get_queue() {
+                self.podcast_queue = queue;
+                self.update_podcast_queue_table();
+            }
+        }
+    }
+
+    /// Reload podcasts after the server refreshed feeds, applied feed policies or synced
+    /// with other devices.
+    pub fn podcast_changed_by_server(
+        &mut self,
+        new_episodes: u32,
+        files_changed: u32,
+        sync_changes: u32,
+    ) {
+        if new_episodes == 0 && files_changed == 0 && sync_changes == 0 {
+            return;
+        }
+        match self.db_podcast.get_podcasts() {
+            Ok(podcasts) => {
+                self.podcasts = podcasts;
+                self.podcast_sync_feeds_and_episodes();
+                let mut changes = Vec::new();
+                if new_episodes > 0 {
+                    changes.push(format!("{new_episodes} new episodes"));
+                }
+                if files_changed > 0 {
+                    changes.push(format!("{files_changed} files downloaded or deleted"));
+                }
+                if sync_changes > 0 {
+                    changes.push(format!("{sync_changes} changes synced from other devices"));
+                }
+                self.update_show_message_timeout("Podcast", &changes.join(", "), Some(5));
+            }
+            Err(e) => self.mount_error_popup(format!("error reloading podcasts: {e}")),
+        }
+    }
+
     /// Synchronize RSS feed data for one or more podcasts.
     pub fn podcast_refresh_feeds(&mut self, index: Option<usize>) -> Result<()> {
         // We pull out the data we need here first, so we can
@@ -741,6 +921,7 @@ impl Model {
                         title: ep.title.clone(),
                         url: ep.url.clone(),
                         pubdate: ep.pubdate,
+                        enclosure_length: ep.enclosure_length.and_then(|l| u64::try_from(l).ok()),
                         file_path: None,
                     };
                     if ep.path.is_none() && !self.download_tracker.contains(&ep.url) {
@@ -760,6 +941,9 @@ impl Model {
                                     title: ep.title.clone(),
                                     url: ep.url.clone(),
                                     pubdate: ep.pubdate,
+                                    enclosure_length: ep
+                                        .enclosure_length
+                                        .and_then(|l| u64::try_from(l).ok()),
                                     file_path: None,
                                 })
                             } else {
@@ -776,36 +960,103 @@ impl Model {
         // ep_data.retain(|ep| !self.download_tracker.contains(&ep.id));
 
         if !ep_data.is_empty() {
-            // add directory for podcast, create if it does not exist
-            let dir_name = sanitize_with_options(
-                &pod_title,
-                Options {
-                    truncate: true,
-                    windows: true, // for simplicity, we'll just use Windows-friendly paths for everyone
-                    replacement: "",
-                },
-            );
-            match crate::utils::create_podcast_dir(&self.config, dir_name) {
-                Ok(path) => {
-                    // for ep in ep_data.iter() {
-                    //     self.download_tracker.insert(ep.id);
-                    // }
-                    download_list(
-                        ep_data,
-                        &path,
-                        self.config.podcast_max_retries,
-                        &self.threadpool,
-                        &self.tx_to_main,
-                    );
-                }
-                Err(_) => bail!("Could not create dir: {pod_title}"),
-            }
+            self.episode_download_list(&pod_title, ep_data)?;
         }
 
         // self.podcast_sync_feeds_and_episodes();
         Ok(())
     }
 
+    /// Starts downloading episodes of one podcast into its directory
+    fn episode_download_list(&self, pod_title: &str, ep_data: Vec<EpData>) -> Result<()> {
+        // add directory for podcast, create if it does not exist
+        let dir_name = sanitize_with_options(
+            pod_title,
+            Options {
+                truncate: true,
+                windows: true, // for simplicity, we'll just use Windows-friendly paths for everyone
+                replacement: "",
+            },
+        );
+        match crate::utils::create_podcast_dir(&self.config, dir_name) {
+            Ok(path) => {
+                download_list(
+                    ep_data,
+                    &path,
+                    &self.config,
+                    &self.threadpool,
+                    &self.tx_to_main,
+                );
+                Ok(())
+            }
+            Err(_) => bail!("Could not create dir: {pod_title}"),
+        }
+    }
+
+    /// Keeps the failed downloads up to date with a finished download
+    pub fn episode_download_record(&mut self, msg: &PCMsg) {
+        if let Err(e) = download::record_result(&self.db_podcast, msg) {
+            self.mount_error_popup(format!("Error in failed downloads: {e}"));
+            return;
+        }
+        if self.app.mounted(&Id::PodcastFailedTablePopup) {
+            if let Ok(failed) = self.db_podcast.get_failed_downloads() {
+                self.podcast_failed_downloads = failed;
+                self.update_podcast_failed_table();
+            }
+        }
+    }
+
+    pub fn podcast_failed_show(&mut self) -> Result<()> {
+        self.podcast_failed_downloads = self.db_podcast.get_failed_downloads()?;
+        self.mount_podcast_failed_table();
+        Ok(())
+    }
+
+    /// Downloads a failed episode again, going on from what the last try got
+    pub fn podcast_failed_retry(&mut self, index: usize) -> Result<()> {
+        let (ep, _) = self
+            .podcast_failed_downloads
+            .get(index)
+            .ok_or_else(|| anyhow!("get failed download failed."))?;
+        if self.download_tracker.contains(&ep.url) {
+            return Ok(());
+        }
+        let pod_title = self
+            .podcasts
+            .iter()
+            .find(|pod| pod.id == ep.pod_id)
+            .ok_or_else(|| anyhow!("get podcast of failed download failed."))?
+            .title
+            .clone();
+        let ep_data = EpData {
+            id: ep.id,
+            pod_id: ep.pod_id,
+            title: ep.title.clone(),
+            url: ep.url.clone(),
+            pubdate: ep.pubdate,
+            enclosure_length: ep.enclosure_length.and_then(|l| u64::try_from(l).ok()),
+            file_path: None,
+        };
+        self.episode_download_list(&pod_title, vec![ep_data])
+    }
+
+    pub fn podcast_failed_remove(&mut self, index: usize) -> Result<()> {
+        if let Some((ep, _)) = self.podcast_failed_downloads.get(index) {
+            self.db_podcast.remove_download_failed(ep.id)?;
+        }
+        self.podcast_failed_downloads = self.db_podcast.get_failed_downloads()?;
+        self.update_podcast_failed_table();
+        Ok(())
+    }
+
+    pub fn podcast_failed_clear(&mut self) -> Result<()> {
+        self.db_podcast.clear_download_failed()?;
+        self.podcast_failed_downloads.clear();
+        self.update_podcast_failed_table();
+        Ok(())
+    }
+
     pub fn episode_download_complete(&mut self, ep_data: EpData) -> Result<()> {
         let file_path = ep_data.file_path.unwrap();
         let res = self.db_podcast.insert_file(ep_data.id, &file_path);
@@ -815,6 +1066,9 @@ impl Model {
                 file_path.to_string_lossy()
             );
         }
+        if let Err(e) = chapters::save_from_file(&self.db_podcast, ep_data.id, &file_path) {
+            warn!("cannot read chapters of {}: {e}", ep_data.title);
+        }
 
         let podcasts = self.db_podcast.get_podcasts()?;
         self.podcasts = podcasts;
@@ -972,6 +1226,141 @@ impl Model {
         Ok(())
     }
 
+    /// Show the stored transcript of the playing episode like lyrics. One that is only
+    /// linked from the feed is fetched in the background first.
+    pub fn podcast_load_transcript(&mut self) {
+        self.lyric_transcript_hidden = false;
+        let Some(song) = &self.current_song else {
+            return;
+        };
+        if song.media_type != Some(MediaType::Podcast) {
+            return;
+        }
+        let Some(ep) = self
+            .podcasts
+            .iter()
+            .flat_map(|pod| &pod.episodes)
+            .find(|ep| Some(ep.url.as_str()) == song.file())
+        else {
+            return;
+        };
+        let text = match self.db_podcast.get_transcript(ep.id) {
+            Ok(Some(text)) => text,
+            Ok(None) => {
+                if let Some(url) = &ep.transcript_url {
+                    if self.podcast_transcripts_requested.insert(ep.id) {
+                        let url = url.clone();
+                        let episode_id = ep.id;
+                        let tx = self.tx_to_main.clone();
+                        self.threadpool.execute(move || {
+                            let saved = transcript::fetch(&url).and_then(|text| {
+                                let db = DBPod::connect(&get_app_config_path()?)?;
+                                db.set_transcript(episode_id, &text)
+                            });
+                            match saved {
+                                Ok(()) => tx
+                                    .send(Msg::Podcast(PCMsg::TranscriptFetched(episode_id)))
+                                    .ok(),
+                                Err(e) => {
+                                    warn!("cannot fetch transcript from {url}: {e}");
+                                    None
+                                }
+                            };
+                        });
+                    }
+                }
+                return;
+            }
+            Err(e) => {
+                error!("cannot read transcript of {}: {e}", ep.title);
+                return;
+            }
+        };
+        match transcript::parse(&text) {
+            Ok(lyric) => {
+                if let Some(song) = self.current_song.as_mut() {
+                    song.set_lyric(&lyric.as_lrc_text(), "Transcript");
+                    song.set_parsed_lyric(Some(lyric));
+                }
+            }
+            Err(e) => warn!("cannot read transcript of {}: {e}", ep.title),
+        }
+    }
+
+    pub fn podcast_update_search_transcripts(&mut self, input: &str) {
+        let mut table: TableBuilder = TableBuilder::default();
+        let matches = if input.trim().is_empty() {
+            Vec::new()
+        } else {
+            transcript::search(&self.db_podcast, input.trim()).unwrap_or_default()
+        };
+        for (idx, found) in matches.iter().enumerate() {
+            let Some(ep) = self
+                .podcasts
+                .iter()
+                .flat_map(|pod| &pod.episodes)
+                .find(|ep| ep.id == found.episode_id)
+            else {
+                continue;
+            };
+            if idx > 0 {
+                table.add_row();
+            }
+            let seconds = found.time_stamp / 1000;
+            table
+                .add_col(TextSpan::new(format!(
+                    "[{:02}:{:02}:{:02}]",
+                    seconds / 3600,
+                    seconds / 60 % 60,
+                    seconds % 60
+                )))
+                .add_col(TextSpan::new(&ep.title).fg(tuirealm::tui::style::Color::LightYellow))
+                .add_col(TextSpan::new(&found.text).bold())
+                .add_col(TextSpan::new(found.episode_id.to_string()))
+                .add_col(TextSpan::new(found.time_stamp.to_string()));
+        }
+        if matches.is_empty() {
+            table.add_col(TextSpan::from("0"));
+            table.add_col(TextSpan::from("no matching transcript line"));
+            table.add_col(TextSpan::from(""));
+        }
+        self.general_search_update_show(table.build());
+    }
+
+    /// Select an episode in the feeds and episodes lists, returning its url
+    pub fn podcast_locate_episode(&mut self, episode_id: i64) -> Option<String> {
+        let (pod_index, ep_index, url) =
+            self.podcasts
+                .iter()
+                .enumerate()
+                .find_map(|(pod_index, pod)| {
+                    pod.episodes
+                        .iter()
+                        .position(|ep| ep.id == episode_id)
+                        .map(|ep_index| (pod_index, ep_index, pod.episodes[ep_index].url.clone()))
+                })?;
+        self.podcasts_index = pod_index;
+        self.app
+            .attr(
+                &Id::Podcast,
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(pod_index))),
+            )
+            .ok();
+        if let Err(e) = self.podcast_sync_episodes() {
+            self.mount_error_popup(format!("Error sync episodes: {e}"));
+        }
+        self.app
+            .attr(
+                &Id::Episode,
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(ep_index))),
+            )
+            .ok();
+        self.lyric_update();
+        Some(url)
+    }
+
     pub fn podcast_get_album_photo_by_url(&self, url: &str) -> Option<String> {
         if self.podcasts.is_empty() {
             return None;
@@ -1002,31 +1391,3 @@ impl Model {
     //     None
     // }
 }
-
-fn parse_itunes_results(data: &str) -> Option<Vec<PodcastFeed>> {
-    if let Ok(value) = serde_json::from_str::<Value>(data) {
-        // below two lines are left for debug purpose
-        // let mut file = std::fs::File::create("data.txt").expect("create failed");
-        // file.write_all(data.as_bytes()).expect("write failed");
-
-        let mut vec: Vec<PodcastFeed> = Vec::new();
-        let array = value.get("results")?.as_array()?;
-        for v in array.iter() {
-            if let Some((title, url)) = parse_itunes_item(v) {
-                vec.push(PodcastFeed {
-                    id: None,
-                    url,
-                    title: Some(title),
-                });
-            }
-        }
-        return Some(vec);
-    }
-    None
-}
-
-fn parse_itunes_item(v: &Value) -> Option<(String, String)> {
-    let title = v.get("collectionName")?.as_str()?.to_owned();
-    let url = v.get("feedUrl")?.as_str()?.to_owned();
-    Some((title, url))
-}
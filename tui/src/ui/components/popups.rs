@@ -23,6 +23,11 @@ use crate::config::{BindingForEvent, Keys, Settings, StyleColorSymbol};
  * SOFTWARE.
  */
 use crate::ui::{Id, Model, Msg, PCMsg};
+use std::path::Path;
+use termusiclib::organize::Plan;
+use termusiclib::podcast::policy::Policy;
+use termusiclib::podcast::queue::Generator;
+use termusiclib::stats::LibraryStats;
 use tui_realm_stdlib::{Input, Paragraph, Radio, Table};
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
@@ -243,6 +248,12 @@ impl HelpPopup {
                         ]))
                         .add_col(Self::comment("Seek forward/backward 5 seconds"))
                         .add_row()
+                        .add_col(Self::key(&[
+                            keys.global_player_next_chapter,
+                            keys.global_player_previous_chapter,
+                        ]))
+                        .add_col(Self::comment("Next/previous podcast chapter"))
+                        .add_row()
                         .add_col(Self::key(&[
                             keys.global_lyric_adjust_forward,
                             keys.global_lyric_adjust_backward,
@@ -267,6 +278,15 @@ impl HelpPopup {
                         .add_col(Self::key(&[keys.global_lyric_cycle]))
                         .add_col(Self::comment("Switch lyrics if more than 1 available"))
                         .add_row()
+                        .add_col(Self::key(&[keys.global_lyric_toggle_scroll]))
+                        .add_col(Self::comment("Show whole lyric or lines around current"))
+                        .add_row()
+                        .add_col(Self::key(&[keys.global_lyric_seek_line]))
+                        .add_col(Self::comment("Lyrics: seek to selected line"))
+                        .add_row()
+                        .add_col(Self::key(&[keys.global_lyric_seek_line, keys.library_yank]))
+                        .add_col(Self::comment("Episode notes: seek or open link/copy link"))
+                        .add_row()
                         .add_col(Self::key(&[
                             keys.global_player_next,
                             keys.global_player_previous,
@@ -297,6 +317,9 @@ impl HelpPopup {
                         .add_col(Self::key(&[keys.global_layout_podcast]))
                         .add_col(Self::comment("Switch layout to podcast"))
                         .add_row()
+                        .add_col(Self::key(&[keys.global_layout_browse]))
+                        .add_col(Self::comment("Switch layout to album browser"))
+                        .add_row()
                         .add_col(Self::key(&[
                             keys.global_xywh_move_left,
                             keys.global_xywh_move_right,
@@ -330,7 +353,14 @@ impl HelpPopup {
                         .add_col(Self::comment("Search or download track from youtube"))
                         .add_row()
                         .add_col(Self::key(&[keys.library_tag_editor_open]))
-                        .add_col(Self::comment("Open tag editor for tag and lyric download"))
+                        .add_col(Self::comment(
+                            "Open tag editor, for all tracks if on a folder",
+                        ))
+                        .add_row()
+                        .add_col(Self::key(&[keys.library_organize]))
+                        .add_col(Self::comment(
+                            "Move tracks into folders named by their tags",
+                        ))
                         .add_row()
                         .add_col(Self::key(&[keys.library_yank, keys.library_paste]))
                         .add_col(Self::comment("Yank and Paste files"))
@@ -384,9 +414,15 @@ impl HelpPopup {
                         .add_col(Self::key(&[keys.global_right, keys.database_add_all]))
                         .add_col(Self::comment("Add one/all track(s) to playlist"))
                         .add_row()
+                        .add_col(Self::key(&[keys.library_tag_editor_open]))
+                        .add_col(Self::comment("Edit tags of all listed tracks at once"))
+                        .add_row()
                         .add_col(Self::key(&[keys.library_search]))
                         .add_col(Self::comment("Search in database"))
                         .add_row()
+                        .add_col(Self::key(&[keys.database_stats]))
+                        .add_col(Self::comment("Show library statistics"))
+                        .add_row()
                         .add_col(TextSpan::new("Podcast").bold().fg(Color::LightYellow))
                         .add_row()
                         .add_col(Self::key(&[keys.podcast_search_add_feed]))
@@ -404,6 +440,21 @@ impl HelpPopup {
                         ]))
                         .add_col(Self::comment("Feeds : refresh one/all feeds"))
                         .add_row()
+                        .add_col(Self::key(&[keys.podcast_feed_policy]))
+                        .add_col(Self::comment("Feeds : auto download and cleanup"))
+                        .add_row()
+                        .add_col(Self::key(&[keys.podcast_queue_feed]))
+                        .add_col(Self::comment("Feeds : queue unplayed episodes"))
+                        .add_row()
+                        .add_col(Self::key(&[keys.podcast_queue_show]))
+                        .add_col(Self::comment("Podcast: show queue"))
+                        .add_row()
+                        .add_col(Self::key(&[keys.podcast_feed_filter]))
+                        .add_col(Self::comment("Podcast: filter and sort episodes"))
+                        .add_row()
+                        .add_col(Self::key(&[keys.podcast_download_failed]))
+                        .add_col(Self::comment("Podcast: retry failed downloads"))
+                        .add_row()
                         .add_col(Self::key(&[
                             keys.podcast_mark_played,
                             keys.podcast_mark_all_played,
@@ -415,6 +466,9 @@ impl HelpPopup {
                         .add_row()
                         .add_col(Self::key(&[keys.podcast_episode_delete_file]))
                         .add_col(Self::comment("Episode: delete episode local file"))
+                        .add_row()
+                        .add_col(Self::key(&[keys.podcast_search_transcripts]))
+                        .add_col(Self::comment("Episode: search transcripts"))
                         .build(),
                 ),
             keys: keys.clone(),
@@ -458,6 +512,230 @@ impl Component<Msg, NoUserEvent> for HelpPopup {
     }
 }
 
+#[derive(MockComponent)]
+pub struct StatsPopup {
+    component: Table,
+    keys: Keys,
+}
+
+impl StatsPopup {
+    pub fn new(config: &Settings, stats: &LibraryStats) -> Self {
+        let mut table = TableBuilder::default();
+        for (idx, (label, value)) in stats.rows().into_iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            if value.is_empty() {
+                table.add_col(TextSpan::new(label).bold().fg(Color::LightYellow));
+            } else {
+                table
+                    .add_col(TextSpan::new(label))
+                    .add_col(TextSpan::new(value).bold().fg(Color::Cyan));
+            }
+        }
+        Self {
+            component: Table::default()
+                .borders(
+                    Borders::default().modifiers(BorderType::Rounded).color(
+                        config
+                            .style_color_symbol
+                            .library_border()
+                            .unwrap_or(Color::Green),
+                    ),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .library_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .scroll(true)
+                .title(
+                    "Library statistics: Esc or Enter to exit.",
+                    Alignment::Center,
+                )
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .column_spacing(3)
+                .widths(&[60, 40])
+                .table(table.build()),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for StatsPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter,
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::StatsPopupClose),
+            Event::Keyboard(key) if key == self.keys.global_quit.key_event() => {
+                return Some(Msg::StatsPopupClose)
+            }
+            Event::Keyboard(key) if key == self.keys.global_esc.key_event() => {
+                return Some(Msg::StatsPopupClose)
+            }
+
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down,
+                modifiers: KeyModifiers::NONE,
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Up,
+                modifiers: KeyModifiers::NONE,
+            }) => self.perform(Cmd::Move(Direction::Up)),
+            _ => CmdResult::None,
+        };
+
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct OrganizePopup {
+    component: Table,
+    keys: Keys,
+}
+
+impl OrganizePopup {
+    pub fn new(config: &Settings, plan: &Plan) -> Self {
+        let relative = |p: &Path| {
+            p.strip_prefix(&plan.root)
+                .unwrap_or(p)
+                .display()
+                .to_string()
+        };
+        let mut table = TableBuilder::default();
+        for (idx, m) in plan.moves.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            table
+                .add_col(TextSpan::new(relative(&m.from)))
+                .add_col(TextSpan::new(relative(&m.to)).fg(Color::Cyan));
+        }
+        for (idx, m) in plan.conflicts.iter().enumerate() {
+            if idx > 0 || !plan.moves.is_empty() {
+                table.add_row();
+            }
+            table
+                .add_col(TextSpan::new(relative(&m.from)))
+                .add_col(TextSpan::new(format!("taken: {}", relative(&m.to))).fg(Color::Red));
+        }
+        Self {
+            component: Table::default()
+                .borders(
+                    Borders::default().modifiers(BorderType::Rounded).color(
+                        config
+                            .style_color_symbol
+                            .library_border()
+                            .unwrap_or(Color::Green),
+                    ),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .library_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .scroll(true)
+                .title(
+                    format!(
+                        "Move {} tracks, {} skipped: Enter to move, Esc to cancel.",
+                        plan.moves.len(),
+                        plan.conflicts.len()
+                    ),
+                    Alignment::Center,
+                )
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .column_spacing(3)
+                .widths(&[50, 50])
+                .table(table.build()),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for OrganizePopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter,
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::OrganizePopupCloseOk),
+            Event::Keyboard(key) if key == self.keys.global_quit.key_event() => {
+                return Some(Msg::OrganizePopupCloseCancel)
+            }
+            Event::Keyboard(key) if key == self.keys.global_esc.key_event() => {
+                return Some(Msg::OrganizePopupCloseCancel)
+            }
+
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down,
+                modifiers: KeyModifiers::NONE,
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Up,
+                modifiers: KeyModifiers::NONE,
+            }) => self.perform(Cmd::Move(Direction::Up)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                modifiers: KeyModifiers::NONE,
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp,
+                modifiers: KeyModifiers::NONE,
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            _ => CmdResult::None,
+        };
+
+        Some(Msg::None)
+    }
+}
+
 #[derive(MockComponent)]
 pub struct DeleteConfirmRadioPopup {
     component: Radio,
@@ -931,81 +1209,560 @@ impl Component<Msg, NoUserEvent> for PodcastAddPopup {
 }
 
 #[derive(MockComponent)]
-pub struct FeedDeleteConfirmRadioPopup {
-    component: Radio,
-    keys: Keys,
+pub struct PodcastPolicyPopup {
+    component: Input,
 }
 
-impl FeedDeleteConfirmRadioPopup {
-    pub fn new(config: &Settings) -> Self {
+impl PodcastPolicyPopup {
+    pub fn new(style_color_symbol: &StyleColorSymbol, policy: &Policy) -> Self {
         Self {
-            component: Radio::default()
+            component: Input::default()
                 .foreground(
-                    config
-                        .style_color_symbol
+                    style_color_symbol
                         .library_foreground()
-                        .unwrap_or(Color::LightRed),
+                        .unwrap_or(Color::Yellow),
                 )
-                // .background(Color::Black)
                 .background(
-                    config
-                        .style_color_symbol
+                    style_color_symbol
                         .library_background()
                         .unwrap_or(Color::Reset),
                 )
                 .borders(
                     Borders::default()
-                        .color(
-                            config
-                                .style_color_symbol
-                                .library_border()
-                                .unwrap_or(Color::LightRed),
-                        )
+                        .color(style_color_symbol.library_border().unwrap_or(Color::Green))
                         .modifiers(BorderType::Rounded),
                 )
-                .title("Are sure you to delete the feed?", Alignment::Left)
-                .rewind(true)
-                .choices(&["No", "Yes"])
-                .value(0),
-            keys: config.keys.clone(),
+                .input_type(InputType::Text)
+                .value(policy.to_string())
+                .title(
+                    " Download newest, delete played after days, keep at most: (0 is off) ",
+                    Alignment::Left,
+                ),
         }
     }
 }
 
-impl Component<Msg, NoUserEvent> for FeedDeleteConfirmRadioPopup {
+impl Component<Msg, NoUserEvent> for PodcastPolicyPopup {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
-        let cmd_result = match ev {
+        let _cmd_result = match ev {
             Event::Keyboard(KeyEvent {
                 code: Key::Left, ..
             }) => self.perform(Cmd::Move(Direction::Left)),
             Event::Keyboard(KeyEvent {
                 code: Key::Right, ..
             }) => self.perform(Cmd::Move(Direction::Right)),
-
-            Event::Keyboard(key) if key == self.keys.global_left.key_event() => {
-                self.perform(Cmd::Move(Direction::Left))
-            }
-            Event::Keyboard(key) if key == self.keys.global_right.key_event() => {
-                self.perform(Cmd::Move(Direction::Right))
-            }
-            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
-                self.perform(Cmd::Move(Direction::Left))
-            }
-            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
-                self.perform(Cmd::Move(Direction::Right))
-            }
-            Event::Keyboard(key) if key == self.keys.global_quit.key_event() => {
-                return Some(Msg::Podcast(PCMsg::FeedDeleteCloseCancel))
-            }
-            Event::Keyboard(key) if key == self.keys.global_esc.key_event() => {
-                return Some(Msg::Podcast(PCMsg::FeedDeleteCloseCancel))
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
             }
-
             Event::Keyboard(KeyEvent {
-                code: Key::Enter, ..
-            }) => self.perform(Cmd::Submit),
-            _ => CmdResult::None,
-        };
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Podcast(PCMsg::PolicyPopupCloseCancel));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.component.state() {
+                State::One(StateValue::String(input_string)) => {
+                    return Some(Msg::Podcast(PCMsg::PolicyPopupCloseOk(input_string)));
+                }
+                _ => return Some(Msg::None),
+            },
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct PodcastFilterPopup {
+    component: Input,
+}
+
+impl PodcastFilterPopup {
+    pub fn new(style_color_symbol: &StyleColorSymbol, rules: &str) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .borders(
+                    Borders::default()
+                        .color(style_color_symbol.library_border().unwrap_or(Color::Green))
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .value(rules)
+                .title(
+                    " unplayed, downloaded, oldest/longest/shortest, +include, -exclude, all feeds: all ",
+                    Alignment::Left,
+                ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for PodcastFilterPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Podcast(PCMsg::FilterPopupCloseCancel));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.component.state() {
+                State::One(StateValue::String(input_string)) => {
+                    return Some(Msg::Podcast(PCMsg::FilterPopupCloseOk(input_string)));
+                }
+                _ => return Some(Msg::None),
+            },
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct PodcastQueuePopup {
+    component: Input,
+}
+
+impl PodcastQueuePopup {
+    pub fn new(style_color_symbol: &StyleColorSymbol, generator: Generator) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .borders(
+                    Borders::default()
+                        .color(style_color_symbol.library_border().unwrap_or(Color::Green))
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .value(generator.to_string())
+                .title(
+                    " Queue unplayed episodes, oldest or newest first: (all for every feed) ",
+                    Alignment::Left,
+                ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for PodcastQueuePopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Podcast(PCMsg::QueuePopupCloseCancel));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.component.state() {
+                State::One(StateValue::String(input_string)) => {
+                    return Some(Msg::Podcast(PCMsg::QueuePopupCloseOk(input_string)));
+                }
+                _ => return Some(Msg::None),
+            },
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct PodcastQueueTablePopup {
+    component: Table,
+    keys: Keys,
+}
+
+impl PodcastQueueTablePopup {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Table::default()
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Magenta),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::Magenta),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .title(
+                    format!(
+                        " Queue: Enter to play, {}/{} to remove one/all ",
+                        config.keys.playlist_delete, config.keys.playlist_delete_all
+                    ),
+                    Alignment::Left,
+                )
+                .scroll(true)
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .library_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers(&[" Date ", " Feed ", " Episode "])
+                .column_spacing(2)
+                .widths(&[12, 28, 60])
+                .table(
+                    TableBuilder::default()
+                        .add_col(TextSpan::from(""))
+                        .add_col(TextSpan::from(""))
+                        .add_col(TextSpan::from("Empty queue."))
+                        .build(),
+                ),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for PodcastQueueTablePopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Podcast(PCMsg::QueueTableCloseCancel))
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_quit.key_event() => {
+                return Some(Msg::Podcast(PCMsg::QueueTableCloseCancel))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_goto_top.key_event() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_goto_bottom.key_event() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.playlist_delete.key_event() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Podcast(PCMsg::QueueRemove(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.playlist_delete_all.key_event() => {
+                return Some(Msg::Podcast(PCMsg::QueueClear))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Podcast(PCMsg::QueuePlay(index)));
+                }
+                CmdResult::None
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct PodcastFailedTablePopup {
+    component: Table,
+    keys: Keys,
+}
+
+impl PodcastFailedTablePopup {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Table::default()
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Magenta),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::Magenta),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .title(
+                    format!(
+                        " Failed downloads: Enter to retry, {}/{} to forget one/all ",
+                        config.keys.playlist_delete, config.keys.playlist_delete_all
+                    ),
+                    Alignment::Left,
+                )
+                .scroll(true)
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .library_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers(&[" Feed ", " Episode ", " Error "])
+                .column_spacing(2)
+                .widths(&[25, 45, 30])
+                .table(
+                    TableBuilder::default()
+                        .add_col(TextSpan::from(""))
+                        .add_col(TextSpan::from("No failed downloads."))
+                        .add_col(TextSpan::from(""))
+                        .build(),
+                ),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for PodcastFailedTablePopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Podcast(PCMsg::FailedTableCloseCancel))
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_quit.key_event() => {
+                return Some(Msg::Podcast(PCMsg::FailedTableCloseCancel))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_goto_top.key_event() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_goto_bottom.key_event() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.playlist_delete.key_event() => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Podcast(PCMsg::FailedRemove(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(keyevent) if keyevent == self.keys.playlist_delete_all.key_event() => {
+                return Some(Msg::Podcast(PCMsg::FailedClear))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Podcast(PCMsg::FailedRetry(index)));
+                }
+                CmdResult::None
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct FeedDeleteConfirmRadioPopup {
+    component: Radio,
+    keys: Keys,
+}
+
+impl FeedDeleteConfirmRadioPopup {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Radio::default()
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::LightRed),
+                )
+                // .background(Color::Black)
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::LightRed),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .title("Are sure you to delete the feed?", Alignment::Left)
+                .rewind(true)
+                .choices(&["No", "Yes"])
+                .value(0),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for FeedDeleteConfirmRadioPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+
+            Event::Keyboard(key) if key == self.keys.global_left.key_event() => {
+                self.perform(Cmd::Move(Direction::Left))
+            }
+            Event::Keyboard(key) if key == self.keys.global_right.key_event() => {
+                self.perform(Cmd::Move(Direction::Right))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Left))
+            }
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Right))
+            }
+            Event::Keyboard(key) if key == self.keys.global_quit.key_event() => {
+                return Some(Msg::Podcast(PCMsg::FeedDeleteCloseCancel))
+            }
+            Event::Keyboard(key) if key == self.keys.global_esc.key_event() => {
+                return Some(Msg::Podcast(PCMsg::FeedDeleteCloseCancel))
+            }
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.perform(Cmd::Submit),
+            _ => CmdResult::None,
+        };
         if matches!(
             cmd_result,
             CmdResult::Submit(State::One(StateValue::Usize(0)))
@@ -1227,6 +1984,54 @@ impl Component<Msg, NoUserEvent> for PodcastSearchTablePopup {
 }
 
 impl Model {
+    pub fn mount_organize_popup(&mut self, plan: &Plan) {
+        assert!(self
+            .app
+            .remount(
+                Id::OrganizePopup,
+                Box::new(OrganizePopup::new(&self.config, plan)),
+                vec![]
+            )
+            .is_ok());
+        self.update_photo().ok();
+        assert!(self.app.active(&Id::OrganizePopup).is_ok());
+    }
+
+    pub fn umount_organize_popup(&mut self) {
+        if self.app.mounted(&Id::OrganizePopup) {
+            assert!(self.app.umount(&Id::OrganizePopup).is_ok());
+        }
+        self.update_photo().ok();
+    }
+
+    pub fn mount_stats_popup(&mut self) {
+        let records = match self.db.get_all_records() {
+            Ok(records) => records,
+            Err(e) => {
+                self.mount_error_popup(format!("read library database error: {e}"));
+                return;
+            }
+        };
+        let stats = LibraryStats::from_records(&records);
+        assert!(self
+            .app
+            .remount(
+                Id::StatsPopup,
+                Box::new(StatsPopup::new(&self.config, &stats)),
+                vec![]
+            )
+            .is_ok());
+        self.update_photo().ok();
+        assert!(self.app.active(&Id::StatsPopup).is_ok());
+    }
+
+    pub fn umount_stats_popup(&mut self) {
+        if self.app.mounted(&Id::StatsPopup) {
+            assert!(self.app.umount(&Id::StatsPopup).is_ok());
+        }
+        self.update_photo().ok();
+    }
+
     pub fn mount_confirm_radio(&mut self) {
         assert!(self
             .app
@@ -1348,4 +2153,144 @@ impl Model {
             self.mount_error_popup(format!("update photo error: {e}"));
         }
     }
+
+    pub fn mount_podcast_queue_popup(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::PodcastQueuePopup,
+                Box::new(PodcastQueuePopup::new(
+                    &self.config.style_color_symbol,
+                    self.podcast_queue_generator
+                )),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::PodcastQueuePopup).is_ok());
+    }
+
+    pub fn umount_podcast_queue_popup(&mut self) {
+        if self.app.mounted(&Id::PodcastQueuePopup) {
+            assert!(self.app.umount(&Id::PodcastQueuePopup).is_ok());
+        }
+    }
+
+    pub fn mount_podcast_queue_table(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::PodcastQueueTablePopup,
+                Box::new(PodcastQueueTablePopup::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::PodcastQueueTablePopup).is_ok());
+        self.update_podcast_queue_table();
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(format!("update photo error: {e}"));
+        }
+    }
+
+    pub fn update_podcast_queue_table(&mut self) {
+        let mut table: TableBuilder = TableBuilder::default();
+        for (idx, ep) in self.podcast_queue.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            let date = ep
+                .pubdate
+                .map_or_else(String::new, |d| d.format("%Y-%m-%d").to_string());
+            let feed = self
+                .podcasts
+                .iter()
+                .find(|pod| pod.id == ep.pod_id)
+                .map_or("", |pod| pod.title.as_str());
+            table
+                .add_col(TextSpan::new(date))
+                .add_col(TextSpan::new(feed))
+                .add_col(TextSpan::new(&ep.title).bold());
+        }
+        if self.podcast_queue.is_empty() {
+            table
+                .add_col(TextSpan::from(""))
+                .add_col(TextSpan::from(""))
+                .add_col(TextSpan::from("Empty queue."));
+        }
+        let table = table.build();
+
+        self.app
+            .attr(
+                &Id::PodcastQueueTablePopup,
+                tuirealm::Attribute::Content,
+                tuirealm::AttrValue::Table(table),
+            )
+            .ok();
+    }
+
+    pub fn umount_podcast_queue_table(&mut self) {
+        if self.app.mounted(&Id::PodcastQueueTablePopup) {
+            assert!(self.app.umount(&Id::PodcastQueueTablePopup).is_ok());
+        }
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(format!("update photo error: {e}"));
+        }
+    }
+
+    pub fn mount_podcast_failed_table(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::PodcastFailedTablePopup,
+                Box::new(PodcastFailedTablePopup::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::PodcastFailedTablePopup).is_ok());
+        self.update_podcast_failed_table();
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(format!("update photo error: {e}"));
+        }
+    }
+
+    pub fn update_podcast_failed_table(&mut self) {
+        let mut table: TableBuilder = TableBuilder::default();
+        for (idx, (ep, error)) in self.podcast_failed_downloads.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            let feed = self
+                .podcasts
+                .iter()
+                .find(|pod| pod.id == ep.pod_id)
+                .map_or("", |pod| pod.title.as_str());
+            table
+                .add_col(TextSpan::new(feed))
+                .add_col(TextSpan::new(&ep.title).bold())
+                .add_col(TextSpan::new(error));
+        }
+        if self.podcast_failed_downloads.is_empty() {
+            table
+                .add_col(TextSpan::from(""))
+                .add_col(TextSpan::from("No failed downloads."))
+                .add_col(TextSpan::from(""));
+        }
+        let table = table.build();
+
+        self.app
+            .attr(
+                &Id::PodcastFailedTablePopup,
+                tuirealm::Attribute::Content,
+                tuirealm::AttrValue::Table(table),
+            )
+            .ok();
+    }
+
+    pub fn umount_podcast_failed_table(&mut self) {
+        if self.app.mounted(&Id::PodcastFailedTablePopup) {
+            assert!(self.app.umount(&Id::PodcastFailedTablePopup).is_ok());
+        }
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(format!("update photo error: {e}"));
+        }
+    }
 }
@@ -1,5 +1,5 @@
 use crate::ui::Model;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use termusiclib::config::Settings;
 use termusiclib::track::{MediaType, Track};
 use termusiclib::types::{Id, Msg};
@@ -123,7 +123,10 @@ impl Model {
             return;
         }
 
-        self.time_pos = time_pos;
+        if time_pos != self.time_pos {
+            self.time_pos = time_pos;
+            self.time_pos_updated = Instant::now();
+        }
 
         let progress = (time_pos * 100).checked_div(duration).unwrap() as f64;
 
@@ -0,0 +1,212 @@
+/*
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::ui::model::Model;
+use std::path::Path;
+use std::sync::mpsc;
+use termusiclib::tag_batch::{changed_fields, save_batch, BatchSaveState, TagField};
+use termusiclib::track::Track;
+use termusiclib::types::{Id, IdTagEditor};
+use tuirealm::props::TextSpan;
+use tuirealm::props::{Alignment, AttrValue, Attribute, InputType, PropPayload, PropValue};
+use tuirealm::{State, StateValue};
+
+pub(super) const fn input_id(field: TagField) -> IdTagEditor {
+    match field {
+        TagField::Artist => IdTagEditor::InputArtist,
+        TagField::Title => IdTagEditor::InputTitle,
+        TagField::Album => IdTagEditor::InputAlbum,
+        TagField::Genre => IdTagEditor::InputGenre,
+        TagField::AlbumArtist => IdTagEditor::InputAlbumArtist,
+        TagField::Year => IdTagEditor::InputYear,
+        TagField::Track => IdTagEditor::InputTrack,
+        TagField::Disc => IdTagEditor::InputDisc,
+        TagField::Composer => IdTagEditor::InputComposer,
+        TagField::Comment => IdTagEditor::InputComment,
+    }
+}
+
+fn file_name(file: &str) -> String {
+    Path::new(file)
+        .file_name()
+        .map_or_else(|| file.to_string(), |n| n.to_string_lossy().to_string())
+}
+
+impl Model {
+    /// Open the tag editor on several files at once. Files that cannot be read are left out.
+    pub fn mount_tageditor_batch(&mut self, files: Vec<String>) {
+        let mut tracks = vec![];
+        let mut errors = vec![];
+        for file in files {
+            match Track::read_from_path(&file, false) {
+                Ok(track) => tracks.push(track),
+                Err(e) => errors.push(format!("{}: {e}", file_name(&file))),
+            }
+        }
+        if tracks.is_empty() {
+            errors.insert(0, "no tracks to edit".to_string());
+            self.mount_error_popup(errors.join("; "));
+            return;
+        }
+
+        self.mount_tageditor_components();
+        self.init_by_batch(tracks);
+        if !errors.is_empty() {
+            self.mount_error_popup(format!("skipped {}", errors.join("; ")));
+        }
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(format!("clear photo error: {e}"));
+        }
+    }
+
+    fn init_by_batch(&mut self, tracks: Vec<Track>) {
+        self.tageditor_song = None;
+        for field in TagField::ALL {
+            let id = Id::TagEditor(input_id(field));
+            // number inputs would drop the placeholder
+            self.app
+                .attr(
+                    &id,
+                    Attribute::InputType,
+                    AttrValue::InputType(InputType::Text),
+                )
+                .ok();
+            self.app
+                .attr(
+                    &id,
+                    Attribute::Value,
+                    AttrValue::String(field.common_value(&tracks)),
+                )
+                .ok();
+        }
+
+        self.init_by_song_no_lyric();
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::TextareaLyric),
+                Attribute::Title,
+                AttrValue::Title((format!("Editing {} tracks", tracks.len()), Alignment::Left)),
+            )
+            .ok();
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::TextareaLyric),
+                Attribute::Text,
+                AttrValue::Payload(PropPayload::Vec(
+                    tracks
+                        .iter()
+                        .map(|t| {
+                            PropValue::TextSpan(TextSpan::from(file_name(
+                                t.file().unwrap_or_default(),
+                            )))
+                        })
+                        .collect(),
+                )),
+            )
+            .ok();
+        self.tageditor_batch = tracks;
+    }
+
+    /// Write the changed fields to every track of the batch in the background
+    pub fn te_save_batch(&mut self) {
+        if self.receiver_tag_batch.is_some() {
+            return;
+        }
+        let values: Vec<(TagField, String)> = TagField::ALL
+            .iter()
+            .map(|field| {
+                let value = match self.app.state(&Id::TagEditor(input_id(*field))) {
+                    Ok(State::One(StateValue::String(value))) => value,
+                    _ => String::new(),
+                };
+                (*field, value)
+            })
+            .collect();
+        let changes = changed_fields(&self.tageditor_batch, &values);
+        if changes.is_empty() {
+            self.update_show_message_timeout("Tag Editor", "Nothing changed", Some(5));
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        save_batch(self.tageditor_batch.clone(), &changes, tx);
+        self.receiver_tag_batch = Some(rx);
+        self.te_batch_progress(0);
+    }
+
+    pub fn te_update_batch_save(&mut self) {
+        let Some(rx) = &self.receiver_tag_batch else {
+            return;
+        };
+        let Ok(state) = rx.try_recv() else {
+            return;
+        };
+        match state {
+            BatchSaveState::Progress(done) => self.te_batch_progress(done),
+            BatchSaveState::Finish(saved, errors) => {
+                self.receiver_tag_batch = None;
+                self.app.umount(&Id::MessagePopup).ok();
+                if let Err(e) = self.db.update_records(&saved) {
+                    self.mount_error_popup(format!("update database error: {e}"));
+                }
+
+                let total = self.tageditor_batch.len();
+                let written = saved.len();
+                let mut tracks = std::mem::take(&mut self.tageditor_batch);
+                for track in &mut tracks {
+                    if let Some((_, s)) = saved.iter().find(|(f, _)| track.file() == Some(f)) {
+                        *track = s.clone();
+                    }
+                }
+                self.init_by_batch(tracks);
+                self.database_reload();
+                self.playlist_update_library_delete();
+
+                if errors.is_empty() {
+                    self.update_show_message_timeout(
+                        "Tag Editor",
+                        &format!("{written} tracks saved"),
+                        Some(5),
+                    );
+                } else {
+                    let errors: Vec<String> = errors
+                        .iter()
+                        .map(|(file, e)| format!("{}: {e}", file_name(file)))
+                        .collect();
+                    self.mount_error_popup(format!(
+                        "{} of {total} files not saved: {}",
+                        errors.len(),
+                        errors.join("; ")
+                    ));
+                }
+            }
+        }
+        self.redraw = true;
+    }
+
+    pub(super) fn te_batch_progress(&mut self, done: usize) {
+        self.mount_message(
+            "Tag Editor",
+            &format!("Saving tags {done}/{}", self.tageditor_batch.len()),
+        );
+    }
+}
@@ -23,8 +23,12 @@
  */
 
 /// -- modules
+mod batch;
 mod te_counter_delete_lyric;
+mod te_cover;
+mod te_filename_tags;
 mod te_input;
+mod te_list_lyric_sync;
 mod te_select_lyric;
 mod te_table_lyric_options;
 mod te_textarea_lyric;
@@ -34,6 +38,7 @@ mod view;
 // -- exports
 pub use te_counter_delete_lyric::TECounterDelete;
 pub use te_input::*;
+pub use te_list_lyric_sync::TEListLyricSync;
 pub use te_select_lyric::TESelectLyric;
 pub use te_table_lyric_options::TETableLyricOptions;
 pub use te_textarea_lyric::TETextareaLyric;
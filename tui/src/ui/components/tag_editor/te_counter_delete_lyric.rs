@@ -285,12 +285,21 @@ impl Model {
                 song.set_parsed_lyric(None);
                 return;
             }
-            song.lyric_frames_remove_selected();
+            // removing a sidecar deletes the .lrc file, the song itself is left alone
+            let is_sidecar = song.lyric_selected_is_sidecar();
+            if let Err(e) = song.lyric_frames_remove_selected() {
+                self.mount_error_popup(format!("delete lyric error: {e}"));
+                return;
+            }
             if (song.lyric_selected_index() >= song.lyric_frames_len())
                 && (song.lyric_selected_index() > 0)
             {
                 song.set_lyric_selected_index(song.lyric_selected_index() - 1);
             }
+            if is_sidecar {
+                self.init_by_song(&song);
+                return;
+            }
             match song.save_tag() {
                 Ok(_) => self.init_by_song(&song),
                 Err(e) => {
@@ -0,0 +1,264 @@
+/*
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::config::{BindingForEvent, Settings};
+use crate::ui::{CoverMsg, Model, Msg, TEMsg};
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use termusiclib::cover;
+use termusiclib::tag_batch::save_tracks;
+use termusiclib::types::{Id, IdTagEditor};
+use tui_realm_stdlib::Input;
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
+use tuirealm::props::{Alignment, BorderType, Borders, Color, InputType};
+use tuirealm::{Component, Event, MockComponent, State, StateValue};
+
+/// Opens the cover popup from the tag fields
+pub const COVER_KEY: BindingForEvent = BindingForEvent {
+    code: Key::Char('p'),
+    modifier: KeyModifiers::CONTROL,
+};
+
+/// Writes the embedded cover to the file named in the popup
+pub const EXTRACT_COVER_KEY: BindingForEvent = BindingForEvent {
+    code: Key::Char('x'),
+    modifier: KeyModifiers::CONTROL,
+};
+
+#[derive(MockComponent)]
+pub struct TEInputCoverFile {
+    component: Input,
+    config: Settings,
+}
+
+impl TEInputCoverFile {
+    pub fn new(config: &Settings, file: &str) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Cyan),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::LightYellow),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .value(file)
+                .title(
+                    format!(" Cover image: <ENTER> to embed, <{EXTRACT_COVER_KEY}> to extract to this file "),
+                    Alignment::Left,
+                ),
+            config: config.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TEInputCoverFile {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(k) if k == self.config.keys.global_esc.key_event() => {
+                return Some(Msg::TagEditor(TEMsg::TECover(CoverMsg::Cancel)))
+            }
+            Event::Keyboard(k) if k == EXTRACT_COVER_KEY.key_event() => {
+                return Some(Msg::TagEditor(TEMsg::TECover(CoverMsg::Extract)))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => return Some(Msg::TagEditor(TEMsg::TECover(CoverMsg::Embed))),
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+impl Model {
+    pub fn te_update_cover(&mut self, msg: &CoverMsg) {
+        let result = match msg {
+            CoverMsg::Show => {
+                self.te_cover_show();
+                Ok(())
+            }
+            CoverMsg::Embed => self.te_cover_embed(),
+            CoverMsg::Extract => self.te_cover_extract(),
+            CoverMsg::Cancel => {
+                self.te_cover_close();
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            self.mount_error_popup(format!("cover error: {e}"));
+        }
+    }
+
+    /// The folder image if there is one, otherwise where extracting would put the cover
+    fn te_cover_default_file(&self) -> String {
+        let track = self
+            .tageditor_song
+            .as_ref()
+            .or_else(|| self.tageditor_batch.first());
+        let Some(dir) = track
+            .and_then(|t| t.file())
+            .and_then(|f| Path::new(f).parent())
+        else {
+            return String::new();
+        };
+        let file = cover::find_in_dir(dir).unwrap_or_else(|| {
+            let ext = track
+                .and_then(|t| t.picture())
+                .map_or("jpg", cover::extension);
+            dir.join(format!("cover.{ext}"))
+        });
+        file.to_string_lossy().to_string()
+    }
+
+    fn te_cover_show(&mut self) {
+        let file = self.te_cover_default_file();
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputCoverFile),
+                Box::new(TEInputCoverFile::new(&self.config, &file)),
+                vec![]
+            )
+            .is_ok());
+        self.app
+            .active(&Id::TagEditor(IdTagEditor::InputCoverFile))
+            .ok();
+    }
+
+    pub fn te_cover_close(&mut self) {
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::InputCoverFile))
+            .ok();
+        self.app
+            .active(&Id::TagEditor(IdTagEditor::InputArtist))
+            .ok();
+    }
+
+    fn te_cover_file(&self) -> Result<PathBuf> {
+        match self.app.state(&Id::TagEditor(IdTagEditor::InputCoverFile)) {
+            Ok(State::One(StateValue::String(file))) if !file.is_empty() => {
+                Ok(PathBuf::from(shellexpand::tilde(&file).to_string()))
+            }
+            _ => Err(anyhow!("no image file given")),
+        }
+    }
+
+    /// Embed the image into the track, or into every track of the batch in the background
+    fn te_cover_embed(&mut self) -> Result<()> {
+        let picture = cover::load(
+            &self.te_cover_file()?,
+            self.config.tag_editor_cover_max_size,
+        )?;
+        if self.tageditor_batch.is_empty() {
+            let Some(mut song) = self.tageditor_song.clone() else {
+                return Ok(());
+            };
+            song.set_photo(picture);
+            song.save_tag()?;
+            self.te_cover_close();
+            self.init_by_song(&song);
+            self.playlist_update_library_delete();
+            self.update_show_message_timeout("Tag Editor", "Cover embedded", Some(5));
+            return Ok(());
+        }
+
+        if self.receiver_tag_batch.is_some() {
+            return Ok(());
+        }
+        let tracks = self
+            .tageditor_batch
+            .iter()
+            .map(|t| {
+                let mut track = t.clone();
+                track.set_photo(picture.clone());
+                track
+            })
+            .collect();
+        self.te_cover_close();
+        let (tx, rx) = mpsc::channel();
+        save_tracks(tracks, tx);
+        self.receiver_tag_batch = Some(rx);
+        self.te_batch_progress(0);
+        Ok(())
+    }
+
+    /// Save the embedded cover, of the first track that has one when editing a batch
+    fn te_cover_extract(&mut self) -> Result<()> {
+        let file = self.te_cover_file()?;
+        let picture = self
+            .tageditor_song
+            .iter()
+            .chain(&self.tageditor_batch)
+            .find_map(|t| t.picture())
+            .ok_or_else(|| anyhow!("no embedded cover"))?;
+        cover::extract(picture, &file)?;
+        self.te_cover_close();
+        self.update_show_message_timeout(
+            "Tag Editor",
+            &format!("Cover saved to {}", file.display()),
+            Some(5),
+        );
+        Ok(())
+    }
+}
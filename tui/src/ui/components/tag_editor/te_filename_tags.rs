@@ -0,0 +1,429 @@
+/*
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::batch::input_id;
+use crate::config::{BindingForEvent, Settings};
+use crate::ui::{FilenameTagsMsg, Model, Msg, TEMsg};
+use anyhow::Result;
+use std::path::Path;
+use std::sync::mpsc;
+use termusiclib::filename_tags::FilenameParser;
+use termusiclib::tag_batch::{save_each, TagField};
+use termusiclib::types::{Id, IdTagEditor};
+use tui_realm_stdlib::{Input, Table};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
+use tuirealm::props::{Alignment, BorderType, Borders, Color, InputType, TableBuilder, TextSpan};
+use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent, State, StateValue};
+
+/// Opens the file name parser from the tag fields, which take every plain key as text
+pub const FILENAME_TAGS_KEY: BindingForEvent = BindingForEvent {
+    code: Key::Char('f'),
+    modifier: KeyModifiers::CONTROL,
+};
+
+/// Fields shown in the preview, in this order
+const PREVIEW_FIELDS: [TagField; 6] = [
+    TagField::Artist,
+    TagField::Title,
+    TagField::Album,
+    TagField::Track,
+    TagField::Disc,
+    TagField::Year,
+];
+
+#[derive(MockComponent)]
+pub struct TEInputFilenamePattern {
+    component: Input,
+    config: Settings,
+}
+
+impl TEInputFilenamePattern {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Cyan),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::LightYellow),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .value(&config.tag_editor_filename_pattern)
+                .title(
+                    " Pattern like {artist} - {title}, empty for the usual layouts. <ENTER> to preview ",
+                    Alignment::Left,
+                ),
+            config: config.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TEInputFilenamePattern {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(k) if k == self.config.keys.global_esc.key_event() => {
+                return Some(Msg::TagEditor(TEMsg::TEFilenameTags(
+                    FilenameTagsMsg::Cancel,
+                )))
+            }
+            Event::Keyboard(k) if k == self.config.keys.config_save.key_event() => {
+                return Some(Msg::TagEditor(TEMsg::TEFilenameTags(
+                    FilenameTagsMsg::Apply,
+                )))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                return Some(Msg::TagEditor(TEMsg::TEFilenameTags(
+                    FilenameTagsMsg::Preview,
+                )))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down | Key::Tab,
+                ..
+            }) => {
+                return Some(Msg::TagEditor(TEMsg::TEFilenameTags(
+                    FilenameTagsMsg::FocusTable,
+                )))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct TETableFilenamePreview {
+    component: Table,
+    config: Settings,
+}
+
+impl TETableFilenamePreview {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Table::default()
+                .borders(
+                    Borders::default().modifiers(BorderType::Rounded).color(
+                        config
+                            .style_color_symbol
+                            .library_border()
+                            .unwrap_or(Color::LightMagenta),
+                    ),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Green),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .title(
+                    format!(
+                        " Tags from file name: <{}> to apply ",
+                        config.keys.config_save
+                    ),
+                    Alignment::Left,
+                )
+                .scroll(true)
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .library_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers(&["File", "Artist", "Title", "Album", "Track", "Disc", "Year"])
+                .column_spacing(1)
+                .widths(&[28, 18, 24, 18, 4, 4, 4])
+                .table(TableBuilder::default().add_col(TextSpan::from("")).build()),
+            config: config.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TETableFilenamePreview {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(k) if k == self.config.keys.global_esc.key_event() => {
+                return Some(Msg::TagEditor(TEMsg::TEFilenameTags(
+                    FilenameTagsMsg::Cancel,
+                )))
+            }
+            Event::Keyboard(k) if k == self.config.keys.config_save.key_event() => {
+                return Some(Msg::TagEditor(TEMsg::TEFilenameTags(
+                    FilenameTagsMsg::Apply,
+                )))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter,
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                return Some(Msg::TagEditor(TEMsg::TEFilenameTags(
+                    FilenameTagsMsg::Apply,
+                )))
+            }
+            Event::Keyboard(
+                KeyEvent { code: Key::Tab, .. }
+                | KeyEvent {
+                    code: Key::BackTab,
+                    modifiers: KeyModifiers::SHIFT,
+                },
+            ) => {
+                return Some(Msg::TagEditor(TEMsg::TEFilenameTags(
+                    FilenameTagsMsg::FocusInput,
+                )))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(k) if k == self.config.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(k) if k == self.config.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(k) if k == self.config.keys.global_goto_top.key_event() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(k) if k == self.config.keys.global_goto_bottom.key_event() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+impl Model {
+    pub fn te_update_filename_tags(&mut self, msg: &FilenameTagsMsg) {
+        let result = match msg {
+            FilenameTagsMsg::Show => {
+                self.te_filename_tags_show();
+                self.te_filename_tags_preview()
+            }
+            FilenameTagsMsg::Preview => self.te_filename_tags_preview(),
+            FilenameTagsMsg::FocusInput => {
+                self.app
+                    .active(&Id::TagEditor(IdTagEditor::InputFilenamePattern))
+                    .ok();
+                Ok(())
+            }
+            FilenameTagsMsg::FocusTable => {
+                self.app
+                    .active(&Id::TagEditor(IdTagEditor::TableFilenamePreview))
+                    .ok();
+                Ok(())
+            }
+            FilenameTagsMsg::Apply => self.te_filename_tags_apply(),
+            FilenameTagsMsg::Cancel => {
+                self.te_filename_tags_close();
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            self.mount_error_popup(format!("tags from file name error: {e}"));
+        }
+    }
+
+    fn te_filename_tags_show(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputFilenamePattern),
+                Box::new(TEInputFilenamePattern::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::TableFilenamePreview),
+                Box::new(TETableFilenamePreview::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        self.app
+            .active(&Id::TagEditor(IdTagEditor::InputFilenamePattern))
+            .ok();
+    }
+
+    pub fn te_filename_tags_close(&mut self) {
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::InputFilenamePattern))
+            .ok();
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::TableFilenamePreview))
+            .ok();
+        self.app
+            .active(&Id::TagEditor(IdTagEditor::InputArtist))
+            .ok();
+    }
+
+    fn te_filename_tags_parser(&self) -> Result<FilenameParser> {
+        let pattern = match self
+            .app
+            .state(&Id::TagEditor(IdTagEditor::InputFilenamePattern))
+        {
+            Ok(State::One(StateValue::String(pattern))) => pattern,
+            _ => String::new(),
+        };
+        FilenameParser::new(&pattern)
+    }
+
+    /// The files open in the tag editor
+    fn te_filename_tags_files(&self) -> Vec<String> {
+        if self.tageditor_batch.is_empty() {
+            return self
+                .tageditor_song
+                .iter()
+                .filter_map(|s| s.file().map(str::to_string))
+                .collect();
+        }
+        self.tageditor_batch
+            .iter()
+            .filter_map(|t| t.file().map(str::to_string))
+            .collect()
+    }
+
+    fn te_filename_tags_preview(&mut self) -> Result<()> {
+        let parser = self.te_filename_tags_parser()?;
+        let mut table = TableBuilder::default();
+        for (idx, file) in self.te_filename_tags_files().iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            let tags = parser.parse(file);
+            let name = Path::new(file)
+                .file_name()
+                .map_or_else(String::new, |n| n.to_string_lossy().to_string());
+            table.add_col(TextSpan::new(name));
+            for field in PREVIEW_FIELDS {
+                let value = tags
+                    .iter()
+                    .find(|(f, _)| *f == field)
+                    .map_or_else(String::new, |(_, v)| v.clone());
+                table.add_col(TextSpan::new(value).fg(Color::Cyan));
+            }
+        }
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::TableFilenamePreview),
+                Attribute::Content,
+                AttrValue::Table(table.build()),
+            )
+            .ok();
+        Ok(())
+    }
+
+    /// One track goes to the tag fields, to be saved as usual. Several are written right away.
+    fn te_filename_tags_apply(&mut self) -> Result<()> {
+        let parser = self.te_filename_tags_parser()?;
+        if self.tageditor_batch.is_empty() {
+            let file = self.te_filename_tags_files().pop().unwrap_or_default();
+            self.te_filename_tags_close();
+            for (field, value) in parser.parse(&file) {
+                self.app
+                    .attr(
+                        &Id::TagEditor(input_id(field)),
+                        Attribute::Value,
+                        AttrValue::String(value),
+                    )
+                    .ok();
+            }
+            return Ok(());
+        }
+
+        if self.receiver_tag_batch.is_some() {
+            return Ok(());
+        }
+        let tracks = self
+            .tageditor_batch
+            .iter()
+            .map(|t| (t.clone(), parser.parse(t.file().unwrap_or_default())))
+            .collect();
+        self.te_filename_tags_close();
+        let (tx, rx) = mpsc::channel();
+        save_each(tracks, tx);
+        self.receiver_tag_batch = Some(rx);
+        self.te_batch_progress(0);
+        Ok(())
+    }
+}
@@ -21,8 +21,10 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+use super::te_cover::COVER_KEY;
+use super::te_filename_tags::FILENAME_TAGS_KEY;
 use crate::config::Settings;
-use crate::ui::{Msg, TEMsg, TFMsg};
+use crate::ui::{CoverMsg, FilenameTagsMsg, Msg, TEMsg, TFMsg};
 use tui_realm_stdlib::Input;
 use tuirealm::command::{Cmd, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
@@ -146,6 +148,12 @@ fn handle_input_ev(
         Event::Keyboard(keyevent) if keyevent == config.keys.config_save.key_event() => {
             Some(Msg::TagEditor(TEMsg::TERename))
         }
+        Event::Keyboard(keyevent) if keyevent == FILENAME_TAGS_KEY.key_event() => {
+            Some(Msg::TagEditor(TEMsg::TEFilenameTags(FilenameTagsMsg::Show)))
+        }
+        Event::Keyboard(keyevent) if keyevent == COVER_KEY.key_event() => {
+            Some(Msg::TagEditor(TEMsg::TECover(CoverMsg::Show)))
+        }
         Event::Keyboard(KeyEvent {
             code: Key::Down | Key::Tab,
             ..
@@ -316,3 +324,315 @@ impl Component<Msg, NoUserEvent> for TEInputGenre {
         )
     }
 }
+
+#[derive(MockComponent)]
+pub struct TEInputAlbumArtist {
+    component: Input,
+    config: Settings,
+}
+
+impl TEInputAlbumArtist {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Cyan),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::LightYellow),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .title(" Album Artist ", Alignment::Left),
+            config: config.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TEInputAlbumArtist {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        handle_input_ev(
+            self,
+            ev,
+            &config,
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputAlbumArtistBlurDown)),
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputAlbumArtistBlurUp)),
+        )
+    }
+}
+
+#[derive(MockComponent)]
+pub struct TEInputYear {
+    component: Input,
+    config: Settings,
+}
+
+impl TEInputYear {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Cyan),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::LightYellow),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::UnsignedInteger)
+                .title(" Year ", Alignment::Left),
+            config: config.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TEInputYear {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        handle_input_ev(
+            self,
+            ev,
+            &config,
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputYearBlurDown)),
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputYearBlurUp)),
+        )
+    }
+}
+
+#[derive(MockComponent)]
+pub struct TEInputTrack {
+    component: Input,
+    config: Settings,
+}
+
+impl TEInputTrack {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Cyan),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::LightYellow),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::UnsignedInteger)
+                .title(" Track ", Alignment::Left),
+            config: config.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TEInputTrack {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        handle_input_ev(
+            self,
+            ev,
+            &config,
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputTrackBlurDown)),
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputTrackBlurUp)),
+        )
+    }
+}
+
+#[derive(MockComponent)]
+pub struct TEInputDisc {
+    component: Input,
+    config: Settings,
+}
+
+impl TEInputDisc {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Cyan),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::LightYellow),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::UnsignedInteger)
+                .title(" Disc ", Alignment::Left),
+            config: config.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TEInputDisc {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        handle_input_ev(
+            self,
+            ev,
+            &config,
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputDiscBlurDown)),
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputDiscBlurUp)),
+        )
+    }
+}
+
+#[derive(MockComponent)]
+pub struct TEInputComposer {
+    component: Input,
+    config: Settings,
+}
+
+impl TEInputComposer {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Cyan),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::LightYellow),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .title(" Composer ", Alignment::Left),
+            config: config.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TEInputComposer {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        handle_input_ev(
+            self,
+            ev,
+            &config,
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputComposerBlurDown)),
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputComposerBlurUp)),
+        )
+    }
+}
+
+#[derive(MockComponent)]
+pub struct TEInputComment {
+    component: Input,
+    config: Settings,
+}
+
+impl TEInputComment {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Cyan),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::LightYellow),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .title(" Comment ", Alignment::Left),
+            config: config.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TEInputComment {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let config = self.config.clone();
+        handle_input_ev(
+            self,
+            ev,
+            &config,
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputCommentBlurDown)),
+            Msg::TagEditor(TEMsg::TEFocus(TFMsg::InputCommentBlurUp)),
+        )
+    }
+}
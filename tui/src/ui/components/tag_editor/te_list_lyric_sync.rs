@@ -0,0 +1,340 @@
+/*
+ * MIT License
+ *
+ * tuifeed - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use crate::config::Settings;
+use crate::ui::{LyricSyncMsg, Model, Msg, TEMsg};
+use anyhow::{bail, Result};
+use std::str::FromStr;
+use termusiclib::songtag::lrc::{Lyric, LyricSync};
+use termusiclib::types::{Id, IdTagEditor};
+use termusicplayback::PlayerCmd;
+use tui_realm_stdlib::List;
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
+use tuirealm::props::{
+    Alignment, BorderType, Borders, Color, PropPayload, PropValue, TableBuilder, TextSpan,
+};
+use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent, State, StateValue};
+
+/// Milliseconds a line moves with each nudge
+const NUDGE_STEP: i64 = 100;
+/// Seconds of the song played before the line being previewed
+const PREVIEW_LEAD: i64 = 2;
+
+#[derive(MockComponent)]
+pub struct TEListLyricSync {
+    component: List,
+    config: Settings,
+}
+
+impl TEListLyricSync {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: List::default()
+                .borders(
+                    Borders::default().modifiers(BorderType::Rounded).color(
+                        config
+                            .style_color_symbol
+                            .library_border()
+                            .unwrap_or(Color::LightMagenta),
+                    ),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Green),
+                )
+                .title(" Sync Lyrics ", Alignment::Left)
+                .scroll(true)
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .library_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .rewind(false)
+                .step(4)
+                .rows(
+                    TableBuilder::default()
+                        .add_col(TextSpan::from("No lyrics."))
+                        .build(),
+                ),
+            config: config.clone(),
+        }
+    }
+
+    fn selected(&self) -> usize {
+        match self.state() {
+            State::One(StateValue::Usize(index)) => index,
+            _ => 0,
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for TEListLyricSync {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let row = self.selected();
+        let _cmd_result = match ev {
+            Event::Keyboard(keyevent) if keyevent == self.config.keys.config_save.key_event() => {
+                return Some(Msg::TagEditor(TEMsg::TELyricSync(LyricSyncMsg::Save)))
+            }
+            Event::Keyboard(k) if k == self.config.keys.global_esc.key_event() => {
+                return Some(Msg::TagEditor(TEMsg::TELyricSync(LyricSyncMsg::Cancel)))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter,
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Msg::TagEditor(TEMsg::TELyricSync(LyricSyncMsg::Stamp(row)))),
+            Event::Keyboard(KeyEvent {
+                code: Key::Left,
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                return Some(Msg::TagEditor(TEMsg::TELyricSync(LyricSyncMsg::Nudge(
+                    row,
+                    -NUDGE_STEP,
+                ))))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right,
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                return Some(Msg::TagEditor(TEMsg::TELyricSync(LyricSyncMsg::Nudge(
+                    row, NUDGE_STEP,
+                ))))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace | Key::Delete,
+                ..
+            }) => return Some(Msg::TagEditor(TEMsg::TELyricSync(LyricSyncMsg::Clear(row)))),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('p'),
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                return Some(Msg::TagEditor(TEMsg::TELyricSync(LyricSyncMsg::Preview(
+                    row,
+                ))))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(k) if k == self.config.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(k) if k == self.config.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(k) if k == self.config.keys.global_goto_top.key_event() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(k) if k == self.config.keys.global_goto_bottom.key_event() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+fn format_time_stamp(time_stamp: Option<i64>) -> String {
+    time_stamp.map_or_else(
+        || "[--:--.--]".to_string(),
+        |t| {
+            format!(
+                "[{:02}:{:02}.{:02}]",
+                t / 60000,
+                t / 1000 % 60,
+                t % 1000 / 10
+            )
+        },
+    )
+}
+
+impl Model {
+    /// Switch the lyric box to timing the selected lyrics line by line. The song needs to be
+    /// playing, as lines are stamped with the playback position.
+    pub fn te_lyric_sync_start(&mut self) -> Result<()> {
+        let Some(song) = &self.tageditor_song else {
+            return Ok(());
+        };
+        if self.current_song.as_ref().and_then(|s| s.file()) != song.file() {
+            bail!("play this song first, lines are timed while it plays");
+        }
+        let sync = LyricSync::new(song.lyric_selected().map_or("", |l| l.text.as_str()));
+        if sync.is_empty() {
+            bail!("no lyrics to sync");
+        }
+        let first_unstamped = sync.lines().iter().position(|(t, _)| t.is_none());
+        self.te_lyric_sync = Some(sync);
+        self.te_lyric_sync_playing = None;
+
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::ListLyricSync),
+                Box::new(TEListLyricSync::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        self.te_lyric_sync_update_rows();
+        self.te_lyric_sync_select(first_unstamped.unwrap_or_default());
+        self.app
+            .active(&Id::TagEditor(IdTagEditor::ListLyricSync))
+            .ok();
+        self.remount_tag_editor_label_sync();
+        Ok(())
+    }
+
+    pub fn te_lyric_sync_stop(&mut self) {
+        self.te_lyric_sync = None;
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::ListLyricSync))
+            .ok();
+        self.app
+            .active(&Id::TagEditor(IdTagEditor::TextareaLyric))
+            .ok();
+        self.remount_tag_editor_label_help();
+    }
+
+    pub fn te_update_lyric_sync(&mut self, msg: &LyricSyncMsg) {
+        let position = self.lyric_position();
+        let Some(sync) = self.te_lyric_sync.as_mut() else {
+            return;
+        };
+        match msg {
+            LyricSyncMsg::Stamp(row) => {
+                sync.stamp(*row, position);
+                let next = (*row + 1).min(sync.len() - 1);
+                self.te_lyric_sync_select(next);
+            }
+            LyricSyncMsg::Nudge(row, delta) => sync.nudge(*row, *delta),
+            LyricSyncMsg::Clear(row) => sync.clear(*row),
+            LyricSyncMsg::Preview(row) => {
+                if let Some((Some(time_stamp), _)) = sync.lines().get(*row) {
+                    let position = (time_stamp / 1000 - PREVIEW_LEAD).max(0);
+                    self.command(&PlayerCmd::SeekTo(
+                        u32::try_from(position).unwrap_or_default(),
+                    ));
+                }
+            }
+            LyricSyncMsg::Save => {
+                if let Err(e) = self.te_lyric_sync_save() {
+                    self.mount_error_popup(format!("save synced lyrics error: {e}"));
+                }
+                return;
+            }
+            LyricSyncMsg::Cancel => {
+                self.te_lyric_sync_stop();
+                return;
+            }
+        }
+        self.te_lyric_sync_update_rows();
+    }
+
+    /// Called from the main loop to follow playback, so stamps can be checked by ear
+    pub fn te_lyric_sync_tick(&mut self) {
+        let Some(sync) = &self.te_lyric_sync else {
+            return;
+        };
+        let playing = sync.line_at(self.lyric_position());
+        if playing != self.te_lyric_sync_playing {
+            self.te_lyric_sync_playing = playing;
+            self.te_lyric_sync_update_rows();
+            self.redraw = true;
+        }
+    }
+
+    fn te_lyric_sync_save(&mut self) -> Result<()> {
+        let (Some(sync), Some(mut song)) = (&self.te_lyric_sync, self.tageditor_song.clone())
+        else {
+            return Ok(());
+        };
+        let text = sync.as_lrc_text()?;
+        song.set_lyric(&text, "eng");
+        song.save_lyric()?;
+        if let Some(current) = self.current_song.as_mut() {
+            current.set_parsed_lyric(Lyric::from_str(&text).ok());
+        }
+        self.te_lyric_sync_stop();
+        self.init_by_song(&song);
+        Ok(())
+    }
+
+    fn te_lyric_sync_update_rows(&mut self) {
+        let Some(sync) = &self.te_lyric_sync else {
+            return;
+        };
+        let highlight = self
+            .config
+            .style_color_symbol
+            .library_highlight()
+            .unwrap_or(Color::Cyan);
+        let mut table = TableBuilder::default();
+        for (index, (time_stamp, text)) in sync.lines().iter().enumerate() {
+            if index > 0 {
+                table.add_row();
+            }
+            let mut text = TextSpan::new(format!(" {text}"));
+            if Some(index) == self.te_lyric_sync_playing {
+                text = text.bold().fg(highlight);
+            }
+            table
+                .add_col(TextSpan::new(format_time_stamp(*time_stamp)).fg(highlight))
+                .add_col(text);
+        }
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::ListLyricSync),
+                Attribute::Content,
+                AttrValue::Table(table.build()),
+            )
+            .ok();
+    }
+
+    fn te_lyric_sync_select(&mut self, row: usize) {
+        self.app
+            .attr(
+                &Id::TagEditor(IdTagEditor::ListLyricSync),
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(row))),
+            )
+            .ok();
+    }
+}
@@ -2,6 +2,7 @@ use crate::ui::Model;
 use anyhow::{anyhow, Context, Result};
 use std::path::Path;
 use termusiclib::config::Settings;
+use termusiclib::cover;
 /**
  * MIT License
  *
@@ -25,7 +26,11 @@ use termusiclib::config::Settings;
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use termusiclib::songtag::{search, SongTag};
+#[cfg(feature = "fingerprint")]
+use termusiclib::fingerprint;
+#[cfg(feature = "fingerprint")]
+use termusiclib::songtag::acoustid::AcoustId;
+use termusiclib::songtag::{Registry, SongTag};
 use termusiclib::types::{Id, IdTagEditor, Msg, SearchLyricState, TEMsg, TFMsg};
 use tui_realm_stdlib::Table;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
@@ -244,17 +249,47 @@ impl Model {
                 }
             }
         }
-        search(&search_str, self.sender_songtag.clone());
+        self.songtag_options.clear();
+        Registry::new(&self.config.lyric_providers)
+            .search(&search_str, self.sender_songtag.clone());
+        #[cfg(feature = "fingerprint")]
+        self.te_songtag_identify();
+    }
+
+    /// Look the song up by its audio as well, which works when tags and file name say nothing.
+    #[cfg(feature = "fingerprint")]
+    fn te_songtag_identify(&self) {
+        let settings = &self.config.tag_editor_fingerprint;
+        if settings.api_key.is_empty() {
+            return;
+        }
+        if let Some(file) = self.tageditor_song.as_ref().and_then(|song| song.file()) {
+            fingerprint::search(
+                file,
+                Box::new(AcoustId::new(settings)),
+                self.sender_songtag.clone(),
+            );
+        }
     }
     pub fn te_update_lyric_options(&mut self) {
         if self
             .app
             .mounted(&Id::TagEditor(IdTagEditor::TableLyricOptions))
         {
-            if let Ok(SearchLyricState::Finish(l)) = self.receiver_songtag.try_recv() {
-                self.te_add_songtag_options(l);
-                self.redraw = true;
-            }
+            let items = match self.receiver_songtag.try_recv() {
+                Ok(SearchLyricState::Finish(l)) => {
+                    let mut items = std::mem::take(&mut self.songtag_options);
+                    items.extend(l);
+                    items
+                }
+                Ok(SearchLyricState::Identified(mut l)) => {
+                    l.append(&mut self.songtag_options);
+                    l
+                }
+                Err(_) => return,
+            };
+            self.te_add_songtag_options(items);
+            self.redraw = true;
         }
     }
 
@@ -265,7 +300,8 @@ impl Model {
             .with_context(|| format!("no song_tag with index {index} found"))?;
         if let Some(song) = &self.tageditor_song {
             let file = song.file().context("no file path found")?;
-            song_tag.download(file, &self.tx_to_main)?;
+            let providers = Registry::new(&self.config.lyric_providers);
+            song_tag.download(file, &providers, &self.tx_to_main)?;
         }
         Ok(())
     }
@@ -292,6 +328,25 @@ impl Model {
             {
                 song.set_genre(&genre);
             }
+            if let Ok(State::One(StateValue::String(album_artist))) = self
+                .app
+                .state(&Id::TagEditor(IdTagEditor::InputAlbumArtist))
+            {
+                song.set_album_artist(&album_artist);
+            }
+            song.set_year(self.te_input_number(IdTagEditor::InputYear));
+            song.set_track_number(self.te_input_number(IdTagEditor::InputTrack));
+            song.set_disc_number(self.te_input_number(IdTagEditor::InputDisc));
+            if let Ok(State::One(StateValue::String(composer))) =
+                self.app.state(&Id::TagEditor(IdTagEditor::InputComposer))
+            {
+                song.set_composer(&composer);
+            }
+            if let Ok(State::One(StateValue::String(comment))) =
+                self.app.state(&Id::TagEditor(IdTagEditor::InputComment))
+            {
+                song.set_comment(&comment);
+            }
             song.save_tag()?;
             self.init_by_song(&song);
             self.playlist_update_library_delete();
@@ -299,6 +354,14 @@ impl Model {
         Ok(())
     }
 
+    /// Numeric inputs report no state when empty or invalid, which clears the field.
+    fn te_input_number(&self, id: IdTagEditor) -> Option<u32> {
+        match self.app.state(&Id::TagEditor(id)) {
+            Ok(State::One(StateValue::String(value))) => value.parse().ok(),
+            _ => None,
+        }
+    }
+
     pub fn te_load_lyric_and_photo(&mut self, index: usize) -> Result<()> {
         if self.songtag_options.is_empty() {
             return Ok(());
@@ -309,6 +372,15 @@ impl Model {
                 .get(index)
                 .ok_or_else(|| anyhow!("cannot get songtag"))?;
             let lang_ext = song_tag.lang_ext().unwrap_or("eng");
+            let providers = Registry::new(&self.config.lyric_providers);
+            if self.config.lyric_save_to_sidecar {
+                // the song stays untouched, so tags and cover from the result are not applied
+                let lyric_string = providers.fetch_lyric(song_tag)?;
+                song.set_lyric_sidecar(&lyric_string, &self.config.lyric_sidecar_dir);
+                song.save_lyric_sidecars()?;
+                self.init_by_song(&song);
+                return Ok(());
+            }
             if let Some(artist) = song_tag.artist() {
                 song.set_artist(artist);
             }
@@ -319,10 +391,13 @@ impl Model {
                 song.set_album(album);
             }
 
-            if let Ok(lyric_string) = song_tag.fetch_lyric() {
+            if let Ok(lyric_string) = providers.fetch_lyric(song_tag) {
                 song.set_lyric(&lyric_string, lang_ext);
             }
-            if let Ok(artwork) = song_tag.fetch_photo() {
+            if let Ok(artwork) = providers
+                .fetch_cover(song_tag)
+                .and_then(|p| cover::downscale(p, self.config.tag_editor_cover_max_size))
+            {
                 song.set_photo(artwork);
             }
 
@@ -71,6 +71,9 @@ impl Component<Msg, NoUserEvent> for TETextareaLyric {
             Event::Keyboard(keyevent) if keyevent == self.config.keys.config_save.key_event() => {
                 return Some(Msg::TagEditor(TEMsg::TERename))
             }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => return Some(Msg::TagEditor(TEMsg::TELyricSyncStart)),
             Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
                 return Some(Msg::TagEditor(TEMsg::TEFocus(TFMsg::TextareaLyricBlurDown)))
             }
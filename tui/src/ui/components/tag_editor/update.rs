@@ -30,9 +30,13 @@ impl Model {
                 self.mount_tageditor(node_id);
             }
             TEMsg::TagEditorClose(_song) => {
+                self.te_lyric_sync = None;
                 if let Some(s) = self.tageditor_song.clone() {
                     self.library_reload_with_node_focus(s.file());
+                } else if !self.tageditor_batch.is_empty() {
+                    self.library_reload_with_node_focus(None);
                 }
+                self.tageditor_batch.clear();
                 self.umount_tageditor();
             }
 
@@ -58,12 +62,21 @@ impl Model {
                     self.mount_error_popup(format!("embed error: {e}"));
                 }
             }
+            TEMsg::TERename if !self.tageditor_batch.is_empty() => self.te_save_batch(),
             TEMsg::TERename => {
                 if let Err(e) = self.te_rename_song_by_tag() {
                     self.mount_error_popup(format!("rename song by tag error: {e}"));
                 }
             }
             TEMsg::TEFocus(m) => self.update_tag_editor_focus(m),
+            TEMsg::TELyricSyncStart => {
+                if let Err(e) = self.te_lyric_sync_start() {
+                    self.mount_error_popup(format!("sync lyrics error: {e}"));
+                }
+            }
+            TEMsg::TELyricSync(m) => self.te_update_lyric_sync(m),
+            TEMsg::TEFilenameTags(m) => self.te_update_filename_tags(m),
+            TEMsg::TECover(m) => self.te_update_cover(m),
         }
     }
 
@@ -79,17 +92,43 @@ impl Model {
                     .active(&Id::TagEditor(IdTagEditor::InputTitle))
                     .ok();
             }
-            TFMsg::InputTitleBlurDown | TFMsg::InputGenreBlurUp => {
+            TFMsg::InputTitleBlurDown | TFMsg::InputAlbumArtistBlurUp => {
                 self.app
                     .active(&Id::TagEditor(IdTagEditor::InputAlbum))
                     .ok();
             }
-            TFMsg::InputAlbumBlurDown | TFMsg::TableLyricOptionsBlurUp => {
+            TFMsg::InputAlbumBlurDown | TFMsg::InputGenreBlurUp => {
+                self.app
+                    .active(&Id::TagEditor(IdTagEditor::InputAlbumArtist))
+                    .ok();
+            }
+            TFMsg::InputAlbumArtistBlurDown | TFMsg::InputYearBlurUp => {
                 self.app
                     .active(&Id::TagEditor(IdTagEditor::InputGenre))
                     .ok();
             }
-            TFMsg::InputGenreBlurDown | TFMsg::SelectLyricBlurUp => {
+            TFMsg::InputGenreBlurDown | TFMsg::InputTrackBlurUp => {
+                self.app.active(&Id::TagEditor(IdTagEditor::InputYear)).ok();
+            }
+            TFMsg::InputYearBlurDown | TFMsg::InputDiscBlurUp => {
+                self.app
+                    .active(&Id::TagEditor(IdTagEditor::InputTrack))
+                    .ok();
+            }
+            TFMsg::InputTrackBlurDown | TFMsg::InputComposerBlurUp => {
+                self.app.active(&Id::TagEditor(IdTagEditor::InputDisc)).ok();
+            }
+            TFMsg::InputDiscBlurDown | TFMsg::InputCommentBlurUp => {
+                self.app
+                    .active(&Id::TagEditor(IdTagEditor::InputComposer))
+                    .ok();
+            }
+            TFMsg::InputComposerBlurDown | TFMsg::TableLyricOptionsBlurUp => {
+                self.app
+                    .active(&Id::TagEditor(IdTagEditor::InputComment))
+                    .ok();
+            }
+            TFMsg::InputCommentBlurDown | TFMsg::SelectLyricBlurUp => {
                 self.app
                     .active(&Id::TagEditor(IdTagEditor::TableLyricOptions))
                     .ok();
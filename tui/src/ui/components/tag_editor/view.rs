@@ -1,3 +1,5 @@
+use super::te_cover::COVER_KEY;
+use super::te_filename_tags::FILENAME_TAGS_KEY;
 /**
  * MIT License
  *
@@ -23,15 +25,18 @@
  */
 // use crate::config::Settings;
 use crate::ui::components::{
-    LabelGeneric, LabelSpan, TECounterDelete, TEInputAlbum, TEInputArtist, TEInputGenre,
-    TEInputTitle, TESelectLyric, TETableLyricOptions, TETextareaLyric,
+    LabelGeneric, LabelSpan, TECounterDelete, TEInputAlbum, TEInputAlbumArtist, TEInputArtist,
+    TEInputComment, TEInputComposer, TEInputDisc, TEInputGenre, TEInputTitle, TEInputTrack,
+    TEInputYear, TESelectLyric, TETableLyricOptions, TETextareaLyric,
 };
 use crate::ui::model::Model;
 use std::convert::TryFrom;
 use std::path::Path;
 use termusiclib::track::Track;
 use termusiclib::types::{Id, IdTagEditor};
-use termusiclib::utils::{draw_area_in_absolute, draw_area_top_right_absolute};
+use termusiclib::utils::{
+    draw_area_in_absolute, draw_area_in_relative, draw_area_top_right_absolute, filetype_supported,
+};
 use tuirealm::props::{Alignment, AttrValue, Attribute, Color, PropPayload, PropValue, TextSpan};
 use tuirealm::tui::layout::{Constraint, Direction, Layout};
 use tuirealm::tui::widgets::Clear;
@@ -59,7 +64,7 @@ impl Model {
                                 Constraint::Length(1),
                                 Constraint::Length(3),
                                 Constraint::Length(3),
-                                // Constraint::Length(3),
+                                Constraint::Length(3),
                                 Constraint::Min(2),
                                 Constraint::Length(1),
                             ]
@@ -85,11 +90,24 @@ impl Model {
                             .as_ref(),
                         )
                         .split(chunks_main[2]);
+                    let chunks_row3 = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .margin(0)
+                        .constraints(
+                            [
+                                Constraint::Ratio(1, 8),
+                                Constraint::Ratio(1, 8),
+                                Constraint::Ratio(3, 8),
+                                Constraint::Ratio(3, 8),
+                            ]
+                            .as_ref(),
+                        )
+                        .split(chunks_main[3]);
                     let chunks_row4 = Layout::default()
                         .direction(Direction::Horizontal)
                         .margin(0)
                         .constraints([Constraint::Ratio(3, 5), Constraint::Ratio(2, 5)].as_ref())
-                        .split(chunks_main[3]);
+                        .split(chunks_main[4]);
 
                     let chunks_row4_right = Layout::default()
                         .direction(Direction::Vertical)
@@ -118,25 +136,43 @@ impl Model {
                                 ]
                                 .as_ref(),
                             )
-                            .split(chunks_main[4]);
+                            .split(chunks_main[5]);
 
                         self.app.view(&Id::DownloadSpinner, f, chunks_footer[1]);
                         self.app.view(&Id::Label, f, chunks_footer[2]);
                     } else {
-                        self.app.view(&Id::Label, f, chunks_main[4]);
+                        self.app.view(&Id::Label, f, chunks_main[5]);
                     }
 
                     self.app
                         .view(&Id::TagEditor(IdTagEditor::LabelHint), f, chunks_main[0]);
-                    // self.app.view(&Id::Label, f, chunks_main[4]);
+                    // self.app.view(&Id::Label, f, chunks_main[5]);
                     self.app
                         .view(&Id::TagEditor(IdTagEditor::InputArtist), f, chunks_row1[0]);
                     self.app
                         .view(&Id::TagEditor(IdTagEditor::InputTitle), f, chunks_row1[1]);
                     self.app
                         .view(&Id::TagEditor(IdTagEditor::InputAlbum), f, chunks_row2[0]);
+                    self.app.view(
+                        &Id::TagEditor(IdTagEditor::InputAlbumArtist),
+                        f,
+                        chunks_row2[1],
+                    );
+                    self.app
+                        .view(&Id::TagEditor(IdTagEditor::InputGenre), f, chunks_row2[2]);
+                    self.app
+                        .view(&Id::TagEditor(IdTagEditor::InputYear), f, chunks_row2[3]);
+                    self.app
+                        .view(&Id::TagEditor(IdTagEditor::InputTrack), f, chunks_row3[0]);
                     self.app
-                        .view(&Id::TagEditor(IdTagEditor::InputGenre), f, chunks_row2[1]);
+                        .view(&Id::TagEditor(IdTagEditor::InputDisc), f, chunks_row3[1]);
+                    self.app.view(
+                        &Id::TagEditor(IdTagEditor::InputComposer),
+                        f,
+                        chunks_row3[2],
+                    );
+                    self.app
+                        .view(&Id::TagEditor(IdTagEditor::InputComment), f, chunks_row3[3]);
                     self.app.view(
                         &Id::TagEditor(IdTagEditor::TableLyricOptions),
                         f,
@@ -152,11 +188,52 @@ impl Model {
                         f,
                         chunks_row4_right_top[1],
                     );
-                    self.app.view(
-                        &Id::TagEditor(IdTagEditor::TextareaLyric),
-                        f,
-                        chunks_row4_right[1],
-                    );
+                    if self.app.mounted(&Id::TagEditor(IdTagEditor::ListLyricSync)) {
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::ListLyricSync),
+                            f,
+                            chunks_row4_right[1],
+                        );
+                    } else {
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::TextareaLyric),
+                            f,
+                            chunks_row4_right[1],
+                        );
+                    }
+
+                    if self
+                        .app
+                        .mounted(&Id::TagEditor(IdTagEditor::InputFilenamePattern))
+                    {
+                        let popup = draw_area_in_relative(f.size(), 90, 70);
+                        f.render_widget(Clear, popup);
+                        let chunks_popup = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(0)
+                            .constraints([Constraint::Length(3), Constraint::Min(3)].as_ref())
+                            .split(popup);
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::InputFilenamePattern),
+                            f,
+                            chunks_popup[0],
+                        );
+                        self.app.view(
+                            &Id::TagEditor(IdTagEditor::TableFilenamePreview),
+                            f,
+                            chunks_popup[1],
+                        );
+                    }
+
+                    if self
+                        .app
+                        .mounted(&Id::TagEditor(IdTagEditor::InputCoverFile))
+                    {
+                        let popup = draw_area_in_absolute(f.size(), f.size().width * 4 / 5, 3);
+                        f.render_widget(Clear, popup);
+                        self.app
+                            .view(&Id::TagEditor(IdTagEditor::InputCoverFile), f, popup);
+                    }
 
                     if self.app.mounted(&Id::MessagePopup) {
                         let popup = draw_area_top_right_absolute(f.size(), 25, 4);
@@ -176,90 +253,15 @@ impl Model {
     pub fn mount_tageditor(&mut self, node_id: &str) {
         let p: &Path = Path::new(node_id);
         if p.is_dir() {
-            self.mount_error_popup("directory doesn't have tag!");
+            self.mount_tageditor_batch(files_in_dir(p));
             return;
         }
 
         let p = p.to_string_lossy();
         match Track::read_from_path(p.as_ref(), false) {
-            Ok(s) => {
-                self.remount_tag_editor_label_help();
-                assert!(self
-                    .app
-                    .remount(
-                        Id::TagEditor(IdTagEditor::LabelHint),
-                        Box::new(LabelGeneric::new(&self.config, "Press <ENTER> to search:")),
-                        vec![]
-                    )
-                    .is_ok());
-                assert!(self
-                    .app
-                    .remount(
-                        Id::TagEditor(IdTagEditor::InputArtist),
-                        Box::new(TEInputArtist::new(&self.config)),
-                        vec![]
-                    )
-                    .is_ok());
-                assert!(self
-                    .app
-                    .remount(
-                        Id::TagEditor(IdTagEditor::InputTitle),
-                        Box::new(TEInputTitle::new(&self.config)),
-                        vec![]
-                    )
-                    .is_ok());
-                assert!(self
-                    .app
-                    .remount(
-                        Id::TagEditor(IdTagEditor::InputAlbum),
-                        Box::new(TEInputAlbum::new(&self.config)),
-                        vec![]
-                    )
-                    .is_ok());
-                assert!(self
-                    .app
-                    .remount(
-                        Id::TagEditor(IdTagEditor::InputGenre),
-                        Box::new(TEInputGenre::new(&self.config)),
-                        vec![]
-                    )
-                    .is_ok());
-                assert!(self
-                    .app
-                    .remount(
-                        Id::TagEditor(IdTagEditor::TableLyricOptions),
-                        Box::new(TETableLyricOptions::new(&self.config)),
-                        vec![]
-                    )
-                    .is_ok());
-                assert!(self
-                    .app
-                    .remount(
-                        Id::TagEditor(IdTagEditor::SelectLyric),
-                        Box::new(TESelectLyric::new(&self.config)),
-                        vec![]
-                    )
-                    .is_ok());
-                assert!(self
-                    .app
-                    .remount(
-                        Id::TagEditor(IdTagEditor::CounterDelete),
-                        Box::new(TECounterDelete::new(5, &self.config)),
-                        vec![]
-                    )
-                    .is_ok());
-                assert!(self
-                    .app
-                    .remount(
-                        Id::TagEditor(IdTagEditor::TextareaLyric),
-                        Box::new(TETextareaLyric::new(&self.config)),
-                        vec![]
-                    )
-                    .is_ok());
-
-                self.app
-                    .active(&Id::TagEditor(IdTagEditor::InputArtist))
-                    .ok();
+            Ok(mut s) => {
+                s.load_lyric_dir(&self.config.lyric_sidecar_dir);
+                self.mount_tageditor_components();
                 self.init_by_song(&s);
             }
             Err(e) => {
@@ -270,6 +272,136 @@ impl Model {
             self.mount_error_popup(format!("clear photo error: {e}"));
         }
     }
+
+    #[allow(clippy::too_many_lines)]
+    pub fn mount_tageditor_components(&mut self) {
+        self.remount_tag_editor_label_help();
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::LabelHint),
+                Box::new(LabelGeneric::new(&self.config, "Press <ENTER> to search:")),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputArtist),
+                Box::new(TEInputArtist::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputTitle),
+                Box::new(TEInputTitle::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputAlbum),
+                Box::new(TEInputAlbum::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputGenre),
+                Box::new(TEInputGenre::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputAlbumArtist),
+                Box::new(TEInputAlbumArtist::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputYear),
+                Box::new(TEInputYear::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputTrack),
+                Box::new(TEInputTrack::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputDisc),
+                Box::new(TEInputDisc::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputComposer),
+                Box::new(TEInputComposer::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::InputComment),
+                Box::new(TEInputComment::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::TableLyricOptions),
+                Box::new(TETableLyricOptions::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::SelectLyric),
+                Box::new(TESelectLyric::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::CounterDelete),
+                Box::new(TECounterDelete::new(5, &self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::TagEditor(IdTagEditor::TextareaLyric),
+                Box::new(TETextareaLyric::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+
+        self.app
+            .active(&Id::TagEditor(IdTagEditor::InputArtist))
+            .ok();
+    }
+
     pub fn umount_tageditor(&mut self) {
         self.mount_label_help();
         self.app.umount(&Id::TagEditor(IdTagEditor::LabelHint)).ok();
@@ -285,6 +417,20 @@ impl Model {
         self.app
             .umount(&Id::TagEditor(IdTagEditor::InputGenre))
             .ok();
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::InputAlbumArtist))
+            .ok();
+        self.app.umount(&Id::TagEditor(IdTagEditor::InputYear)).ok();
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::InputTrack))
+            .ok();
+        self.app.umount(&Id::TagEditor(IdTagEditor::InputDisc)).ok();
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::InputComposer))
+            .ok();
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::InputComment))
+            .ok();
         // self.app.umount(&Id::TagEditor(IdTagEditor::RadioTag)).ok();
         self.app
             .umount(&Id::TagEditor(IdTagEditor::TableLyricOptions))
@@ -298,6 +444,18 @@ impl Model {
         self.app
             .umount(&Id::TagEditor(IdTagEditor::TextareaLyric))
             .ok();
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::ListLyricSync))
+            .ok();
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::InputFilenamePattern))
+            .ok();
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::TableFilenamePreview))
+            .ok();
+        self.app
+            .umount(&Id::TagEditor(IdTagEditor::InputCoverFile))
+            .ok();
         if let Err(e) = self.update_photo() {
             self.mount_error_popup(format!("update photo error: {e}"));
         }
@@ -351,6 +509,35 @@ impl Model {
                 .is_ok());
         }
 
+        for (id, value) in [
+            (
+                IdTagEditor::InputAlbumArtist,
+                s.album_artist().map(str::to_string),
+            ),
+            (IdTagEditor::InputYear, s.year().map(|y| y.to_string())),
+            (
+                IdTagEditor::InputTrack,
+                s.track_number().map(|t| t.to_string()),
+            ),
+            (
+                IdTagEditor::InputDisc,
+                s.disc_number().map(|d| d.to_string()),
+            ),
+            (IdTagEditor::InputComposer, s.composer().map(str::to_string)),
+            (IdTagEditor::InputComment, s.comment().map(str::to_string)),
+        ] {
+            if let Some(value) = value {
+                assert!(self
+                    .app
+                    .attr(
+                        &Id::TagEditor(id),
+                        Attribute::Value,
+                        AttrValue::String(value)
+                    )
+                    .is_ok());
+            }
+        }
+
         if s.lyric_frames_is_empty() {
             self.init_by_song_no_lyric();
             return;
@@ -417,7 +604,7 @@ impl Model {
             .is_ok());
     }
 
-    fn init_by_song_no_lyric(&mut self) {
+    pub fn init_by_song_no_lyric(&mut self) {
         assert!(self
             .app
             .attr(
@@ -512,6 +699,28 @@ impl Model {
                             .style_color_symbol
                             .library_foreground()
                             .unwrap_or(Color::White)),
+                        TextSpan::new(format!("<{FILENAME_TAGS_KEY}>"))
+                            .bold()
+                            .fg(self
+                                .config
+                                .style_color_symbol
+                                .library_highlight()
+                                .unwrap_or(Color::Cyan)),
+                        TextSpan::new(" Tags from file name ").fg(self
+                            .config
+                            .style_color_symbol
+                            .library_foreground()
+                            .unwrap_or(Color::White)),
+                        TextSpan::new(format!("<{COVER_KEY}>")).bold().fg(self
+                            .config
+                            .style_color_symbol
+                            .library_highlight()
+                            .unwrap_or(Color::Cyan)),
+                        TextSpan::new(" Cover ").fg(self
+                            .config
+                            .style_color_symbol
+                            .library_foreground()
+                            .unwrap_or(Color::White)),
                         TextSpan::new(format!("<{}>", self.config.keys.library_search_youtube))
                             .bold()
                             .fg(self
@@ -530,4 +739,52 @@ impl Model {
             )
             .is_ok());
     }
+
+    pub fn remount_tag_editor_label_sync(&mut self) {
+        let highlight = self
+            .config
+            .style_color_symbol
+            .library_highlight()
+            .unwrap_or(Color::Cyan);
+        let foreground = self
+            .config
+            .style_color_symbol
+            .library_foreground()
+            .unwrap_or(Color::White);
+        let mut spans = vec![];
+        for (key, action) in [
+            ("<ENTER>".to_string(), " Stamp line "),
+            ("<Left/Right>".to_string(), " Nudge "),
+            ("<Backspace>".to_string(), " Clear "),
+            ("<p>".to_string(), " Preview "),
+            (format!("<{}>", self.config.keys.config_save), " Save "),
+            (format!("<{}>", self.config.keys.global_esc), " Back "),
+        ] {
+            spans.push(TextSpan::new(key).bold().fg(highlight));
+            spans.push(TextSpan::new(action).fg(foreground));
+        }
+        assert!(self
+            .app
+            .remount(
+                Id::Label,
+                Box::new(LabelSpan::new(&self.config, &spans)),
+                Vec::default(),
+            )
+            .is_ok());
+    }
+}
+
+/// Audio files directly in `dir`, sorted by name
+fn files_in_dir(dir: &Path) -> Vec<String> {
+    let mut files: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path().to_string_lossy().to_string())
+                .filter(|f| filetype_supported(f))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
 }
@@ -21,7 +21,7 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use crate::ui::model::{Model, ViuerSupported};
+use crate::ui::model::{Model, TermusicLayout, ViuerSupported};
 #[cfg(feature = "cover")]
 use anyhow::bail;
 use anyhow::{anyhow, Result};
@@ -30,7 +30,7 @@ use image::io::Reader as ImageReader;
 use image::DynamicImage;
 use lofty::Picture;
 use std::io::Write;
-use termusiclib::track::MediaType;
+use termusiclib::track::{MediaType, Track};
 use termusiclib::types::{DLMsg, Id, IdConfigEditor, IdTagEditor, ImageWrapper, Msg};
 
 impl Model {
@@ -70,9 +70,18 @@ impl Model {
         if self.app.mounted(&Id::HelpPopup) {
             return true;
         }
+        if self.app.mounted(&Id::StatsPopup) {
+            return true;
+        }
+        if self.app.mounted(&Id::OrganizePopup) {
+            return true;
+        }
         if self.app.mounted(&Id::PodcastSearchTablePopup) {
             return true;
         }
+        if self.app.mounted(&Id::PodcastQueueTablePopup) {
+            return true;
+        }
 
         if self.app.mounted(&Id::TagEditor(IdTagEditor::InputTitle)) {
             return true;
@@ -108,8 +117,16 @@ impl Model {
         if self.should_not_show_photo() {
             return Ok(());
         }
-        let Some(track) = self.playlist.current_track().cloned() else {
-            return Ok(())
+        let track = if self.layout == TermusicLayout::Browse {
+            let Some(file) = &self.browse_cover else {
+                return Ok(());
+            };
+            Track::read_from_path(file, false)?
+        } else {
+            let Some(track) = self.playlist.current_track().cloned() else {
+                return Ok(());
+            };
+            track
         };
 
         match track.media_type {
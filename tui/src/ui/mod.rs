@@ -80,9 +80,11 @@ impl UI {
         let mut progress_interval = 0;
         while !self.model.quit {
             self.model.te_update_lyric_options();
+            self.model.te_lyric_sync_tick();
+            self.model.te_update_batch_save();
             // self.model.update_player_msg();
             self.model.update_outside_msg();
-            if self.model.layout != TermusicLayout::Podcast {
+            if self.model.layout != TermusicLayout::Podcast || self.model.lyric_shows_transcript() {
                 self.model.lyric_update();
             }
             if progress_interval == 0 {
@@ -150,6 +152,7 @@ impl UI {
             .set_current_track_index(current_track_index);
         self.model.playlist_locate(current_track_index);
         self.model.current_song = self.model.playlist.current_track().cloned();
+        self.model.podcast_load_transcript();
         self.model.update_layout_for_current_track();
         self.model.player_update_current_track_after();
 
@@ -211,11 +214,20 @@ impl UI {
                         i64::from(response.position),
                         i64::from(response.duration),
                     );
+                    // The playlist may have changed under the index that comes with it
+                    if response.podcast_queue_advanced {
+                        self.model.podcast_queue_advanced_by_server();
+                    }
                     if response.current_track_updated {
                         self.handle_current_track_index(response.current_track_index as usize);
                     }
 
                     self.model.lyric_update_for_radio(&response.radio_title);
+                    self.model.podcast_changed_by_server(
+                        response.podcast_new_episodes,
+                        response.podcast_files_changed,
+                        response.podcast_sync_changes,
+                    );
 
                     self.handle_status(Status::from_u32(response.status));
                 }
@@ -242,6 +254,24 @@ impl UI {
                         .progress_update(i64::from(position), i64::from(duration));
                     self.model.force_redraw();
                 }
+                PlayerCmd::SeekTo(position) => {
+                    let (position, duration) = self.playback.seek_to(position).await?;
+                    self.model
+                        .progress_update(i64::from(position), i64::from(duration));
+                    self.model.force_redraw();
+                }
+                PlayerCmd::NextChapter => {
+                    let (position, duration) = self.playback.next_chapter().await?;
+                    self.model
+                        .progress_update(i64::from(position), i64::from(duration));
+                    self.model.force_redraw();
+                }
+                PlayerCmd::PreviousChapter => {
+                    let (position, duration) = self.playback.previous_chapter().await?;
+                    self.model
+                        .progress_update(i64::from(position), i64::from(duration));
+                    self.model.force_redraw();
+                }
                 PlayerCmd::SpeedDown => {
                     self.model.config.player_speed = self.playback.speed_down().await?;
                     self.model.progress_update_title();
@@ -24,8 +24,9 @@
 mod update;
 mod view;
 mod youtube_options;
+use crate::ui::components::{KaraokeView, NotesView};
 use crate::ui::Application;
-use termusiclib::sqlite::{DataBase, SearchCriteria};
+use termusiclib::sqlite::{AlbumForDB, DataBase, SearchCriteria};
 use termusiclib::types::{Id, Msg, SearchLyricState, YoutubeOptions};
 
 #[cfg(feature = "cover")]
@@ -35,13 +36,18 @@ use termusiclib::{
     track::{MediaType, Track},
 };
 
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::time::{Duration, Instant};
 use termusiclib::config::{Keys, StyleColorSymbol};
-use termusiclib::podcast::{db::Database as DBPod, Podcast, PodcastFeed, Threadpool};
+use termusiclib::organize::Plan;
+use termusiclib::podcast::queue::Generator;
+use termusiclib::podcast::{db::Database as DBPod, Episode, Podcast, PodcastFeed, Threadpool};
+use termusiclib::songtag::lrc::LyricSync;
 use termusiclib::songtag::SongTag;
 use termusiclib::sqlite::TrackForDB;
+use termusiclib::tag_batch::BatchSaveState;
 // use termusiclib::track::MediaType;
 use termusiclib::utils::{get_app_config_path, DownloadTracker};
 // use termusicplayback::{GeneralPlayer, PlayerMsg, PlayerTrait};
@@ -57,6 +63,7 @@ pub enum TermusicLayout {
     TreeView,
     DataBase,
     Podcast,
+    Browse,
 }
 
 #[derive(PartialEq, Clone, Eq)]
@@ -67,6 +74,7 @@ pub enum ConfigEditorLayout {
     Key2,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct Model {
     /// Indicates that the application must quit
     pub quit: bool,
@@ -83,8 +91,22 @@ pub struct Model {
     pub yanked_node_id: Option<String>,
     pub current_song: Option<Track>,
     pub tageditor_song: Option<Track>,
+    /// Tracks edited together, empty when editing one
+    pub tageditor_batch: Vec<Track>,
+    pub receiver_tag_batch: Option<Receiver<BatchSaveState>>,
+    /// Moves shown in the organize popup, waiting to be confirmed
+    pub organize_plan: Option<Plan>,
+    pub te_lyric_sync: Option<LyricSync>,
+    /// Line of `te_lyric_sync` the playback is at
+    pub te_lyric_sync_playing: Option<usize>,
     pub time_pos: i64,
+    /// When `time_pos` last changed, to follow lyrics within the second
+    pub time_pos_updated: Instant,
     pub lyric_line: String,
+    pub lyric_karaoke: Option<KaraokeView>,
+    pub lyric_notes: NotesView,
+    /// The podcast layout shows the details of the playing episode instead of its transcript
+    pub lyric_transcript_hidden: bool,
     youtube_options: YoutubeOptions,
     #[cfg(feature = "cover")]
     pub ueberzug_instance: UeInstance,
@@ -99,6 +121,13 @@ pub struct Model {
     pub db_criteria: SearchCriteria,
     pub db_search_results: Vec<String>,
     pub db_search_tracks: Vec<TrackForDB>,
+    pub db_duplicate_groups: Vec<Vec<TrackForDB>>,
+    pub browse_artists: Vec<String>,
+    pub browse_album_artist: String,
+    pub browse_albums: Vec<AlbumForDB>,
+    pub browse_tracks: Vec<TrackForDB>,
+    /// A file of the album highlighted in the browse layout, whose cover is shown
+    pub browse_cover: Option<String>,
     pub layout: TermusicLayout,
     pub config_layout: ConfigEditorLayout,
     pub config_changed: bool,
@@ -110,6 +139,16 @@ pub struct Model {
     pub tx_to_main: Sender<Msg>,
     pub rx_to_main: Receiver<Msg>,
     pub podcast_search_vec: Option<Vec<PodcastFeed>>,
+    /// Episodes whose chapters file was already fetched, or is being fetched
+    pub podcast_chapters_requested: HashSet<i64>,
+    /// Episodes whose transcript was already fetched, or is being fetched
+    pub podcast_transcripts_requested: HashSet<i64>,
+    /// Queued episodes as listed in the queue popup
+    pub podcast_queue: Vec<Episode>,
+    /// Episodes whose download failed, with the reason
+    pub podcast_failed_downloads: Vec<(Episode, String)>,
+    /// What was last queued, offered again in the queue popup
+    pub podcast_queue_generator: Generator,
     pub playlist: Playlist,
     pub cmd_tx: UnboundedSender<PlayerCmd>,
 }
@@ -174,8 +213,17 @@ impl Model {
             yanked_node_id: None,
             // current_song: None,
             tageditor_song: None,
+            tageditor_batch: vec![],
+            receiver_tag_batch: None,
+            organize_plan: None,
+            te_lyric_sync: None,
+            te_lyric_sync_playing: None,
             time_pos: 0,
+            time_pos_updated: Instant::now(),
             lyric_line: String::new(),
+            lyric_karaoke: None,
+            lyric_notes: NotesView::default(),
+            lyric_transcript_hidden: false,
             youtube_options: YoutubeOptions::default(),
             #[cfg(feature = "cover")]
             ueberzug_instance,
@@ -192,6 +240,12 @@ impl Model {
             db_criteria,
             db_search_results: Vec::new(),
             db_search_tracks: Vec::new(),
+            db_duplicate_groups: Vec::new(),
+            browse_artists: Vec::new(),
+            browse_album_artist: String::new(),
+            browse_albums: Vec::new(),
+            browse_tracks: Vec::new(),
+            browse_cover: None,
             config_changed: false,
             podcasts,
             podcasts_index: 0,
@@ -201,6 +255,11 @@ impl Model {
             rx_to_main,
             download_tracker: DownloadTracker::default(),
             podcast_search_vec: None,
+            podcast_chapters_requested: HashSet::new(),
+            podcast_transcripts_requested: HashSet::new(),
+            podcast_queue: Vec::new(),
+            podcast_failed_downloads: Vec::new(),
+            podcast_queue_generator: Generator::default(),
             playlist,
             cmd_tx,
             current_song: None,
@@ -263,6 +322,7 @@ impl Model {
 
     pub fn player_update_current_track_after(&mut self) {
         self.time_pos = 0;
+        self.time_pos_updated = Instant::now();
         if let Err(e) = self.update_photo() {
             self.mount_error_popup(format!("update photo error: {e}"));
         };
@@ -3,9 +3,10 @@ use std::thread::{self, sleep};
 use std::time::Duration;
 use termusiclib::sqlite::SearchCriteria;
 // use termusiclib::track::MediaType;
-use termusiclib::track::MediaType;
+use termusiclib::track::{MediaType, Track};
 use termusiclib::types::{
-    DBMsg, DLMsg, GSMsg, Id, IdTagEditor, LIMsg, LyricMsg, Msg, PCMsg, PLMsg, XYWHMsg, YSMsg,
+    BrowseMsg, DBMsg, DLMsg, GSMsg, Id, IdTagEditor, LIMsg, LyricMsg, Msg, PCMsg, PLMsg, XYWHMsg,
+    YSMsg,
 };
 use termusicplayback::PlayerCmd;
 /**
@@ -93,7 +94,9 @@ impl Update<Msg> for Model {
                 | Msg::PlayerVolumeUp
                 | Msg::PlayerVolumeDown
                 | Msg::PlayerSeekForward
-                | Msg::PlayerSeekBackward => self.update_player(&msg),
+                | Msg::PlayerSeekBackward
+                | Msg::PlayerNextChapter
+                | Msg::PlayerPreviousChapter => self.update_player(&msg),
 
                 Msg::HelpPopupShow => {
                     self.mount_help_popup();
@@ -106,6 +109,23 @@ impl Update<Msg> for Model {
                     self.update_photo().ok();
                     None
                 }
+                Msg::StatsPopupShow => {
+                    self.mount_stats_popup();
+                    None
+                }
+                Msg::StatsPopupClose => {
+                    self.umount_stats_popup();
+                    None
+                }
+                Msg::OrganizePopupCloseOk => {
+                    self.library_organize_apply();
+                    None
+                }
+                Msg::OrganizePopupCloseCancel => {
+                    self.organize_plan = None;
+                    self.umount_organize_popup();
+                    None
+                }
                 Msg::YoutubeSearch(m) => {
                     self.update_youtube_search(&m);
                     None
@@ -128,8 +148,24 @@ impl Update<Msg> for Model {
                     }
                     None
                 }
-                Msg::LayoutDataBase | Msg::LayoutTreeView | Msg::LayoutPodCast => {
-                    self.update_layout(&msg)
+                Msg::LayoutDataBase
+                | Msg::LayoutTreeView
+                | Msg::LayoutPodCast
+                | Msg::LayoutBrowse => {
+                    let was_browse = self.layout == TermusicLayout::Browse;
+                    let msg = self.update_layout(&msg);
+                    // the browse layout shows the highlighted album's cover instead of the
+                    // playing track's, so swap it when entering or leaving
+                    if was_browse != (self.layout == TermusicLayout::Browse) {
+                        if let Err(e) = self.update_photo() {
+                            self.mount_error_popup(format!("update photo error: {e}"));
+                        }
+                    }
+                    msg
+                }
+                Msg::Browse(m) => {
+                    self.update_browse(&m);
+                    None
                 }
 
                 Msg::None => None,
@@ -201,7 +237,16 @@ impl Model {
                 TermusicLayout::TreeView => self.app.active(&Id::Library).ok(),
                 TermusicLayout::DataBase => self.app.active(&Id::DBListCriteria).ok(),
                 TermusicLayout::Podcast => self.app.active(&Id::Podcast).ok(),
+                TermusicLayout::Browse => self.app.active(&Id::BrowseArtists).ok(),
             },
+            LyricMsg::LyricToggleScroll => {
+                self.lyric_toggle_scroll();
+                None
+            }
+            LyricMsg::LyricSeekLine(row) => {
+                self.lyric_seek_line(*row);
+                None
+            }
         };
         None
     }
@@ -228,11 +273,91 @@ impl Model {
                 if url.starts_with("http") {
                     self.podcast_add(url);
                 } else {
-                    self.podcast_search_itunes(url);
+                    self.podcast_search_directories(url);
                     self.mount_podcast_search_table();
                 }
             }
             PCMsg::PodcastAddPopupCloseCancel => self.umount_podcast_add_popup(),
+            PCMsg::PolicyPopupShow => {
+                if let Err(e) = self.podcast_policy_show() {
+                    self.mount_error_popup(format!("Error in podcast policy: {e}"));
+                }
+            }
+            PCMsg::PolicyPopupCloseOk(input) => {
+                if let Err(e) = self.podcast_policy_save(input) {
+                    self.mount_error_popup(format!("Error in podcast policy: {e}"));
+                    return None;
+                }
+                self.umount_podcast_policy_popup();
+            }
+            PCMsg::PolicyPopupCloseCancel => self.umount_podcast_policy_popup(),
+            PCMsg::FilterPopupShow => {
+                if let Err(e) = self.podcast_filter_show() {
+                    self.mount_error_popup(format!("Error in podcast filter: {e}"));
+                }
+            }
+            PCMsg::FilterPopupCloseOk(input) => {
+                if let Err(e) = self.podcast_filter_save(input) {
+                    self.mount_error_popup(format!("Error in podcast filter: {e}"));
+                    return None;
+                }
+                self.umount_podcast_filter_popup();
+            }
+            PCMsg::FilterPopupCloseCancel => self.umount_podcast_filter_popup(),
+            PCMsg::QueuePopupShow => self.mount_podcast_queue_popup(),
+            PCMsg::QueuePopupCloseOk(input) => {
+                if let Err(e) = self.podcast_queue_add(input) {
+                    self.mount_error_popup(format!("Error in podcast queue: {e}"));
+                    return None;
+                }
+                self.umount_podcast_queue_popup();
+            }
+            PCMsg::QueuePopupCloseCancel => self.umount_podcast_queue_popup(),
+            PCMsg::QueueTableShow => {
+                if let Err(e) = self.podcast_queue_show() {
+                    self.mount_error_popup(format!("Error in podcast queue: {e}"));
+                }
+            }
+            PCMsg::QueueTableCloseCancel => self.umount_podcast_queue_table(),
+            PCMsg::QueuePlay(index) => {
+                self.umount_podcast_queue_table();
+                if let Err(e) = self.podcast_queue_play(*index) {
+                    self.mount_error_popup(format!("Error in podcast queue: {e}"));
+                }
+            }
+            PCMsg::QueueRemove(index) => {
+                if let Err(e) = self.podcast_queue_remove(*index) {
+                    self.mount_error_popup(format!("Error in podcast queue: {e}"));
+                }
+            }
+            PCMsg::QueueClear => {
+                if let Err(e) = self.podcast_queue_clear() {
+                    self.mount_error_popup(format!("Error in podcast queue: {e}"));
+                }
+            }
+            PCMsg::NotesSelect(row) => self.lyric_notes_select(*row),
+            PCMsg::NotesCopyLink(row) => self.lyric_notes_copy_link(*row),
+            PCMsg::FailedTableShow => {
+                if let Err(e) = self.podcast_failed_show() {
+                    self.mount_error_popup(format!("Error in failed downloads: {e}"));
+                }
+            }
+            PCMsg::FailedTableCloseCancel => self.umount_podcast_failed_table(),
+            PCMsg::FailedRetry(index) => {
+                if let Err(e) = self.podcast_failed_retry(*index) {
+                    self.mount_error_popup(format!("Error in failed downloads: {e}"));
+                }
+            }
+            PCMsg::FailedRemove(index) => {
+                if let Err(e) = self.podcast_failed_remove(*index) {
+                    self.mount_error_popup(format!("Error in failed downloads: {e}"));
+                }
+            }
+            PCMsg::FailedClear => {
+                if let Err(e) = self.podcast_failed_clear() {
+                    self.mount_error_popup(format!("Error in failed downloads: {e}"));
+                }
+            }
             PCMsg::SyncData((id, pod)) => {
                 self.download_tracker.decrease_one(&pod.url);
                 self.show_message_timeout_label_help(
@@ -273,7 +398,24 @@ impl Model {
                     self.mount_error_popup(format!("Error sync episodes: {e}"));
                 }
             }
-            PCMsg::DescriptionUpdate => self.lyric_update(),
+            PCMsg::DescriptionUpdate => {
+                self.lyric_update();
+                self.lyric_update_title();
+            }
+            PCMsg::TranscriptFetched(episode_id) => {
+                let playing = self
+                    .podcasts
+                    .iter()
+                    .flat_map(|pod| &pod.episodes)
+                    .find(|ep| ep.id == *episode_id)
+                    .map_or(false, |ep| {
+                        self.current_song.as_ref().and_then(Track::file) == Some(ep.url.as_str())
+                    });
+                if playing {
+                    self.podcast_load_transcript();
+                    self.lyric_update();
+                }
+            }
             PCMsg::EpisodeAdd(index) => {
                 if let Err(e) = self.playlist_add_episode(*index) {
                     self.mount_error_popup(format!("Error add episode: {e}"));
@@ -322,10 +464,17 @@ impl Model {
                     None,
                 );
             }
-            PCMsg::DLComplete(ep_data) => {
+            PCMsg::DLComplete(ep_data) | PCMsg::DLUnchecked(ep_data) => {
+                self.episode_download_record(msg);
                 if let Err(e) = self.episode_download_complete(ep_data.clone()) {
                     self.mount_error_popup(format!("Error in inserting episode: {e}"));
                 }
+                if matches!(msg, PCMsg::DLUnchecked(_)) {
+                    self.mount_error_popup(format!(
+                        "downloaded episode: {}, but the server and feed gave nothing to check it is complete",
+                        ep_data.title
+                    ));
+                }
                 self.download_tracker.decrease_one(&ep_data.url);
                 self.show_message_timeout_label_help(
                     self.download_tracker.message_download_complete(),
@@ -335,8 +484,12 @@ impl Model {
                 );
             }
             PCMsg::DLResponseError(ep_data) => {
+                self.episode_download_record(msg);
                 self.download_tracker.decrease_one(&ep_data.url);
-                self.mount_error_popup(format!("download failed for episode: {}", ep_data.title));
+                self.mount_error_popup(format!(
+                    "download failed for episode: {}, {} lists failed downloads",
+                    ep_data.title, self.config.keys.podcast_download_failed
+                ));
                 self.show_message_timeout_label_help(
                     self.download_tracker
                         .message_download_error_response(&ep_data.title),
@@ -346,8 +499,12 @@ impl Model {
                 );
             }
             PCMsg::DLFileCreateError(ep_data) => {
+                self.episode_download_record(msg);
                 self.download_tracker.decrease_one(&ep_data.url);
-                self.mount_error_popup(format!("download failed for episode: {}", ep_data.title));
+                self.mount_error_popup(format!(
+                    "download failed for episode: {}, {} lists failed downloads",
+                    ep_data.title, self.config.keys.podcast_download_failed
+                ));
                 self.show_message_timeout_label_help(
                     self.download_tracker
                         .message_download_error_file_create(&ep_data.title),
@@ -357,8 +514,12 @@ impl Model {
                 );
             }
             PCMsg::DLFileWriteError(ep_data) => {
+                self.episode_download_record(msg);
                 self.download_tracker.decrease_one(&ep_data.url);
-                self.mount_error_popup(format!("download failed for episode: {}", ep_data.title));
+                self.mount_error_popup(format!(
+                    "download failed for episode: {}, {} lists failed downloads",
+                    ep_data.title, self.config.keys.podcast_download_failed
+                ));
                 self.show_message_timeout_label_help(
                     self.download_tracker
                         .message_download_error_file_write(&ep_data.title),
@@ -434,6 +595,8 @@ impl Model {
                 }
                 self.command(&PlayerCmd::SeekBackward);
             }
+            Msg::PlayerNextChapter => self.command(&PlayerCmd::NextChapter),
+            Msg::PlayerPreviousChapter => self.command(&PlayerCmd::PreviousChapter),
             Msg::PlayerSpeedUp => {
                 self.command(&PlayerCmd::SpeedUp);
             }
@@ -453,6 +616,7 @@ impl Model {
         }
         None
     }
+    #[allow(clippy::too_many_lines)]
     fn update_layout(&mut self, msg: &Msg) -> Option<Msg> {
         match msg {
             Msg::LayoutDataBase => {
@@ -544,9 +708,63 @@ impl Model {
                 self.playlist_switch_layout();
                 None
             }
+
+            Msg::LayoutBrowse => {
+                let mut need_to_set_focus = true;
+                for id in [
+                    Id::BrowseArtists,
+                    Id::BrowseAlbums,
+                    Id::BrowseTracks,
+                    Id::Playlist,
+                ] {
+                    if let Ok(Some(AttrValue::Flag(true))) = self.app.query(&id, Attribute::Focus) {
+                        need_to_set_focus = false;
+                    }
+                }
+                if need_to_set_focus {
+                    self.app.active(&Id::BrowseArtists).ok();
+                }
+
+                self.layout = TermusicLayout::Browse;
+                self.browse_sync_artists();
+                self.playlist_switch_layout();
+                None
+            }
             _ => None,
         }
     }
+    fn update_browse(&mut self, msg: &BrowseMsg) {
+        match msg {
+            BrowseMsg::ArtistsBlurDown | BrowseMsg::TracksBlurUp => {
+                self.app.active(&Id::BrowseAlbums).ok();
+            }
+            BrowseMsg::AlbumsBlurDown => {
+                self.app.active(&Id::BrowseTracks).ok();
+            }
+            BrowseMsg::AlbumsBlurUp => {
+                self.app.active(&Id::BrowseArtists).ok();
+            }
+            BrowseMsg::TracksBlurDown | BrowseMsg::ArtistsBlurUp => {
+                self.app.active(&Id::Playlist).ok();
+            }
+            BrowseMsg::ArtistSelected(index) => self.browse_select_artist(*index),
+            BrowseMsg::AlbumHighlighted(index) => self.browse_highlight_album(*index),
+            BrowseMsg::AlbumSelected(index) => self.browse_select_album(*index),
+            BrowseMsg::AddPlaylist(index) => {
+                if let Some(track) = self.browse_tracks.get(*index) {
+                    let file = track.file.clone();
+                    if let Err(e) = self.playlist_add(&file) {
+                        self.mount_error_popup(format!("Add playlist error: {e}"));
+                    }
+                }
+            }
+            BrowseMsg::AddAllToPlaylist => {
+                let browse_tracks = self.browse_tracks.clone();
+                self.playlist_add_all_from_db(&browse_tracks);
+            }
+        }
+    }
+
     fn update_database_list(&mut self, msg: &DBMsg) -> Option<Msg> {
         match msg {
             DBMsg::CriteriaBlurDown | DBMsg::SearchTracksBlurUp => {
@@ -582,6 +800,22 @@ impl Model {
                 let db_search_tracks = self.db_search_tracks.clone();
                 self.playlist_add_all_from_db(&db_search_tracks);
             }
+            DBMsg::DeleteTrack(index) => {
+                if let Err(e) = self.database_before_delete(*index) {
+                    self.mount_error_popup(format!("Delete error: {e}"));
+                }
+            }
+            DBMsg::DuplicatesFound(groups) => {
+                self.database_show_duplicates(groups.clone());
+            }
+            DBMsg::TagEditorRunAll => {
+                let files = self
+                    .db_search_tracks
+                    .iter()
+                    .map(|t| t.file.clone())
+                    .collect();
+                self.mount_tageditor_batch(files);
+            }
         }
         None
     }
@@ -612,6 +846,11 @@ impl Model {
                     self.mount_error_popup(format!("Remove root error: {e}"));
                 }
             }
+            LIMsg::Organize(node) => {
+                if let Err(e) = self.library_organize_preview(node) {
+                    self.mount_error_popup(format!("Organize error: {e}"));
+                }
+            }
         }
     }
 
@@ -673,6 +912,10 @@ impl Model {
                 self.mount_search_playlist();
                 self.playlist_update_search("*");
             }
+            GSMsg::PopupShowTranscript => {
+                self.mount_search_transcript();
+                self.podcast_update_search_transcripts("");
+            }
 
             GSMsg::PopupUpdateLibrary(input) => self.library_update_search(input),
 
@@ -680,6 +923,8 @@ impl Model {
 
             GSMsg::PopupUpdateDatabase(input) => self.database_update_search(input),
 
+            GSMsg::PopupUpdateTranscript(input) => self.podcast_update_search_transcripts(input),
+
             GSMsg::InputBlur => {
                 if self.app.mounted(&Id::GeneralSearchTable) {
                     self.app.active(&Id::GeneralSearchTable).ok();
@@ -735,6 +980,16 @@ impl Model {
                     self.mount_error_popup(format!("db add playlist error: {e}"));
                 };
             }
+            GSMsg::PopupCloseOkTranscriptLocate => {
+                self.app.umount(&Id::GeneralSearchInput).ok();
+                self.app.umount(&Id::GeneralSearchTable).ok();
+                self.general_search_after_transcript_locate();
+            }
+            GSMsg::PopupCloseTranscriptAddPlaylist => {
+                if let Err(e) = self.general_search_after_transcript_add_playlist() {
+                    self.mount_error_popup(format!("Error add episode: {e}"));
+                };
+            }
         }
     }
     fn update_delete_confirmation(&mut self, msg: &Msg) -> Option<Msg> {
@@ -760,6 +1015,9 @@ impl Model {
                 if let Err(e) = self.library_delete_song() {
                     self.mount_error_popup(format!("Delete error: {e}"));
                 };
+                if self.layout == TermusicLayout::DataBase {
+                    self.database_after_delete();
+                }
             }
             _ => {}
         }
@@ -793,6 +1051,7 @@ impl Model {
                 TermusicLayout::TreeView => assert!(self.app.active(&Id::Library).is_ok()),
                 TermusicLayout::DataBase => assert!(self.app.active(&Id::DBListCriteria).is_ok()),
                 TermusicLayout::Podcast => assert!(self.app.active(&Id::Lyric).is_ok()),
+                TermusicLayout::Browse => assert!(self.app.active(&Id::BrowseArtists).is_ok()),
             },
             PLMsg::NextSong => {
                 self.command(&PlayerCmd::SkipNext);
@@ -827,6 +1086,7 @@ impl Model {
                     assert!(self.app.active(&Id::DBListSearchTracks).is_ok());
                 }
                 TermusicLayout::Podcast => assert!(self.app.active(&Id::Episode).is_ok()),
+                TermusicLayout::Browse => assert!(self.app.active(&Id::BrowseTracks).is_ok()),
             },
         }
     }
@@ -959,7 +1219,9 @@ impl Model {
                     self.update_layout(&Msg::LayoutPodCast);
                 }
                 Some(MediaType::Music | MediaType::LiveRadio) => match self.layout {
-                    TermusicLayout::TreeView | TermusicLayout::DataBase => {}
+                    TermusicLayout::TreeView
+                    | TermusicLayout::DataBase
+                    | TermusicLayout::Browse => {}
                     TermusicLayout::Podcast => {
                         self.update_layout(&Msg::LayoutTreeView);
                     }
@@ -1,7 +1,8 @@
 use crate::ui::components::{
-    DBListCriteria, DBListSearchResult, DBListSearchTracks, DownloadSpinner, EpisodeList,
-    ErrorPopup, FeedsList, GSInputPopup, GSTablePopup, GlobalListener, HelpPopup, LabelSpan, Lyric,
-    MessagePopup, MusicLibrary, Playlist, PodcastAddPopup, Progress, QuitPopup,
+    BrowseAlbums, BrowseArtists, BrowseTracks, DBListCriteria, DBListSearchResult,
+    DBListSearchTracks, DownloadSpinner, EpisodeList, ErrorPopup, FeedsList, GSInputPopup,
+    GSTablePopup, GlobalListener, HelpPopup, LabelSpan, Lyric, MessagePopup, MusicLibrary,
+    Playlist, PodcastAddPopup, PodcastFilterPopup, PodcastPolicyPopup, Progress, QuitPopup,
     SavePlaylistConfirm, SavePlaylistPopup, Source, YSInputPopup, YSTablePopup,
 };
 use crate::ui::model::{ConfigEditorLayout, Model, TermusicLayout};
@@ -32,6 +33,7 @@ use std::time::{Duration, Instant};
  * SOFTWARE.
  */
 use termusiclib::config::Settings;
+use termusiclib::podcast::policy::Policy;
 use termusiclib::types::{DBMsg, Id, IdConfigEditor, IdTagEditor, Msg, PCMsg};
 use termusiclib::utils::{
     draw_area_in_absolute, draw_area_in_relative, draw_area_top_right_absolute, get_parent_folder,
@@ -46,6 +48,7 @@ use tuirealm::EventListenerCfg;
 use tuirealm::{Frame, State, StateValue};
 
 impl Model {
+    #[allow(clippy::too_many_lines)]
     pub fn init_app(tree: &Tree, config: &Settings) -> Application<Id, Msg, NoUserEvent> {
         // Setup application
         // NOTE: NoUserEvent is a shorthand to tell tui-realm we're not going to use any custom user event
@@ -99,6 +102,27 @@ impl Model {
                 vec![]
             )
             .is_ok());
+        assert!(app
+            .mount(
+                Id::BrowseArtists,
+                Box::new(BrowseArtists::new(config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(app
+            .mount(
+                Id::BrowseAlbums,
+                Box::new(BrowseAlbums::new(config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(app
+            .mount(
+                Id::BrowseTracks,
+                Box::new(BrowseTracks::new(config)),
+                vec![]
+            )
+            .is_ok());
         assert!(app
             .mount(Id::Playlist, Box::new(Playlist::new(config)), vec![])
             .is_ok());
@@ -177,6 +201,7 @@ impl Model {
                 TermusicLayout::TreeView => self.view_layout_treeview(),
                 TermusicLayout::DataBase => self.view_layout_database(),
                 TermusicLayout::Podcast => self.view_layout_podcast(),
+                TermusicLayout::Browse => self.view_layout_browse(),
             }
         }
     }
@@ -282,6 +307,61 @@ impl Model {
             .is_ok());
     }
 
+    pub fn view_layout_browse(&mut self) {
+        assert!(self
+            .terminal
+            .raw_mut()
+            .draw(|f| {
+                let chunks_main = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints([Constraint::Min(2), Constraint::Length(1)].as_ref())
+                    .split(f.size());
+                let chunks_left = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .margin(0)
+                    .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)].as_ref())
+                    .split(chunks_main[0]);
+
+                let chunks_left_sections = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints(
+                        [
+                            Constraint::Ratio(1, 3),
+                            Constraint::Ratio(1, 3),
+                            Constraint::Ratio(1, 3),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(chunks_left[0]);
+                let chunks_right = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints(
+                        [
+                            Constraint::Min(2),
+                            Constraint::Length(3),
+                            Constraint::Length(4),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(chunks_left[1]);
+
+                self.app
+                    .view(&Id::BrowseArtists, f, chunks_left_sections[0]);
+                self.app.view(&Id::BrowseAlbums, f, chunks_left_sections[1]);
+                self.app.view(&Id::BrowseTracks, f, chunks_left_sections[2]);
+
+                self.app.view(&Id::Playlist, f, chunks_right[0]);
+                self.app.view(&Id::Progress, f, chunks_right[1]);
+                self.app.view(&Id::Lyric, f, chunks_right[2]);
+                self.app.view(&Id::Label, f, chunks_main[1]);
+                Self::view_layout_commons(f, &mut self.app, self.download_tracker.visible());
+            })
+            .is_ok());
+    }
+
     pub fn view_layout_treeview(&mut self) {
         assert!(self
             .terminal
@@ -367,6 +447,14 @@ impl Model {
             let popup = draw_area_in_relative(f.size(), 88, 91);
             f.render_widget(Clear, popup);
             app.view(&Id::HelpPopup, f, popup);
+        } else if app.mounted(&Id::StatsPopup) {
+            let popup = draw_area_in_relative(f.size(), 88, 91);
+            f.render_widget(Clear, popup);
+            app.view(&Id::StatsPopup, f, popup);
+        } else if app.mounted(&Id::OrganizePopup) {
+            let popup = draw_area_in_relative(f.size(), 88, 91);
+            f.render_widget(Clear, popup);
+            app.view(&Id::OrganizePopup, f, popup);
         } else if app.mounted(&Id::DeleteConfirmRadioPopup) {
             let popup = draw_area_in_absolute(f.size(), 30, 3);
             f.render_widget(Clear, popup);
@@ -410,6 +498,14 @@ impl Model {
             let popup = draw_area_in_relative(f.size(), 65, 68);
             f.render_widget(Clear, popup);
             app.view(&Id::PodcastSearchTablePopup, f, popup);
+        } else if app.mounted(&Id::PodcastQueueTablePopup) {
+            let popup = draw_area_in_relative(f.size(), 65, 68);
+            f.render_widget(Clear, popup);
+            app.view(&Id::PodcastQueueTablePopup, f, popup);
+        } else if app.mounted(&Id::PodcastFailedTablePopup) {
+            let popup = draw_area_in_relative(f.size(), 65, 68);
+            f.render_widget(Clear, popup);
+            app.view(&Id::PodcastFailedTablePopup, f, popup);
         } else if app.mounted(&Id::SavePlaylistPopup) {
             let popup = draw_area_in_absolute(f.size(), 76, 6);
             f.render_widget(Clear, popup);
@@ -427,6 +523,18 @@ impl Model {
             let popup = draw_area_in_absolute(f.size(), 65, 3);
             f.render_widget(Clear, popup);
             app.view(&Id::PodcastAddPopup, f, popup);
+        } else if app.mounted(&Id::PodcastPolicyPopup) {
+            let popup = draw_area_in_absolute(f.size(), 75, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::PodcastPolicyPopup, f, popup);
+        } else if app.mounted(&Id::PodcastFilterPopup) {
+            let popup = draw_area_in_absolute(f.size(), 90, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::PodcastFilterPopup, f, popup);
+        } else if app.mounted(&Id::PodcastQueuePopup) {
+            let popup = draw_area_in_absolute(f.size(), 75, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::PodcastQueuePopup, f, popup);
         }
         if app.mounted(&Id::MessagePopup) {
             let popup = draw_area_top_right_absolute(f.size(), 25, 4);
@@ -520,6 +628,26 @@ impl Model {
         }
     }
 
+    pub fn mount_search_transcript(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::GeneralSearchInput,
+                Box::new(GSInputPopup::new(Source::Transcript, &self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .remount(
+                Id::GeneralSearchTable,
+                Box::new(GSTablePopup::new(Source::Transcript, &self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::GeneralSearchInput).is_ok());
+    }
+
     pub fn mount_search_database(&mut self) {
         assert!(self
             .app
@@ -596,6 +724,7 @@ impl Model {
         }
     }
 
+    #[allow(clippy::too_many_lines)]
     pub fn mount_label_help(&mut self) {
         assert!(self
             .app
@@ -688,6 +817,20 @@ impl Model {
                                 .library_highlight()
                                 .unwrap_or(Color::Cyan))
                             .bold(),
+                        TextSpan::new(" Browse: ")
+                            .fg(self
+                                .config
+                                .style_color_symbol
+                                .library_foreground()
+                                .unwrap_or(Color::Blue))
+                            .bold(),
+                        TextSpan::new(format!("<{}>", self.config.keys.global_layout_browse))
+                            .fg(self
+                                .config
+                                .style_color_symbol
+                                .library_highlight()
+                                .unwrap_or(Color::Cyan))
+                            .bold(),
                     ]
                 )),
                 Vec::default(),
@@ -813,6 +956,50 @@ impl Model {
         }
     }
 
+    pub fn mount_podcast_policy_popup(&mut self, policy: &Policy) {
+        assert!(self
+            .app
+            .remount(
+                Id::PodcastPolicyPopup,
+                Box::new(PodcastPolicyPopup::new(
+                    &self.config.style_color_symbol,
+                    policy
+                )),
+                vec![]
+            )
+            .is_ok());
+
+        assert!(self.app.active(&Id::PodcastPolicyPopup).is_ok());
+    }
+
+    pub fn umount_podcast_policy_popup(&mut self) {
+        if self.app.mounted(&Id::PodcastPolicyPopup) {
+            assert!(self.app.umount(&Id::PodcastPolicyPopup).is_ok());
+        }
+    }
+
+    pub fn mount_podcast_filter_popup(&mut self, rules: &str) {
+        assert!(self
+            .app
+            .remount(
+                Id::PodcastFilterPopup,
+                Box::new(PodcastFilterPopup::new(
+                    &self.config.style_color_symbol,
+                    rules
+                )),
+                vec![]
+            )
+            .is_ok());
+
+        assert!(self.app.active(&Id::PodcastFilterPopup).is_ok());
+    }
+
+    pub fn umount_podcast_filter_popup(&mut self) {
+        if self.app.mounted(&Id::PodcastFilterPopup) {
+            assert!(self.app.umount(&Id::PodcastFilterPopup).is_ok());
+        }
+    }
+
     pub fn show_message_timeout_label_help<S: AsRef<str>>(
         &mut self,
         active_msg: S,
@@ -1,10 +1,11 @@
 use anyhow::Result;
 use termusicplayback::player::music_player_client::MusicPlayerClient;
 use termusicplayback::player::{
-    CycleLoopRequest, GetProgressRequest, GetProgressResponse, PlaySelectedRequest,
-    ReloadConfigRequest, ReloadPlaylistRequest, SeekBackwardRequest, SeekForwardRequest,
-    SkipNextRequest, SkipPreviousRequest, SpeedDownRequest, SpeedUpRequest, ToggleGaplessRequest,
-    TogglePauseRequest, VolumeDownRequest, VolumeUpRequest,
+    CycleLoopRequest, GetProgressRequest, GetProgressResponse, NextChapterRequest,
+    PlaySelectedRequest, PreviousChapterRequest, ReloadConfigRequest, ReloadPlaylistRequest,
+    SeekBackwardRequest, SeekForwardRequest, SeekToRequest, SkipNextRequest, SkipPreviousRequest,
+    SpeedDownRequest, SpeedUpRequest, ToggleGaplessRequest, TogglePauseRequest, VolumeDownRequest,
+    VolumeUpRequest,
 };
 use termusicplayback::Status;
 use tonic::transport::Channel;
@@ -106,6 +107,30 @@ impl Playback {
         Ok((response.position, response.duration))
     }
 
+    pub async fn seek_to(&mut self, position: u32) -> Result<(u32, u32)> {
+        let request = tonic::Request::new(SeekToRequest { position });
+        let response = self.client.seek_to(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {:?}", response);
+        Ok((response.position, response.duration))
+    }
+
+    pub async fn next_chapter(&mut self) -> Result<(u32, u32)> {
+        let request = tonic::Request::new(NextChapterRequest {});
+        let response = self.client.next_chapter(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {:?}", response);
+        Ok((response.position, response.duration))
+    }
+
+    pub async fn previous_chapter(&mut self) -> Result<(u32, u32)> {
+        let request = tonic::Request::new(PreviousChapterRequest {});
+        let response = self.client.previous_chapter(request).await?;
+        let response = response.into_inner();
+        info!("Got response from server: {:?}", response);
+        Ok((response.position, response.duration))
+    }
+
     pub async fn reload_config(&mut self) -> Result<()> {
         let request = tonic::Request::new(ReloadConfigRequest {});
         let response = self.client.reload_config(request).await?;